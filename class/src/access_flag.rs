@@ -33,9 +33,19 @@ pub trait WithAccessFlags {
 }
 
 impl AccessFlags {
+    pub fn new(flag_types: &[AccessFlagType]) -> Self {
+        Self {
+            types: flag_types.iter().cloned().collect(),
+        }
+    }
+
     pub fn has_type(self: &Self, flag_type: &AccessFlagType) -> bool {
         self.types.contains(flag_type)
     }
+
+    pub fn bits(self: &Self) -> u16 {
+        self.types.iter().fold(0, |bits, flag_type| bits | *flag_type as u16)
+    }
 }
 
 impl Streamable<SourceStream, AccessFlags> for AccessFlags {
@@ -1,4 +1,4 @@
-use crate::{ClassFile, ClassResolvable, SourceStream};
+use crate::{ClassFile, ClassResolvable, Constant, SourceStream};
 
 use wasmjvm_common::{WasmJVMError, Streamable, Parsable, FromData};
 use std::slice::Iter;
@@ -47,9 +47,18 @@ pub enum AttributeBody {
     SourceFile {
         sourcefile: String,
     },
+    ConstantValue {
+        constant: Constant,
+    },
     Signature {
         signature_index: u16
     },
+    NestHost {
+        host_class: String,
+    },
+    NestMembers {
+        classes: Vec<String>,
+    },
     User {
         info: Vec<u8>,
     },
@@ -61,6 +70,28 @@ impl Attribute {
     }
 }
 
+impl CodeBody {
+    pub fn line_number(self: &Self, pc: usize) -> Option<u16> {
+        let mut closest: Option<&LineNumberEntry> = None;
+
+        for attribute in self.attributes.iter() {
+            if let AttributeBody::LineNumberTable { line_number_table } = &attribute.body {
+                for entry in line_number_table.iter() {
+                    if entry.start_pc as usize > pc {
+                        continue;
+                    }
+
+                    if closest.map_or(true, |closest| entry.start_pc >= closest.start_pc) {
+                        closest = Some(entry);
+                    }
+                }
+            }
+        }
+
+        closest.map(|entry| entry.line_number)
+    }
+}
+
 impl ClassResolvable<Attribute> for AttributeInfo {
     fn resolve(self: &Self, class_file: &ClassFile) -> Result<Attribute, WasmJVMError> {
         let name = class_file
@@ -106,11 +137,36 @@ impl ClassResolvable<Attribute> for AttributeInfo {
 
                 AttributeBody::SourceFile { sourcefile }
             }
+            "ConstantValue" => {
+                let constant_index: u16 = source.parse()?;
+                let constant = class_file.constant(constant_index as usize)?;
+
+                AttributeBody::ConstantValue { constant }
+            }
             "Signature" => {
                 let signature_index: u16 = source.parse()?;
 
                 AttributeBody::Signature { signature_index }
             }
+            "NestHost" => {
+                let host_class_index: u16 = source.parse()?;
+                let host_class = class_file
+                    .constant(host_class_index as usize)?
+                    .to_string()?;
+
+                AttributeBody::NestHost { host_class }
+            }
+            "NestMembers" => {
+                let number_of_classes: u16 = source.parse()?;
+                let mut classes = Vec::with_capacity(number_of_classes as usize);
+
+                for _ in 0..number_of_classes {
+                    let class_index: u16 = source.parse()?;
+                    classes.push(class_file.constant(class_index as usize)?.to_string()?);
+                }
+
+                AttributeBody::NestMembers { classes }
+            }
             _ => AttributeBody::User {
                 info: self.info.clone(),
             },
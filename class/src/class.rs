@@ -1,6 +1,6 @@
 use crate::{
-    AccessFlags, Attribute, AttributeInfo, Constant, ConstantInfo, Field, FieldInfo, Interface,
-    InterfaceInfo, Method, MethodInfo, MethodRef, SourceStream, WithAccessFlags, WithAttributes,
+    AccessFlagType, AccessFlags, Attribute, AttributeBody, AttributeInfo, Constant, ConstantInfo, Field, FieldInfo, Interface,
+    InterfaceInfo, Method, MethodInfo, MethodRef, SingleType, SourceStream, WithAccessFlags, WithAttributes,
     WithDescriptor, WithFields, WithInterfaces, WithMethods,
 };
 
@@ -34,6 +34,51 @@ pub struct Class {
 }
 
 impl Class {
+    pub fn new_array(name: &str) -> Class {
+        Class {
+            constant_pool: Vec::new(),
+            access_flags: AccessFlags::new(&[
+                AccessFlagType::Public,
+                AccessFlagType::Final,
+                AccessFlagType::Abstract,
+            ]),
+            this_class: name.to_string(),
+            super_class: Some("java/lang/Object".to_string()),
+            interfaces: vec![
+                Interface::new("java/lang/Cloneable"),
+                Interface::new("java/io/Serializable"),
+            ],
+            fields: Vec::new(),
+            methods: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn new_primitive(name: &str) -> Class {
+        Class {
+            constant_pool: Vec::new(),
+            access_flags: AccessFlags::new(&[
+                AccessFlagType::Public,
+                AccessFlagType::Final,
+                AccessFlagType::Abstract,
+            ]),
+            this_class: name.to_string(),
+            super_class: None,
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn is_array(self: &Self) -> bool {
+        self.this_class.starts_with('[')
+    }
+
+    pub fn is_primitive(self: &Self) -> bool {
+        self.super_class.is_none() && SingleType::primitive(&self.this_class).is_some()
+    }
+
     pub fn from_file<F: std::io::Read>(cursor: F) -> Result<Class, WasmJVMError> {
         let mut stream = SourceStream::from_file(cursor)?;
         Self::from_stream(&mut stream)
@@ -73,8 +118,16 @@ impl Class {
         &self.constant_pool
     }
 
-    pub fn constant(self: &Self, index: usize) -> &Constant {
-        &self.constant_pool[index - 1]
+    pub fn constant(self: &Self, index: usize) -> Result<&Constant, WasmJVMError> {
+        index
+            .checked_sub(1)
+            .and_then(|slot| self.constant_pool.get(slot))
+            .ok_or_else(|| {
+                WasmJVMError::ClassFormatError(format!(
+                    "Constant pool index {} out of range in {}",
+                    index, self.this_class
+                ))
+            })
     }
 
     pub fn access_flags(self: &Self) -> &AccessFlags {
@@ -88,6 +141,36 @@ impl Class {
     pub fn super_class(self: &Self) -> &Option<String> {
         &self.super_class
     }
+
+    pub fn source_file(self: &Self) -> Option<&str> {
+        if let Ok(attribute) = self.attribute("SourceFile") {
+            if let AttributeBody::SourceFile { sourcefile } = &attribute.body {
+                return Some(sourcefile.as_str());
+            }
+        }
+
+        None
+    }
+
+    pub fn nest_host(self: &Self) -> Option<&str> {
+        if let Ok(attribute) = self.attribute("NestHost") {
+            if let AttributeBody::NestHost { host_class } = &attribute.body {
+                return Some(host_class.as_str());
+            }
+        }
+
+        None
+    }
+
+    pub fn nest_members(self: &Self) -> Vec<&str> {
+        if let Ok(attribute) = self.attribute("NestMembers") {
+            if let AttributeBody::NestMembers { classes } = &attribute.body {
+                return classes.iter().map(|class| class.as_str()).collect();
+            }
+        }
+
+        Vec::new()
+    }
 }
 
 impl ClassFile {
@@ -100,7 +183,11 @@ impl ClassFile {
     }
 
     pub fn constant(self: &Self, index: usize) -> Result<Constant, WasmJVMError> {
-        self.constant_pool[index - 1].resolve(self)
+        index
+            .checked_sub(1)
+            .and_then(|slot| self.constant_pool.get(slot))
+            .ok_or_else(|| WasmJVMError::ClassFormatError(format!("Constant pool index {} out of range", index)))?
+            .resolve(self)
     }
 
     pub fn resolve<T, K: ClassResolvable<T>>(self: &Self, target: &K) -> Result<T, WasmJVMError> {
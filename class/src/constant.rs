@@ -38,7 +38,7 @@ impl ConstantTag {
             15 => Ok(ConstantTag::MethodHandle),
             16 => Ok(ConstantTag::MethodType),
             18 => Ok(ConstantTag::InvokeDynamic),
-            _ => unreachable!(),
+            _ => Err(WasmJVMError::ClassFormatError(format!("Unsupported constant pool tag {}", tag))),
         }
     }
 }
@@ -164,18 +164,46 @@ impl Constant {
     }
 }
 
+/// Decodes the JVM's modified UTF-8 into UTF-16 code units (JVMS 4.4.7).
+fn decode_modified_utf8(bytes: &[u8]) -> Result<Vec<u16>, WasmJVMError> {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    let continuation = |index: usize| -> Result<u16, WasmJVMError> {
+        match bytes.get(index) {
+            Some(byte) if byte & 0xc0 == 0x80 => Ok((byte & 0x3f) as u16),
+            _ => Err(WasmJVMError::ClassFormatError(format!("String resolve {:?}", bytes))),
+        }
+    };
+
+    while i < bytes.len() {
+        let byte = bytes[i] as u16;
+
+        if byte & 0x80 == 0 {
+            units.push(byte);
+            i += 1;
+        } else if byte & 0xe0 == 0xc0 {
+            units.push((byte & 0x1f) << 6 | continuation(i + 1)?);
+            i += 2;
+        } else if byte & 0xf0 == 0xe0 {
+            units.push((byte & 0x0f) << 12 | continuation(i + 1)? << 6 | continuation(i + 2)?);
+            i += 3;
+        } else {
+            return Err(WasmJVMError::ClassFormatError(format!("String resolve {:?}", bytes)));
+        }
+    }
+
+    Ok(units)
+}
+
 impl ClassResolvable<Constant> for ConstantInfo {
     fn resolve(self: &Self, class_file: &ClassFile) -> Result<Constant, WasmJVMError> {
         match self {
             ConstantInfo::Empty => Ok(Constant::Empty),
             ConstantInfo::Utf8(u8_str) => {
-                let result = String::from_utf8(u8_str.to_vec());
+                let units = decode_modified_utf8(u8_str)?;
 
-                if let Ok(string) = result {
-                    Ok(Constant::Utf8(string))
-                } else {
-                    Err(WasmJVMError::ClassFormatError(format!("String resolve {:?}", u8_str)))
-                }
+                Ok(Constant::Utf8(String::from_utf16_lossy(&units)))
             }
             ConstantInfo::Integer(b0) => Ok(Constant::Integer(b0.clone() as i32)),
             ConstantInfo::Float(b0) => Ok(Constant::Float(f32::from_bits(b0.clone()))),
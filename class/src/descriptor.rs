@@ -24,6 +24,103 @@ pub enum SingleType {
     Void,
 }
 
+impl SingleType {
+    pub fn descriptor(self: &Self) -> String {
+        match self {
+            SingleType::Byte => "B".to_string(),
+            SingleType::Char => "C".to_string(),
+            SingleType::Double => "D".to_string(),
+            SingleType::Float => "F".to_string(),
+            SingleType::Int => "I".to_string(),
+            SingleType::Long => "J".to_string(),
+            SingleType::Object(name) => format!("L{};", name),
+            SingleType::Short => "S".to_string(),
+            SingleType::Boolean => "Z".to_string(),
+            SingleType::Void => "V".to_string(),
+        }
+    }
+
+    pub fn name(self: &Self) -> String {
+        match self {
+            SingleType::Byte => "byte".to_string(),
+            SingleType::Char => "char".to_string(),
+            SingleType::Double => "double".to_string(),
+            SingleType::Float => "float".to_string(),
+            SingleType::Int => "int".to_string(),
+            SingleType::Long => "long".to_string(),
+            SingleType::Object(name) => name.clone(),
+            SingleType::Short => "short".to_string(),
+            SingleType::Boolean => "boolean".to_string(),
+            SingleType::Void => "void".to_string(),
+        }
+    }
+
+    /// The primitive type (or void) spelled `name` in Java source, as used by `int.class`.
+    pub fn primitive(name: &str) -> Option<SingleType> {
+        match name {
+            "byte" => Some(SingleType::Byte),
+            "char" => Some(SingleType::Char),
+            "double" => Some(SingleType::Double),
+            "float" => Some(SingleType::Float),
+            "int" => Some(SingleType::Int),
+            "long" => Some(SingleType::Long),
+            "short" => Some(SingleType::Short),
+            "boolean" => Some(SingleType::Boolean),
+            "void" => Some(SingleType::Void),
+            _ => None,
+        }
+    }
+}
+
+impl Type {
+    pub fn size(self: &Self) -> usize {
+        match self {
+            Type::Single(SingleType::Long) | Type::Single(SingleType::Double) => 2,
+            Type::Single(SingleType::Void) => 0,
+            _ => 1,
+        }
+    }
+
+    pub fn from_class_name(name: &str) -> Result<Type, WasmJVMError> {
+        if name.starts_with('[') {
+            Ok(Descriptor::from_str(name)?.output().clone())
+        } else {
+            Ok(Type::Single(SingleType::Object(name.to_string())))
+        }
+    }
+
+    pub fn descriptor(self: &Self) -> String {
+        match self {
+            Type::Single(single) => single.descriptor(),
+            Type::Array(single, dimensions) => {
+                format!("{}{}", "[".repeat(*dimensions), single.descriptor())
+            }
+        }
+    }
+
+    pub fn class_name(self: &Self) -> String {
+        match self {
+            Type::Single(SingleType::Object(name)) => name.clone(),
+            _ => self.descriptor(),
+        }
+    }
+
+    pub fn array(self: &Self) -> Type {
+        match self {
+            Type::Single(single) => Type::Array(single.clone(), 1),
+            Type::Array(single, dimensions) => Type::Array(single.clone(), dimensions + 1),
+        }
+    }
+
+    pub fn component(self: &Self) -> Option<Type> {
+        match self {
+            Type::Single(..) => None,
+            Type::Array(single, 1) => Some(Type::Single(single.clone())),
+            Type::Array(single, dimensions) => Some(Type::Array(single.clone(), dimensions - 1)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash)]
 pub struct Descriptor {
     parameters: Vec<Type>,
@@ -57,6 +154,12 @@ impl Descriptor {
         &self.output
     }
 
+    pub fn descriptor(self: &Self) -> String {
+        let parameters: Vec<String> = self.parameters.iter().map(|r#type| r#type.descriptor()).collect();
+
+        format!("({}){}", parameters.join(""), self.output.descriptor())
+    }
+
     pub fn from_constant(constant: &Constant) -> Result<Descriptor, WasmJVMError> {
         match constant {
             Constant::Utf8(string) | Constant::String(string) => Self::from_str(string.as_str()),
@@ -65,8 +168,14 @@ impl Descriptor {
         }
     }
 
+    fn byte(string: &[u8], offset: usize) -> Result<u8, WasmJVMError> {
+        string.get(offset).copied().ok_or_else(|| {
+            WasmJVMError::ClassFormatError(format!("Descriptor {:?} ends early", String::from_utf8_lossy(string)))
+        })
+    }
+
     fn parse_type(string: &[u8], mut offset: usize) -> Result<(Type, usize), WasmJVMError> {
-        let tag = string[offset];
+        let tag = Self::byte(string, offset)?;
         offset += 1;
 
         match tag {
@@ -79,7 +188,7 @@ impl Descriptor {
             b'L' => {
                 let mut vec_string = Vec::new();
 
-                while string[offset] as char != ';' {
+                while Self::byte(string, offset)? as char != ';' {
                     vec_string.push(string[offset]);
                     offset += 1;
                 }
@@ -98,7 +207,7 @@ impl Descriptor {
             b'V' => Ok((Type::Single(SingleType::Void), offset)),
             b'[' => {
                 let mut array_size: usize = 1;
-                while string[offset] as char == '[' {
+                while Self::byte(string, offset)? as char == '[' {
                     array_size += 1;
                     offset += 1;
                 }
@@ -110,7 +219,10 @@ impl Descriptor {
                     _ => unreachable!(),
                 }
             }
-            _ => unreachable!(),
+            _ => Err(WasmJVMError::ClassFormatError(format!(
+                "Invalid descriptor {:?}",
+                String::from_utf8_lossy(string)
+            ))),
         }
     }
 
@@ -120,10 +232,10 @@ impl Descriptor {
         let mut parameters = Vec::new();
         let mut offset = 0;
 
-        if string_bytes[offset] as char == '(' {
+        if Self::byte(string_bytes, offset)? as char == '(' {
             offset += 1;
 
-            while string_bytes[offset] as char != ')' {
+            while Self::byte(string_bytes, offset)? as char != ')' {
                 let (t, new_offset) = Self::parse_type(string_bytes, offset)?;
                 parameters.push(t);
                 offset = new_offset;
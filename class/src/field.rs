@@ -1,6 +1,6 @@
 use crate::{
-    AccessFlags, Attribute, AttributeInfo, ClassFile, ClassResolvable, Constant, Descriptor,
-    SourceStream, WithAccessFlags, WithAttributes, WithDescriptor, AccessFlagType,
+    AccessFlags, Attribute, AttributeBody, AttributeInfo, ClassFile, ClassResolvable, Constant, Descriptor,
+    SourceStream, Type, WithAccessFlags, WithAttributes, WithDescriptor, AccessFlagType,
 };
 
 use std::{result::Result, slice::Iter};
@@ -26,6 +26,20 @@ impl Field {
     pub fn name(self: &Self) -> &str {
         self.name.as_str()
     }
+
+    pub fn r#type(self: &Self) -> &Type {
+        self.descriptor.output()
+    }
+
+    pub fn constant_value(self: &Self) -> Option<&Constant> {
+        for attribute in self.attributes.iter() {
+            if let AttributeBody::ConstantValue { constant } = &attribute.body {
+                return Some(constant);
+            }
+        }
+
+        None
+    }
 }
 
 pub trait WithFields {
@@ -14,7 +14,13 @@ pub struct Interface {
 }
 
 impl Interface {
-    fn name(self: &Self) -> &str {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    pub fn name(self: &Self) -> &str {
         self.name.as_str()
     }
 }
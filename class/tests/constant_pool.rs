@@ -0,0 +1,53 @@
+use wasmjvm_class::Class;
+use wasmjvm_common::WasmJVMError;
+
+/// A class file with no members whose constant pool holds `Utf8 "Broken"` and `Class #name_index`.
+fn class_file(name_index: u16, this_class: u16) -> Vec<u8> {
+    let mut bytes = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 52, 0, 3];
+
+    bytes.push(1);
+    bytes.extend_from_slice(&6u16.to_be_bytes());
+    bytes.extend_from_slice(b"Broken");
+    bytes.push(7);
+    bytes.extend_from_slice(&name_index.to_be_bytes());
+
+    bytes.extend_from_slice(&0x0021u16.to_be_bytes());
+    bytes.extend_from_slice(&this_class.to_be_bytes());
+    // No super class, interfaces, fields, methods or attributes.
+    bytes.extend_from_slice(&[0; 10]);
+
+    bytes
+}
+
+fn assert_class_format_error(bytes: Vec<u8>) {
+    match Class::from_file(bytes.as_slice()) {
+        Err(WasmJVMError::ClassFormatError(..)) => {}
+        Err(error) => panic!("expected a ClassFormatError, got {:?}", error),
+        Ok(class) => panic!("expected a ClassFormatError, got {}", class.this_class()),
+    }
+}
+
+#[test]
+fn loads_valid_constants() {
+    let class = Class::from_file(class_file(1, 2).as_slice()).expect("class should load");
+
+    assert_eq!(class.this_class(), "Broken");
+    assert!(class.constant(2).is_ok());
+    assert!(class.constant(0).is_err());
+    assert!(class.constant(3).is_err());
+}
+
+#[test]
+fn rejects_out_of_range_this_class() {
+    assert_class_format_error(class_file(1, 9));
+}
+
+#[test]
+fn rejects_zero_this_class() {
+    assert_class_format_error(class_file(1, 0));
+}
+
+#[test]
+fn rejects_zero_name_index() {
+    assert_class_format_error(class_file(0, 2));
+}
@@ -10,6 +10,7 @@ pub enum WasmJVMError {
     ArithmeticException(String),
     ArrayStoreException(String),
     ClassCastException(String),
+    CloneNotSupportedException(String),
     EnumConstantNotPresentException(String),
     IllegalArgumentException(String),
     IllegalCallerException(String),
@@ -18,6 +19,7 @@ pub enum WasmJVMError {
     LayerInstantiateException(String),
     NegativeArraySizeException(String),
     NullPointerException(String),
+    NumberFormatException(String),
     SecurityException(String),
     TypeNotPresentException(String),
     UnsupportedOperationException(String),
@@ -26,7 +28,97 @@ pub enum WasmJVMError {
     LinkageError(String),
     NoSuchFieldError(String),
     NoSuchMethodError(String),
+    NoClassDefFoundError(String),
+    IncompatibleClassChangeError(String),
+    IllegalAccessError(String),
     ClassFormatError(String),
+    VerifyError(String),
+    InternalError(String),
     UnhandledException(String),
-    OutOfHeap
+    OutOfHeap,
+    /// A fatal error together with the frame that was executing when it happened.
+    Frame {
+        class: String,
+        method: String,
+        pc: usize,
+        opcode: Option<String>,
+        error: Box<WasmJVMError>,
+    },
+}
+
+impl WasmJVMError {
+    pub fn message(self: &Self) -> Option<String> {
+        match self {
+            WasmJVMError::TODO(..) | WasmJVMError::OutOfHeap => None,
+            WasmJVMError::ClassNotFoundException(message)
+            | WasmJVMError::InstantiationException(message)
+            | WasmJVMError::NoSuchFieldException(message)
+            | WasmJVMError::NoSuchMethodException(message)
+            | WasmJVMError::ArithmeticException(message)
+            | WasmJVMError::ArrayStoreException(message)
+            | WasmJVMError::ClassCastException(message)
+            | WasmJVMError::CloneNotSupportedException(message)
+            | WasmJVMError::EnumConstantNotPresentException(message)
+            | WasmJVMError::IllegalArgumentException(message)
+            | WasmJVMError::IllegalCallerException(message)
+            | WasmJVMError::IllegalStateException(message)
+            | WasmJVMError::IndexOutOfBoundException(message)
+            | WasmJVMError::LayerInstantiateException(message)
+            | WasmJVMError::NegativeArraySizeException(message)
+            | WasmJVMError::NullPointerException(message)
+            | WasmJVMError::NumberFormatException(message)
+            | WasmJVMError::SecurityException(message)
+            | WasmJVMError::TypeNotPresentException(message)
+            | WasmJVMError::UnsupportedOperationException(message)
+            | WasmJVMError::OutOfMemoryError(message)
+            | WasmJVMError::StackOverflowError(message)
+            | WasmJVMError::LinkageError(message)
+            | WasmJVMError::NoSuchFieldError(message)
+            | WasmJVMError::NoSuchMethodError(message)
+            | WasmJVMError::NoClassDefFoundError(message)
+            | WasmJVMError::IncompatibleClassChangeError(message)
+            | WasmJVMError::IllegalAccessError(message)
+            | WasmJVMError::ClassFormatError(message)
+            | WasmJVMError::VerifyError(message)
+            | WasmJVMError::InternalError(message)
+            | WasmJVMError::UnhandledException(message) => Some(message.clone()),
+            WasmJVMError::Frame { error, .. } => error.message(),
+        }
+    }
+
+    /// Attaches the executing frame to an error, keeping the innermost frame if one is already set.
+    pub fn at(self: Self, class: &str, method: &str, pc: usize, opcode: Option<String>) -> Self {
+        match self {
+            WasmJVMError::Frame { .. } => self,
+            error => WasmJVMError::Frame {
+                class: class.to_string(),
+                method: method.to_string(),
+                pc,
+                opcode,
+                error: Box::new(error),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for WasmJVMError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmJVMError::Frame {
+                class,
+                method,
+                pc,
+                opcode,
+                error,
+            } => {
+                write!(f, "{} at {}.{} pc {}", error, class.replace('/', "."), method, pc)?;
+
+                match opcode {
+                    Some(opcode) => write!(f, " ({})", opcode),
+                    None => write!(f, " (native)"),
+                }
+            }
+            error => write!(f, "{:?}", error),
+        }
+    }
 }
@@ -3,12 +3,12 @@ package java.io;
 public class FileInputStream extends InputStream {
     private String path;
 
-    public FileInputStream(String path) {
+    public FileInputStream(String path) throws FileNotFoundException {
         this.path = path;
         this.nativeBind();
     }
 
-    private native void nativeBind();
+    private native void nativeBind() throws FileNotFoundException;
     private native int nativeRead();
 
     @Override
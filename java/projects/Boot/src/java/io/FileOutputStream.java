@@ -3,12 +3,12 @@ package java.io;
 public class FileOutputStream extends OutputStream {
     private String path;
 
-    public FileOutputStream(String path) {
+    public FileOutputStream(String path) throws FileNotFoundException {
         this.path = path;
         this.nativeBind();
     }
 
-    private native void nativeBind();
+    private native void nativeBind() throws FileNotFoundException;
     private native void nativeWrite(int value);
 
     @Override
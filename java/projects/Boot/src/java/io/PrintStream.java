@@ -7,7 +7,7 @@ public class PrintStream extends OutputStream {
         this.outputStream = outputStream;
     }
 
-    public PrintStream(String path) {
+    public PrintStream(String path) throws FileNotFoundException {
         this.outputStream = new FileOutputStream(path);
     }
 
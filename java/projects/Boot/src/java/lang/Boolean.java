@@ -0,0 +1,52 @@
+package java.lang;
+
+public final class Boolean {
+    public static final Class TYPE = Class.getPrimitiveClass("boolean");
+
+    public static final Boolean TRUE = new Boolean(true);
+    public static final Boolean FALSE = new Boolean(false);
+
+    private final boolean value;
+
+    public Boolean(boolean value) {
+        this.value = value;
+    }
+
+    public static Boolean valueOf(boolean value) {
+        return value ? TRUE : FALSE;
+    }
+
+    public static boolean parseBoolean(String string) {
+        return "true".equalsIgnoreCase(string);
+    }
+
+    public static Boolean valueOf(String string) {
+        return parseBoolean(string) ? TRUE : FALSE;
+    }
+
+    public static boolean getBoolean(String name) {
+        try {
+            return parseBoolean(System.getProperty(name));
+        } catch(IllegalArgumentException | NullPointerException exception) {
+            return false;
+        }
+    }
+
+    public boolean booleanValue() {
+        return this.value;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Boolean && ((Boolean)other).value == this.value;
+    }
+
+    public int hashCode() {
+        return this.value ? 1231 : 1237;
+    }
+
+    @Override
+    public String toString() {
+        return this.value ? "true" : "false";
+    }
+}
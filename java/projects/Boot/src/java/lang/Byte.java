@@ -0,0 +1,56 @@
+package java.lang;
+
+public final class Byte extends Number {
+    public static final Class TYPE = Class.getPrimitiveClass("byte");
+
+    public static final byte MIN_VALUE = -128;
+    public static final byte MAX_VALUE = 127;
+
+    private final byte value;
+
+    public Byte(byte value) {
+        this.value = value;
+    }
+
+    public static Byte valueOf(byte value) {
+        return new Byte(value);
+    }
+
+    public int intValue() {
+        return (int)this.value;
+    }
+
+    public long longValue() {
+        return (long)this.value;
+    }
+
+    public float floatValue() {
+        return (float)this.value;
+    }
+
+    public double doubleValue() {
+        return (double)this.value;
+    }
+
+    public byte byteValue() {
+        return this.value;
+    }
+
+    public short shortValue() {
+        return (short)this.value;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Byte && ((Byte)other).value == this.value;
+    }
+
+    public int hashCode() {
+        return (int)this.value;
+    }
+
+    @Override
+    public String toString() {
+        return new StringBuilder().append(this.value).toString();
+    }
+}
@@ -0,0 +1,66 @@
+package java.lang;
+
+public final class Character {
+    public static final Class TYPE = Class.getPrimitiveClass("char");
+
+    public static final char MIN_VALUE = '\u0000';
+    public static final char MAX_VALUE = '\uffff';
+    public static final char MIN_HIGH_SURROGATE = '\uD800';
+    public static final char MAX_HIGH_SURROGATE = '\uDBFF';
+    public static final char MIN_LOW_SURROGATE = '\uDC00';
+    public static final char MAX_LOW_SURROGATE = '\uDFFF';
+    public static final int MIN_SUPPLEMENTARY_CODE_POINT = 0x010000;
+    public static final int MAX_CODE_POINT = 0x10ffff;
+
+    private final char value;
+
+    public Character(char value) {
+        this.value = value;
+    }
+
+    public static Character valueOf(char value) {
+        return new Character(value);
+    }
+
+    public static boolean isHighSurrogate(char ch) {
+        return ch >= MIN_HIGH_SURROGATE && ch <= MAX_HIGH_SURROGATE;
+    }
+
+    public static boolean isLowSurrogate(char ch) {
+        return ch >= MIN_LOW_SURROGATE && ch <= MAX_LOW_SURROGATE;
+    }
+
+    public static boolean isSurrogate(char ch) {
+        return ch >= MIN_HIGH_SURROGATE && ch <= MAX_LOW_SURROGATE;
+    }
+
+    public static boolean isSupplementaryCodePoint(int codePoint) {
+        return codePoint >= MIN_SUPPLEMENTARY_CODE_POINT && codePoint <= MAX_CODE_POINT;
+    }
+
+    public static int charCount(int codePoint) {
+        return codePoint >= MIN_SUPPLEMENTARY_CODE_POINT ? 2 : 1;
+    }
+
+    public static int toCodePoint(char high, char low) {
+        return ((high - MIN_HIGH_SURROGATE) << 10) + (low - MIN_LOW_SURROGATE) + MIN_SUPPLEMENTARY_CODE_POINT;
+    }
+
+    public char charValue() {
+        return this.value;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Character && ((Character)other).value == this.value;
+    }
+
+    public int hashCode() {
+        return (int)this.value;
+    }
+
+    @Override
+    public String toString() {
+        return new StringBuilder().append(this.value).toString();
+    }
+}
@@ -1,7 +1,90 @@
 package java.lang;
 
+import java.lang.reflect.*;
+
 public class Class {
     public native String getName();
+    public native String getSimpleName();
+
+    private static native Class forName0(String name);
+    private native void initialize();
+
+    public static Class forName(String name) throws ClassNotFoundException {
+        Class clazz = forName0(name);
+
+        if(clazz == null) {
+            throw new ClassNotFoundException(name);
+        }
+
+        clazz.initialize();
+
+        return clazz;
+    }
+
+    static native Class getPrimitiveClass(String name);
+
+    public native boolean isPrimitive();
+    public native boolean isArray();
+    public native Class getComponentType();
+
+    public boolean isInterface() {
+        return Modifier.isInterface(this.getModifiers());
+    }
+
+    public native Class getSuperclass();
+    public native Class[] getInterfaces();
+    public native boolean isInstance(Object obj);
+    public native boolean isAssignableFrom(Class cls);
+
+    public Object cast(Object obj) {
+        if(obj != null && !this.isInstance(obj)) {
+            throw new ClassCastException(new StringBuilder().append("Cannot cast ").append(obj.getClass().getName()).append(" to ").append(this.getName()).toString());
+        }
+
+        return obj;
+    }
+    public native int getModifiers();
+
+    public boolean desiredAssertionStatus() {
+        return false;
+    }
+
+    public native Method[] getDeclaredMethods();
+    public native Field[] getDeclaredFields();
+    public native Constructor[] getDeclaredConstructors();
+
+    public Field getDeclaredField(String name) throws NoSuchFieldException {
+        Field[] fields = this.getDeclaredFields();
+
+        for(int i = 0; i < fields.length; i++) {
+            if(fields[i].getName().equals(name)) {
+                return fields[i];
+            }
+        }
+
+        throw new NoSuchFieldException(name);
+    }
+
+    public Object newInstance() throws InstantiationException, IllegalAccessException {
+        Constructor[] constructors = this.getDeclaredConstructors();
+
+        for(int i = 0; i < constructors.length; i++) {
+            if(constructors[i].getParameterCount() == 0) {
+                try {
+                    return constructors[i].newInstance();
+                } catch(InvocationTargetException exception) {
+                    Class.<RuntimeException>rethrow(exception.getCause());
+                }
+            }
+        }
+
+        throw new InstantiationException(this.getName());
+    }
+
+    @SuppressWarnings("unchecked")
+    private static <T extends Throwable> void rethrow(Throwable throwable) throws T {
+        throw (T)throwable;
+    }
 
     public String toString() {
         return new StringBuilder().append("Class(name=").append(getName()).append(")").toString();
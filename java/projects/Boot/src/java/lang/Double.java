@@ -0,0 +1,92 @@
+package java.lang;
+
+public final class Double extends Number {
+    public static final Class TYPE = Class.getPrimitiveClass("double");
+
+    public static final double MIN_VALUE = 4.9e-324;
+    public static final double MAX_VALUE = 1.7976931348623157e+308;
+    public static final double POSITIVE_INFINITY = 1.0 / 0.0;
+    public static final double NEGATIVE_INFINITY = -1.0 / 0.0;
+    public static final double NaN = 0.0 / 0.0;
+    public static final double MIN_NORMAL = 2.2250738585072014E-308;
+
+    private final double value;
+
+    public Double(double value) {
+        this.value = value;
+    }
+
+    public static Double valueOf(double value) {
+        return new Double(value);
+    }
+
+    public static native double parseDouble(String string);
+    public static native String toString(double value);
+
+    public static Double valueOf(String string) {
+        return new Double(parseDouble(string));
+    }
+
+    public static boolean isNaN(double value) {
+        return value != value;
+    }
+
+    public static boolean isInfinite(double value) {
+        return value == POSITIVE_INFINITY || value == NEGATIVE_INFINITY;
+    }
+
+    public static boolean isFinite(double value) {
+        return !isNaN(value) && !isInfinite(value);
+    }
+
+    public static long doubleToLongBits(double value) {
+        return isNaN(value) ? 0x7ff8000000000000L : doubleToRawLongBits(value);
+    }
+
+    public static native long doubleToRawLongBits(double value);
+    public static native double longBitsToDouble(long bits);
+
+    public static int hashCode(double value) {
+        long bits = doubleToLongBits(value);
+        return (int)(bits ^ (bits >>> 32));
+    }
+
+    public int intValue() {
+        return (int)this.value;
+    }
+
+    public long longValue() {
+        return (long)this.value;
+    }
+
+    public float floatValue() {
+        return (float)this.value;
+    }
+
+    public double doubleValue() {
+        return this.value;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Double && doubleToLongBits(((Double)other).value) == doubleToLongBits(this.value);
+    }
+
+    public boolean isNaN() {
+        return isNaN(this.value);
+    }
+
+    public boolean isInfinite() {
+        return isInfinite(this.value);
+    }
+
+    @Override
+    public int hashCode() {
+        return hashCode(this.value);
+    }
+
+    @Override
+    public String toString() {
+        return toString(this.value);
+    }
+}
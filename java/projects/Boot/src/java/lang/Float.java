@@ -0,0 +1,91 @@
+package java.lang;
+
+public final class Float extends Number {
+    public static final Class TYPE = Class.getPrimitiveClass("float");
+
+    public static final float MIN_VALUE = 1.4e-45f;
+    public static final float MAX_VALUE = 3.4028235e+38f;
+    public static final float POSITIVE_INFINITY = 1.0f / 0.0f;
+    public static final float NEGATIVE_INFINITY = -1.0f / 0.0f;
+    public static final float NaN = 0.0f / 0.0f;
+    public static final float MIN_NORMAL = 1.17549435E-38f;
+
+    private final float value;
+
+    public Float(float value) {
+        this.value = value;
+    }
+
+    public static Float valueOf(float value) {
+        return new Float(value);
+    }
+
+    public static native float parseFloat(String string);
+    public static native String toString(float value);
+
+    public static Float valueOf(String string) {
+        return new Float(parseFloat(string));
+    }
+
+    public static boolean isNaN(float value) {
+        return value != value;
+    }
+
+    public static boolean isInfinite(float value) {
+        return value == POSITIVE_INFINITY || value == NEGATIVE_INFINITY;
+    }
+
+    public static boolean isFinite(float value) {
+        return !isNaN(value) && !isInfinite(value);
+    }
+
+    public static int floatToIntBits(float value) {
+        return isNaN(value) ? 0x7fc00000 : floatToRawIntBits(value);
+    }
+
+    public static native int floatToRawIntBits(float value);
+    public static native float intBitsToFloat(int bits);
+
+    public static int hashCode(float value) {
+        return floatToIntBits(value);
+    }
+
+    public int intValue() {
+        return (int)this.value;
+    }
+
+    public long longValue() {
+        return (long)this.value;
+    }
+
+    public float floatValue() {
+        return this.value;
+    }
+
+    public double doubleValue() {
+        return (double)this.value;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Float && floatToIntBits(((Float)other).value) == floatToIntBits(this.value);
+    }
+
+    public boolean isNaN() {
+        return isNaN(this.value);
+    }
+
+    public boolean isInfinite() {
+        return isInfinite(this.value);
+    }
+
+    @Override
+    public int hashCode() {
+        return hashCode(this.value);
+    }
+
+    @Override
+    public String toString() {
+        return toString(this.value);
+    }
+}
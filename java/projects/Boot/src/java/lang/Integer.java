@@ -0,0 +1,96 @@
+package java.lang;
+
+public final class Integer extends Number implements Comparable<Integer> {
+    public static final Class TYPE = Class.getPrimitiveClass("int");
+
+    public static final int MIN_VALUE = 0x80000000;
+    public static final int MAX_VALUE = 0x7fffffff;
+
+    private final int value;
+
+    public Integer(int value) {
+        this.value = value;
+    }
+
+    public static Integer valueOf(int value) {
+        return new Integer(value);
+    }
+
+    public static native int parseInt(String string, int radix);
+    public static native String toString(int value, int radix);
+
+    public static int parseInt(String string) {
+        return parseInt(string, 10);
+    }
+
+    public static Integer valueOf(String string, int radix) {
+        return new Integer(parseInt(string, radix));
+    }
+
+    public static Integer valueOf(String string) {
+        return new Integer(parseInt(string, 10));
+    }
+
+    public static String toString(int value) {
+        return toString(value, 10);
+    }
+
+    public static String toHexString(int value) {
+        if(value == 0) {
+            return "0";
+        }
+
+        char[] digits = new char[8];
+        int position = digits.length;
+
+        while(value != 0) {
+            int digit = value & 0xf;
+            digits[--position] = (char)(digit < 10 ? '0' + digit : 'a' + digit - 10);
+            value >>>= 4;
+        }
+
+        return new String(digits, position, digits.length - position);
+    }
+
+    public static native int numberOfLeadingZeros(int value);
+    public static native int numberOfTrailingZeros(int value);
+    public static native int bitCount(int value);
+
+    public int intValue() {
+        return this.value;
+    }
+
+    public long longValue() {
+        return (long)this.value;
+    }
+
+    public float floatValue() {
+        return (float)this.value;
+    }
+
+    public double doubleValue() {
+        return (double)this.value;
+    }
+
+    public static int compare(int x, int y) {
+        return x < y ? -1 : (x == y ? 0 : 1);
+    }
+
+    public int compareTo(Integer other) {
+        return compare(this.value, other.value);
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Integer && ((Integer)other).value == this.value;
+    }
+
+    public int hashCode() {
+        return this.value;
+    }
+
+    @Override
+    public String toString() {
+        return toString(this.value, 10);
+    }
+}
@@ -0,0 +1,71 @@
+package java.lang;
+
+public final class Long extends Number {
+    public static final Class TYPE = Class.getPrimitiveClass("long");
+
+    public static final long MIN_VALUE = 0x8000000000000000L;
+    public static final long MAX_VALUE = 0x7fffffffffffffffL;
+
+    private final long value;
+
+    public Long(long value) {
+        this.value = value;
+    }
+
+    public static Long valueOf(long value) {
+        return new Long(value);
+    }
+
+    public static native long parseLong(String string, int radix);
+    public static native String toString(long value, int radix);
+
+    public static long parseLong(String string) {
+        return parseLong(string, 10);
+    }
+
+    public static Long valueOf(String string, int radix) {
+        return new Long(parseLong(string, radix));
+    }
+
+    public static Long valueOf(String string) {
+        return new Long(parseLong(string, 10));
+    }
+
+    public static String toString(long value) {
+        return toString(value, 10);
+    }
+
+    public static native int numberOfLeadingZeros(long value);
+    public static native int numberOfTrailingZeros(long value);
+    public static native int bitCount(long value);
+
+    public int intValue() {
+        return (int)this.value;
+    }
+
+    public long longValue() {
+        return this.value;
+    }
+
+    public float floatValue() {
+        return (float)this.value;
+    }
+
+    public double doubleValue() {
+        return (double)this.value;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Long && ((Long)other).value == this.value;
+    }
+
+    public int hashCode() {
+        return (int)(this.value ^ (this.value >>> 32));
+    }
+
+    @Override
+    public String toString() {
+        return toString(this.value, 10);
+    }
+}
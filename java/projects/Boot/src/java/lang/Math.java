@@ -0,0 +1,224 @@
+package java.lang;
+
+public final class Math {
+    public static final double E = 2.7182818284590452354;
+    public static final double PI = 3.14159265358979323846;
+
+    private Math() {}
+
+    public static native double sin(double a);
+    public static native double cos(double a);
+    public static native double tan(double a);
+    public static native double asin(double a);
+    public static native double atan(double a);
+    public static native double atan2(double y, double x);
+
+    public static native double exp(double a);
+    public static native double log(double a);
+    public static native double log10(double a);
+    public static native double pow(double a, double b);
+    public static native double sqrt(double a);
+    public static native double cbrt(double a);
+
+    public static native double floor(double a);
+    public static native double ceil(double a);
+    public static native double rint(double a);
+    public static native double hypot(double x, double y);
+    public static native double IEEEremainder(double f1, double f2);
+
+    public static int abs(int a) {
+        return a < 0 ? -a : a;
+    }
+
+    public static long abs(long a) {
+        return a < 0 ? -a : a;
+    }
+
+    public static float abs(float a) {
+        return a <= 0.0f ? 0.0f - a : a;
+    }
+
+    public static double abs(double a) {
+        return a <= 0.0 ? 0.0 - a : a;
+    }
+
+    public static int max(int a, int b) {
+        return a >= b ? a : b;
+    }
+
+    public static long max(long a, long b) {
+        return a >= b ? a : b;
+    }
+
+    public static float max(float a, float b) {
+        if(a != a) {
+            return a;
+        }
+
+        if(a == 0.0f && b == 0.0f && Float.floatToRawIntBits(a) == Float.floatToRawIntBits(-0.0f)) {
+            return b;
+        }
+
+        return a >= b ? a : b;
+    }
+
+    public static double max(double a, double b) {
+        if(a != a) {
+            return a;
+        }
+
+        if(a == 0.0 && b == 0.0 && Double.doubleToRawLongBits(a) == Double.doubleToRawLongBits(-0.0)) {
+            return b;
+        }
+
+        return a >= b ? a : b;
+    }
+
+    public static int min(int a, int b) {
+        return a <= b ? a : b;
+    }
+
+    public static long min(long a, long b) {
+        return a <= b ? a : b;
+    }
+
+    public static float min(float a, float b) {
+        if(a != a) {
+            return a;
+        }
+
+        if(a == 0.0f && b == 0.0f && Float.floatToRawIntBits(b) == Float.floatToRawIntBits(-0.0f)) {
+            return b;
+        }
+
+        return a <= b ? a : b;
+    }
+
+    public static double min(double a, double b) {
+        if(a != a) {
+            return a;
+        }
+
+        if(a == 0.0 && b == 0.0 && Double.doubleToRawLongBits(b) == Double.doubleToRawLongBits(-0.0)) {
+            return b;
+        }
+
+        return a <= b ? a : b;
+    }
+
+    public static int floorDiv(int x, int y) {
+        int quotient = x / y;
+
+        if((x ^ y) < 0 && quotient * y != x) {
+            quotient--;
+        }
+
+        return quotient;
+    }
+
+    public static long floorDiv(long x, long y) {
+        long quotient = x / y;
+
+        if((x ^ y) < 0 && quotient * y != x) {
+            quotient--;
+        }
+
+        return quotient;
+    }
+
+    public static long floorDiv(long x, int y) {
+        return floorDiv(x, (long)y);
+    }
+
+    public static int floorMod(long x, int y) {
+        return (int)floorMod(x, (long)y);
+    }
+
+    public static int floorMod(int x, int y) {
+        return x - floorDiv(x, y) * y;
+    }
+
+    public static long floorMod(long x, long y) {
+        return x - floorDiv(x, y) * y;
+    }
+
+    public static int addExact(int x, int y) {
+        int result = x + y;
+
+        if(((x ^ result) & (y ^ result)) < 0) {
+            throw new ArithmeticException("integer overflow");
+        }
+
+        return result;
+    }
+
+    public static long addExact(long x, long y) {
+        long result = x + y;
+
+        if(((x ^ result) & (y ^ result)) < 0) {
+            throw new ArithmeticException("long overflow");
+        }
+
+        return result;
+    }
+
+    public static int multiplyExact(int x, int y) {
+        long result = (long)x * (long)y;
+
+        if((int)result != result) {
+            throw new ArithmeticException("integer overflow");
+        }
+
+        return (int)result;
+    }
+
+    public static long multiplyExact(long x, int y) {
+        return multiplyExact(x, (long)y);
+    }
+
+    public static long multiplyExact(long x, long y) {
+        long result = x * y;
+
+        if(((Math.abs(x) | Math.abs(y)) >>> 31 != 0) && ((y != 0 && result / y != x) || (x == Long.MIN_VALUE && y == -1))) {
+            throw new ArithmeticException("long overflow");
+        }
+
+        return result;
+    }
+
+    public static int subtractExact(int x, int y) {
+        int result = x - y;
+
+        if(((x ^ y) & (x ^ result)) < 0) {
+            throw new ArithmeticException("integer overflow");
+        }
+
+        return result;
+    }
+
+    public static long subtractExact(long x, long y) {
+        long result = x - y;
+
+        if(((x ^ y) & (x ^ result)) < 0) {
+            throw new ArithmeticException("long overflow");
+        }
+
+        return result;
+    }
+
+    public static int negateExact(int a) {
+        return subtractExact(0, a);
+    }
+
+    public static long negateExact(long a) {
+        return subtractExact(0L, a);
+    }
+
+    public static int toIntExact(long value) {
+        if((int)value != value) {
+            throw new ArithmeticException("integer overflow");
+        }
+
+        return (int)value;
+    }
+}
@@ -1,21 +1,17 @@
 package java.lang;
 
 public class Object {
-    private static long count = 0;
-    private long index;
-
     public Object() {
-        this.index = count++;
     }
  
     public final native Class getClass();
 
-    public long getIndex() {
-        return this.index;
-    }
+    public native int hashCode();
+
+    protected native Object clone() throws CloneNotSupportedException;
 
     public String toString() {
-        return new StringBuilder().append(this.getClass().getName()).append("(index=").append(this.index).append(")").toString();
+        return new StringBuilder().append(this.getClass().getName()).append("@").append(Integer.toHexString(this.hashCode())).toString();
     }
 
     public boolean equals(Object other) {
@@ -0,0 +1,49 @@
+package java.lang;
+
+public class Runtime {
+    private static final Runtime currentRuntime = new Runtime();
+
+    private Runtime() {
+    }
+
+    public static Runtime getRuntime() {
+        return currentRuntime;
+    }
+
+    public void exit(int status) {
+        Shutdown.exit(status);
+    }
+
+    public void halt(int status) {
+        Shutdown.halt(status);
+    }
+
+    public void addShutdownHook(Thread hook) {
+        if(hook == null) {
+            throw new NullPointerException();
+        }
+
+        Shutdown.add(hook);
+    }
+
+    public boolean removeShutdownHook(Thread hook) {
+        if(hook == null) {
+            throw new NullPointerException();
+        }
+
+        return Shutdown.remove(hook);
+    }
+
+    public native int availableProcessors();
+    public native long freeMemory();
+    public native long totalMemory();
+    public native long maxMemory();
+
+    public void gc() {
+        System.gc();
+    }
+
+    public void loadLibrary(String libname) {
+        System.loadLibrary(libname);
+    }
+}
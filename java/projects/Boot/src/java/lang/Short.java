@@ -0,0 +1,56 @@
+package java.lang;
+
+public final class Short extends Number {
+    public static final Class TYPE = Class.getPrimitiveClass("short");
+
+    public static final short MIN_VALUE = -32768;
+    public static final short MAX_VALUE = 32767;
+
+    private final short value;
+
+    public Short(short value) {
+        this.value = value;
+    }
+
+    public static Short valueOf(short value) {
+        return new Short(value);
+    }
+
+    public int intValue() {
+        return (int)this.value;
+    }
+
+    public long longValue() {
+        return (long)this.value;
+    }
+
+    public float floatValue() {
+        return (float)this.value;
+    }
+
+    public double doubleValue() {
+        return (double)this.value;
+    }
+
+    public byte byteValue() {
+        return (byte)this.value;
+    }
+
+    public short shortValue() {
+        return this.value;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        return other instanceof Short && ((Short)other).value == this.value;
+    }
+
+    public int hashCode() {
+        return (int)this.value;
+    }
+
+    @Override
+    public String toString() {
+        return new StringBuilder().append(this.value).toString();
+    }
+}
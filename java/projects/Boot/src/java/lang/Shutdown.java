@@ -0,0 +1,81 @@
+package java.lang;
+
+// Runs the shutdown hooks for System.exit and for the end of the program, then halts.
+class Shutdown {
+    private static Thread[] hooks = new Thread[0];
+    private static boolean running = false;
+
+    static void add(Thread hook) {
+        if(running) {
+            throw new IllegalStateException("Shutdown in progress");
+        }
+
+        if(indexOf(hook) >= 0) {
+            throw new IllegalArgumentException("Hook previously registered");
+        }
+
+        Thread[] grown = new Thread[hooks.length + 1];
+        System.arraycopy(hooks, 0, grown, 0, hooks.length);
+        grown[hooks.length] = hook;
+        hooks = grown;
+    }
+
+    static boolean remove(Thread hook) {
+        if(running) {
+            throw new IllegalStateException("Shutdown in progress");
+        }
+
+        int index = indexOf(hook);
+
+        if(index < 0) {
+            return false;
+        }
+
+        Thread[] shrunk = new Thread[hooks.length - 1];
+        System.arraycopy(hooks, 0, shrunk, 0, index);
+        System.arraycopy(hooks, index + 1, shrunk, index, shrunk.length - index);
+        hooks = shrunk;
+
+        return true;
+    }
+
+    private static int indexOf(Thread hook) {
+        for(int i = 0; i < hooks.length; i++) {
+            if(hooks[i] == hook) {
+                return i;
+            }
+        }
+
+        return -1;
+    }
+
+    // Hooks run one after another on the calling thread, each only once.
+    private static void runHooks() {
+        if(running) {
+            return;
+        }
+
+        running = true;
+
+        for(int i = 0; i < hooks.length; i++) {
+            try {
+                hooks[i].run();
+            } catch(Throwable throwable) {
+                System.err.print(new StringBuilder().append("Exception in thread \"").append(hooks[i].getName()).append("\" ").toString());
+                throwable.printStackTrace();
+            }
+        }
+    }
+
+    // Called by the VM once every thread has finished.
+    static void shutdown() {
+        runHooks();
+    }
+
+    static void exit(int status) {
+        runHooks();
+        halt(status);
+    }
+
+    static native void halt(int status);
+}
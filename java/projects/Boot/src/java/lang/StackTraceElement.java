@@ -0,0 +1,77 @@
+package java.lang;
+
+public final class StackTraceElement {
+    private String declaringClass;
+    private String methodName;
+    private String fileName;
+    private int lineNumber;
+
+    public StackTraceElement(String declaringClass, String methodName, String fileName, int lineNumber) {
+        this.declaringClass = declaringClass;
+        this.methodName = methodName;
+        this.fileName = fileName;
+        this.lineNumber = lineNumber;
+    }
+
+    public String getClassName() {
+        return this.declaringClass;
+    }
+
+    public String getMethodName() {
+        return this.methodName;
+    }
+
+    public String getFileName() {
+        return this.fileName;
+    }
+
+    public int getLineNumber() {
+        return this.lineNumber;
+    }
+
+    public boolean isNativeMethod() {
+        return this.lineNumber == -2;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        if(!(other instanceof StackTraceElement)) {
+            return false;
+        }
+
+        StackTraceElement element = (StackTraceElement) other;
+        return this.lineNumber == element.lineNumber
+            && same(this.declaringClass, element.declaringClass)
+            && same(this.methodName, element.methodName)
+            && same(this.fileName, element.fileName);
+    }
+
+    private static boolean same(String a, String b) {
+        return a == null ? b == null : a.equals(b);
+    }
+
+    @Override
+    public int hashCode() {
+        int result = 31 * this.declaringClass.hashCode() + this.methodName.hashCode();
+        result = 31 * result + (this.fileName == null ? 0 : this.fileName.hashCode());
+        return 31 * result + this.lineNumber;
+    }
+
+    public String toString() {
+        StringBuilder builder = new StringBuilder().append(this.declaringClass).append(".").append(this.methodName).append("(");
+
+        if(this.isNativeMethod()) {
+            builder.append("Native Method");
+        } else if(this.fileName == null) {
+            builder.append("Unknown Source");
+        } else {
+            builder.append(this.fileName);
+
+            if(this.lineNumber >= 0) {
+                builder.append(":").append(this.lineNumber);
+            }
+        }
+
+        return builder.append(")").toString();
+    }
+}
@@ -0,0 +1,28 @@
+package java.lang;
+
+public final class StrictMath {
+    public static final double E = 2.7182818284590452354;
+    public static final double PI = 3.14159265358979323846;
+
+    private StrictMath() {}
+
+    public static native double sin(double a);
+    public static native double cos(double a);
+    public static native double tan(double a);
+    public static native double asin(double a);
+    public static native double atan(double a);
+    public static native double atan2(double y, double x);
+
+    public static native double exp(double a);
+    public static native double log(double a);
+    public static native double log10(double a);
+    public static native double pow(double a, double b);
+    public static native double sqrt(double a);
+    public static native double cbrt(double a);
+
+    public static native double floor(double a);
+    public static native double ceil(double a);
+    public static native double rint(double a);
+    public static native double hypot(double x, double y);
+    public static native double IEEEremainder(double f1, double f2);
+}
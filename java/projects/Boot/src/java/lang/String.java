@@ -1,45 +1,361 @@
 package java.lang;
 
-public class String {
-    public String(byte[] raw) {
-        this.setInternal(raw);
+public class String implements Comparable<String> {
+    public String(char[] value) {
+        this.setInternal(value);
     }
 
-    private native byte[] getInternal();
-    private native void setInternal(byte[] value);
+    public String(char[] value, int offset, int count) {
+        char[] chars = new char[count];
+        System.arraycopy(value, offset, chars, 0, count);
+        this.setInternal(chars);
+    }
+
+    public String(byte[] bytes) {
+        this.setBytes(bytes);
+    }
+
+    private native char[] getInternal();
+    private native void setInternal(char[] value);
+    private native void setBytes(byte[] bytes);
+    private native char charAtInternal(int index);
+    private native String substringInternal(int beginIndex, int endIndex);
+
+    public native byte[] getBytes();
+    public native String intern();
+    public native int length();
+    public native int hashCode();
+    public native int compareTo(String other);
+    public native int indexOf(int ch);
+    public native int indexOf(int ch, int fromIndex);
+    public native int indexOf(String string);
+    public native int indexOf(String string, int fromIndex);
+    public native String concat(String string);
+    public native boolean startsWith(String prefix);
+    public native boolean startsWith(String prefix, int offset);
+    public native String toUpperCase();
+    public native String toLowerCase();
+
+    @Override
+    public native boolean equals(Object other);
 
-    public byte[] getBytes() {
+    public boolean equalsIgnoreCase(String other) {
+        return other != null && this.toLowerCase().equals(other.toLowerCase());
+    }
+
+    public char[] toCharArray() {
         return this.getInternal();
     }
 
-    public int length() {
-        return this.getBytes().length;
+    public boolean isEmpty() {
+        return this.length() == 0;
     }
 
     public char charAt(int index) {
-        return (char)getInternal()[index];
+        if(index < 0 || index >= this.length()) {
+            throw new StringIndexOutOfBoundsException(new StringBuilder().append("Index ").append(index).append(" out of bounds for length ").append(this.length()).toString());
+        }
+
+        return this.charAtInternal(index);
     }
 
-    @Override
-    public String toString() {
-        return this;
+    public int codePointAt(int index) {
+        char high = this.charAt(index);
+
+        if(Character.isHighSurrogate(high) && index + 1 < this.length()) {
+            char low = this.charAt(index + 1);
+
+            if(Character.isLowSurrogate(low)) {
+                return Character.toCodePoint(high, low);
+            }
+        }
+
+        return high;
     }
 
-    @Override
-    public boolean equals(Object other) {
-        if (!(other instanceof String)) return false;
+    public String substring(int beginIndex) {
+        return this.substring(beginIndex, this.length());
+    }
+
+    public String substring(int beginIndex, int endIndex) {
+        if(beginIndex < 0 || endIndex > this.length() || beginIndex > endIndex) {
+            throw new StringIndexOutOfBoundsException(new StringBuilder().append("begin ").append(beginIndex).append(", end ").append(endIndex).append(", length ").append(this.length()).toString());
+        }
+
+        return this.substringInternal(beginIndex, endIndex);
+    }
+
+    public String repeat(int count) {
+        if(count < 0) {
+            throw new IllegalArgumentException(new StringBuilder().append("count is negative: ").append(count).toString());
+        }
+
+        StringBuilder builder = new StringBuilder();
+        for(int i = 0; i < count; i++) {
+            builder.append(this);
+        }
+
+        return builder.toString();
+    }
+
+    // Follows java.util.Formatter for the general, character, integral and floating point
+    // conversions, always in the root locale: ',' groups thousands and '.' separates decimals.
+    public static String format(String format, Object... args) {
+        StringBuilder builder = new StringBuilder();
+        int next = 0;
+        int last = -1;
+
+        for(int i = 0; i < format.length(); i++) {
+            char c = format.charAt(i);
+
+            if(c != '%') {
+                builder.append(c);
+                continue;
+            }
+
+            int start = i++;
+            int index = -1;
+            int end = digitsEnd(format, i);
+
+            if(end > i && end < format.length() && format.charAt(end) == '$') {
+                index = Integer.parseInt(format.substring(i, end));
+                i = end + 1;
+            }
+
+            int flagsStart = i;
+            while(i < format.length() && "-#+ 0,(<".indexOf(format.charAt(i)) >= 0) {
+                i++;
+            }
+            String flags = format.substring(flagsStart, i);
+
+            int width = -1;
+            end = digitsEnd(format, i);
+            if(end > i) {
+                width = Integer.parseInt(format.substring(i, end));
+                i = end;
+            }
+
+            int precision = -1;
+            if(i < format.length() && format.charAt(i) == '.') {
+                end = digitsEnd(format, ++i);
+                if(end == i) {
+                    throw new IllegalArgumentException(new StringBuilder().append("Conversion = '.'").toString());
+                }
+
+                precision = Integer.parseInt(format.substring(i, end));
+                i = end;
+            }
+
+            if(i == format.length()) {
+                throw new IllegalArgumentException(new StringBuilder().append("Format specifier '").append(format.substring(start)).append("'").toString());
+            }
+
+            char conversion = format.charAt(i);
+            if(conversion == 'n') {
+                builder.append(System.lineSeparator());
+                continue;
+            }
+
+            String text;
+            if(conversion == '%') {
+                text = "%";
+            } else {
+                if(flags.indexOf('<') >= 0) {
+                    index = last + 1;
+                } else if(index < 1) {
+                    index = ++next;
+                }
+
+                if(args == null || index < 1 || index > args.length) {
+                    throw new IllegalArgumentException(new StringBuilder().append("Format specifier '").append(format.substring(start, i + 1)).append("'").toString());
+                }
+
+                last = index - 1;
+                text = formatArgument(args[last], conversion, flags, width, precision);
+            }
+
+            if(width > text.length()) {
+                String padding = " ".repeat(width - text.length());
+                text = flags.indexOf('-') >= 0 ? text.concat(padding) : padding.concat(text);
+            }
+
+            builder.append(text);
+        }
+
+        return builder.toString();
+    }
+
+    private static int digitsEnd(String string, int index) {
+        while(index < string.length() && string.charAt(index) >= '0' && string.charAt(index) <= '9') {
+            index++;
+        }
+
+        return index;
+    }
+
+    private static String formatArgument(Object arg, char conversion, String flags, int width, int precision) {
+        boolean upper = conversion >= 'A' && conversion <= 'Z';
+        char lower = upper ? (char) (conversion + ('a' - 'A')) : conversion;
+        String text;
 
-        String otherString = (String)other;
+        if(lower == 'b') {
+            text = arg == null ? "false" : arg instanceof Boolean ? arg.toString() : "true";
+        } else if(lower == 'h') {
+            text = arg == null ? "null" : Integer.toHexString(arg.hashCode());
+        } else if(lower == 's') {
+            text = String.valueOf(arg);
+        } else if(arg == null) {
+            text = "null";
+        } else if(lower == 'c') {
+            if(arg instanceof Character) {
+                text = arg.toString();
+            } else if(arg instanceof Integer || arg instanceof Short || arg instanceof Byte) {
+                text = String.valueOf((char) ((Number) arg).intValue());
+            } else {
+                throw mismatch(conversion, arg);
+            }
+        } else if(lower == 'd' || lower == 'o' || lower == 'x') {
+            int bits = arg instanceof Long ? 64 : arg instanceof Integer ? 32 : arg instanceof Short ? 16 : arg instanceof Byte ? 8 : 0;
+            if(bits == 0) {
+                throw mismatch(conversion, arg);
+            }
 
-        if(this.length() != otherString.length()) return false;
+            text = formatIntegral(((Number) arg).longValue(), bits, lower, flags, width);
+        } else if(lower == 'f' || lower == 'e') {
+            if(!(arg instanceof Double || arg instanceof Float)) {
+                throw mismatch(conversion, arg);
+            }
 
-        byte[] thisBytes = this.getBytes();
-        byte[] otherBytes = otherString.getBytes();
+            text = formatFloating(((Number) arg).doubleValue(), lower, flags, width, precision == -1 ? 6 : precision);
+            precision = -1;
+        } else {
+            throw new IllegalArgumentException(new StringBuilder().append("Conversion = '").append(conversion).append("'").toString());
+        }
+
+        if(precision >= 0 && precision < text.length()) {
+            text = text.substring(0, precision);
+        }
+
+        return upper ? text.toUpperCase() : text;
+    }
+
+    private static IllegalArgumentException mismatch(char conversion, Object arg) {
+        return new IllegalArgumentException(new StringBuilder().append(conversion).append(" != ").append(arg.getClass().getName()).toString());
+    }
+
+    private static String formatIntegral(long value, int bits, char conversion, String flags, int width) {
+        if(conversion == 'd') {
+            String digits = Long.toString(value, 10);
+            boolean negative = value < 0;
+
+            return signed(negative ? digits.substring(1) : digits, negative, flags, width);
+        }
+
+        int shift = conversion == 'o' ? 3 : 4;
+        if(bits < 64) {
+            value &= (1L << bits) - 1;
+        }
+
+        // Unsigned: the sign bit can't be in the top digit once it's shifted away.
+        String digits = value < 0
+            ? Long.toString(value >>> shift, 1 << shift).concat(Long.toString(value & ((1 << shift) - 1), 1 << shift))
+            : Long.toString(value, 1 << shift);
+        String prefix = flags.indexOf('#') < 0 ? "" : conversion == 'o' ? "0" : "0x";
+
+        return padZeros(prefix, digits, "", flags, width);
+    }
+
+    private static native String formatDecimal(double value, int precision, boolean scientific);
+
+    private static String formatFloating(double value, char conversion, String flags, int width, int precision) {
+        if(Double.isNaN(value)) {
+            return "NaN";
+        }
+
+        boolean negative = value < 0 || (value == 0 && 1 / value < 0);
+        if(Double.isInfinite(value)) {
+            return signed("Infinity", negative, flags, -1);
+        }
+
+        return signed(formatDecimal(negative ? -value : value, precision, conversion == 'e'), negative, flags, width);
+    }
+
+    private static String signed(String magnitude, boolean negative, String flags, int width) {
+        if(flags.indexOf(',') >= 0) {
+            int point = magnitude.indexOf('.');
+            int end = point < 0 ? magnitude.length() : point;
+            StringBuilder grouped = new StringBuilder();
+
+            for(int i = 0; i < end; i++) {
+                if(i > 0 && (end - i) % 3 == 0) {
+                    grouped.append(',');
+                }
+
+                grouped.append(magnitude.charAt(i));
+            }
+
+            magnitude = grouped.append(magnitude.substring(end)).toString();
+        }
 
-        for(int i = 0; i < thisBytes.length; i++) {
-            if (thisBytes[i] != otherBytes[i]) return false;
+        String prefix = "";
+        String suffix = "";
+
+        if(negative) {
+            prefix = flags.indexOf('(') >= 0 ? "(" : "-";
+            suffix = flags.indexOf('(') >= 0 ? ")" : "";
+        } else if(flags.indexOf('+') >= 0) {
+            prefix = "+";
+        } else if(flags.indexOf(' ') >= 0) {
+            prefix = " ";
         }
 
-        return true;
+        return padZeros(prefix, magnitude, suffix, flags, width);
+    }
+
+    private static String padZeros(String prefix, String digits, String suffix, String flags, int width) {
+        int length = prefix.length() + digits.length() + suffix.length();
+
+        if(flags.indexOf('0') >= 0 && width > length) {
+            digits = "0".repeat(width - length).concat(digits);
+        }
+
+        return new StringBuilder().append(prefix).append(digits).append(suffix).toString();
+    }
+
+    public static String valueOf(Object value) {
+        return value == null ? "null" : value.toString();
+    }
+
+    public static String valueOf(char[] value) {
+        return new String(value);
+    }
+
+    public static String valueOf(boolean value) {
+        return value ? "true" : "false";
+    }
+
+    public static String valueOf(char value) {
+        return new String(new char[] { value });
+    }
+
+    public static String valueOf(int value) {
+        return Integer.toString(value, 10);
+    }
+
+    public static String valueOf(long value) {
+        return Long.toString(value, 10);
+    }
+
+    public static String valueOf(float value) {
+        return Float.toString(value);
+    }
+
+    public static String valueOf(double value) {
+        return Double.toString(value);
+    }
+
+    @Override
+    public String toString() {
+        return this;
     }
 }
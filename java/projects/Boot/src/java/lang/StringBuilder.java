@@ -1,16 +1,25 @@
 package java.lang;
 
 public class StringBuilder {
-    private byte[] buffer;
+    private char[] buffer;
     private int pointer;
 
     public StringBuilder() {
-        this.buffer = new byte[1024];
+        this(1024);
+    }
+
+    public StringBuilder(int capacity) {
+        this.buffer = new char[capacity];
         this.pointer = 0;
     }
 
+    public StringBuilder(String value) {
+        this(value.length() + 16);
+        this.append(value);
+    }
+
     public String toString() {
-        byte[] bufferCopy = new byte[this.pointer];
+        char[] bufferCopy = new char[this.pointer];
 
         for(int i = 0; i < bufferCopy.length; i++) {
             bufferCopy[i] = this.buffer[i];
@@ -20,57 +29,143 @@ public class StringBuilder {
     }
 
     public StringBuilder append(boolean value) {
-        return this.append(value ? "True" : "False");
+        return this.append(value ? "true" : "false");
     }
 
     public StringBuilder append(String value) {
-        return this.append(value.getBytes());
+        return this.append(value == null ? "null".toCharArray() : value.toCharArray());
     }
 
-    public StringBuilder append(byte[] bytes) {
-        for(int i = 0; i < bytes.length; i++) {
-            buffer[this.pointer++] = bytes[i];
+    public StringBuilder append(Object value) {
+        return this.append(String.valueOf(value));
+    }
+
+    public StringBuilder append(char[] chars) {
+        this.ensureCapacity(this.pointer + chars.length);
+
+        for(int i = 0; i < chars.length; i++) {
+            buffer[this.pointer++] = chars[i];
         }
 
         return this;
     }
 
     public StringBuilder append(char value) {
-        this.buffer[this.pointer++] = (byte)value;
+        this.ensureCapacity(this.pointer + 1);
+        this.buffer[this.pointer++] = value;
 
         return this;
     }
 
-    public StringBuilder append(int value) {
-        return this.append((long)value);
+    public int length() {
+        return this.pointer;
     }
 
-    public StringBuilder append(long value) {
-        byte[] buffer = new byte[21];
-        boolean positive = true;
+    public char charAt(int index) {
+        this.checkIndex(index, this.pointer);
+        return this.buffer[index];
+    }
+
+    public void setCharAt(int index, char value) {
+        this.checkIndex(index, this.pointer);
+        this.buffer[index] = value;
+    }
+
+    public void setLength(int length) {
+        if(length < 0) {
+            throw new StringIndexOutOfBoundsException(new StringBuilder().append("length ").append(length).toString());
+        }
 
-        if(value < 0) {
-            positive = false;
-            value *= -1;
+        this.ensureCapacity(length);
+        for(int i = this.pointer; i < length; i++) {
+            this.buffer[i] = '\0';
         }
-        
-        int i = buffer.length - 1;
-        for(; i > 0; i--) {
-            buffer[i] = (byte)('0' + value % 10);
-            value /= 10;
-            if(value == 0) {
-                break;
-            }
+
+        this.pointer = length;
+    }
+
+    public StringBuilder delete(int start, int end) {
+        if(end > this.pointer) {
+            end = this.pointer;
         }
 
-        if (!positive) this.buffer[this.pointer++] = '-';
+        if(start < 0 || start > end) {
+            throw new StringIndexOutOfBoundsException(new StringBuilder().append("start ").append(start).append(", end ").append(end).append(", length ").append(this.pointer).toString());
+        }
+
+        for(int i = end; i < this.pointer; i++) {
+            this.buffer[i - (end - start)] = this.buffer[i];
+        }
+
+        this.pointer -= end - start;
+        return this;
+    }
+
+    public StringBuilder deleteCharAt(int index) {
+        this.checkIndex(index, this.pointer);
+        return this.delete(index, index + 1);
+    }
+
+    public StringBuilder insert(int offset, String value) {
+        this.checkIndex(offset, this.pointer + 1);
 
-        for(int j = i; j < buffer.length; j++) {
-            this.buffer[j - i + this.pointer] = buffer[j];
+        char[] chars = (value == null ? "null" : value).toCharArray();
+        this.ensureCapacity(this.pointer + chars.length);
+
+        for(int i = this.pointer - 1; i >= offset; i--) {
+            this.buffer[i + chars.length] = this.buffer[i];
         }
 
-        this.pointer += buffer.length - i;
+        for(int i = 0; i < chars.length; i++) {
+            this.buffer[offset + i] = chars[i];
+        }
 
+        this.pointer += chars.length;
         return this;
     }
+
+    public StringBuilder insert(int offset, char value) {
+        return this.insert(offset, String.valueOf(value));
+    }
+
+    public StringBuilder insert(int offset, int value) {
+        return this.insert(offset, Integer.toString(value, 10));
+    }
+
+    private void checkIndex(int index, int length) {
+        if(index < 0 || index >= length) {
+            throw new StringIndexOutOfBoundsException(new StringBuilder().append("index ").append(index).append(",length ").append(this.pointer).toString());
+        }
+    }
+
+    private void ensureCapacity(int capacity) {
+        if(capacity <= this.buffer.length) return;
+
+        int size = this.buffer.length * 2;
+        if(size < capacity) size = capacity;
+
+        char[] grown = new char[size];
+
+        for(int i = 0; i < this.pointer; i++) {
+            grown[i] = this.buffer[i];
+        }
+
+        this.buffer = grown;
+    }
+
+    public StringBuilder append(int value) {
+        return this.append(Integer.toString(value, 10));
+    }
+
+    public StringBuilder append(long value) {
+        return this.append(Long.toString(value, 10));
+    }
+
+    public StringBuilder append(float value) {
+        return this.append(Float.toString(value));
+    }
+
+    public StringBuilder append(double value) {
+        return this.append(Double.toString(value));
+    }
 }
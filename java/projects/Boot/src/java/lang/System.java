@@ -1,8 +1,133 @@
 package java.lang;
 
 import java.io.*;
+import java.util.Enumeration;
+import java.util.Properties;
 
 public class System {
-    public static final InputStream in = new FileInputStream("<sys>");
-    public static final PrintStream out = new PrintStream("<sys>");
+    public static final InputStream in = standardInput();
+    public static final PrintStream out = standardOutput("<sys>");
+    public static final PrintStream err = standardOutput("<err>");
+
+    // The standard streams are bound by the VM, so opening them can't fail.
+    private static InputStream standardInput() {
+        try {
+            return new FileInputStream("<sys>");
+        } catch(FileNotFoundException exception) {
+            throw new Error(exception);
+        }
+    }
+
+    private static PrintStream standardOutput(String path) {
+        try {
+            return new PrintStream(path);
+        } catch(FileNotFoundException exception) {
+            throw new Error(exception);
+        }
+    }
+
+    // Reads and writes go straight to the VM's property table.
+    private static class VMProperties extends Properties {
+        @Override
+        public String getProperty(String key) {
+            return getPropertyInternal(key);
+        }
+
+        @Override
+        public Object setProperty(String key, String value) {
+            if(key == null || value == null) {
+                throw new NullPointerException();
+            }
+
+            return setPropertyInternal(key, value);
+        }
+
+        @Override
+        public int size() {
+            return System.propertyNames().length;
+        }
+
+        @Override
+        public boolean isEmpty() {
+            return this.size() == 0;
+        }
+
+        @Override
+        public Enumeration<?> propertyNames() {
+            final String[] names = System.propertyNames();
+
+            return new Enumeration<String>() {
+                private int position = 0;
+
+                public boolean hasMoreElements() {
+                    return this.position < names.length;
+                }
+
+                public String nextElement() {
+                    return names[this.position++];
+                }
+            };
+        }
+    }
+
+    private static final Properties properties = new VMProperties();
+
+    public static void exit(int status) {
+        Runtime.getRuntime().exit(status);
+    }
+
+    public static native void gc();
+
+    public static native int identityHashCode(Object object);
+
+    public static native void arraycopy(Object source, int sourcePosition, Object destination, int destinationPosition, int length);
+
+    public static native long currentTimeMillis();
+    public static native long nanoTime();
+
+    private static native String getPropertyInternal(String key);
+    private static native String setPropertyInternal(String key, String value);
+    private static native String[] propertyNames();
+
+    public static native String getenv(String name);
+    public static native String lineSeparator();
+
+    // Links one of the native libraries the host registered with the VM; there are no files to load.
+    public static native void loadLibrary(String libname);
+
+    private static void checkKey(String key) {
+        if(key == null) {
+            throw new NullPointerException("key can't be null");
+        }
+
+        if(key.isEmpty()) {
+            throw new IllegalArgumentException("key can't be empty");
+        }
+    }
+
+    public static String getProperty(String key) {
+        checkKey(key);
+
+        return getPropertyInternal(key);
+    }
+
+    public static String getProperty(String key, String defaultValue) {
+        String value = getProperty(key);
+
+        return value == null ? defaultValue : value;
+    }
+
+    public static String setProperty(String key, String value) {
+        checkKey(key);
+
+        if(value == null) {
+            throw new NullPointerException();
+        }
+
+        return setPropertyInternal(key, value);
+    }
+
+    public static Properties getProperties() {
+        return properties;
+    }
 }
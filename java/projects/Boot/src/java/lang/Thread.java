@@ -1,3 +1,58 @@
 package java.lang;
 
-public class Thread {}
+public class Thread implements Runnable {
+    private static int threadNumber;
+
+    private Runnable target;
+    private String name;
+
+    // The VM's own threads are built with this constructor while booting, so it stays empty.
+    public Thread() {
+    }
+
+    public Thread(Runnable target) {
+        this.target = target;
+    }
+
+    public Thread(String name) {
+        this(null, name);
+    }
+
+    public Thread(Runnable target, String name) {
+        if(name == null) {
+            throw new NullPointerException("name cannot be null");
+        }
+
+        this.target = target;
+        this.name = name;
+    }
+
+    public String getName() {
+        if(this.name == null) {
+            this.name = new StringBuilder().append("Thread-").append(threadNumber++).toString();
+        }
+
+        return this.name;
+    }
+
+    public void setName(String name) {
+        if(name == null) {
+            throw new NullPointerException("name cannot be null");
+        }
+
+        this.name = name;
+    }
+
+    @Override
+    public void run() {
+        if(this.target != null) {
+            this.target.run();
+        }
+    }
+
+    // A thread can't be copied, even by a Cloneable subclass.
+    @Override
+    protected Object clone() throws CloneNotSupportedException {
+        throw new CloneNotSupportedException();
+    }
+}
@@ -1,3 +1,106 @@
 package java.lang;
 
-public abstract class Throwable {}
+import java.io.PrintStream;
+
+public class Throwable {
+    private String detailMessage;
+    private Throwable cause;
+
+    public Throwable() {
+        this.detailMessage = null;
+        this.cause = null;
+        this.fillInStackTrace();
+    }
+
+    public Throwable(String message) {
+        this();
+        this.detailMessage = message;
+    }
+
+    public Throwable(String message, Throwable cause) {
+        this(message);
+        this.cause = cause;
+    }
+
+    public Throwable(Throwable cause) {
+        this(cause == null ? null : cause.toString(), cause);
+    }
+
+    public native Throwable fillInStackTrace();
+
+    public native StackTraceElement[] getStackTrace();
+
+    public String getMessage() {
+        return this.detailMessage;
+    }
+
+    public String getLocalizedMessage() {
+        return this.getMessage();
+    }
+
+    public Throwable getCause() {
+        return this.cause;
+    }
+
+    public Throwable initCause(Throwable cause) {
+        this.cause = cause;
+
+        return this;
+    }
+
+    public void printStackTrace() {
+        this.printStackTrace(System.err);
+    }
+
+    public void printStackTrace(PrintStream stream) {
+        stream.println(this.toString());
+
+        StackTraceElement[] trace = this.getStackTrace();
+        for(int i = 0; i < trace.length; i++) {
+            stream.println(new StringBuilder().append("\tat ").append(trace[i].toString()).toString());
+        }
+
+        Throwable cause = this.getCause();
+        if(cause != null && cause != this) {
+            cause.printEnclosedStackTrace(stream, trace);
+        }
+    }
+
+    // Like HotSpot, the frames a cause shares with the trace that encloses it are folded into "... n more".
+    private void printEnclosedStackTrace(PrintStream stream, StackTraceElement[] enclosingTrace) {
+        StackTraceElement[] trace = this.getStackTrace();
+        int m = trace.length - 1;
+        int n = enclosingTrace.length - 1;
+
+        while(m >= 0 && n >= 0 && trace[m].equals(enclosingTrace[n])) {
+            m--;
+            n--;
+        }
+
+        stream.println(new StringBuilder().append("Caused by: ").append(this.toString()).toString());
+        for(int i = 0; i <= m; i++) {
+            stream.println(new StringBuilder().append("\tat ").append(trace[i].toString()).toString());
+        }
+
+        int framesInCommon = trace.length - 1 - m;
+        if(framesInCommon != 0) {
+            stream.println(new StringBuilder().append("\t... ").append(framesInCommon).append(" more").toString());
+        }
+
+        Throwable cause = this.getCause();
+        if(cause != null && cause != this) {
+            cause.printEnclosedStackTrace(stream, trace);
+        }
+    }
+
+    public String toString() {
+        String name = this.getClass().getName();
+        String message = this.getLocalizedMessage();
+
+        if(message == null) {
+            return name;
+        }
+
+        return new StringBuilder().append(name).append(": ").append(message).toString();
+    }
+}
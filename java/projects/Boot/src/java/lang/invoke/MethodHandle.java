@@ -0,0 +1,20 @@
+package java.lang.invoke;
+
+public final class MethodHandle {
+    private int kind;
+    private Class clazz;
+    private String name;
+    private MethodType type;
+
+    private MethodHandle() {
+    }
+
+    public MethodType type() {
+        return this.type;
+    }
+
+    @Override
+    public String toString() {
+        return new StringBuilder().append("MethodHandle").append(this.type.toString()).toString();
+    }
+}
@@ -0,0 +1,46 @@
+package java.lang.invoke;
+
+public final class MethodType {
+    private Class rtype;
+    private Class[] ptypes;
+
+    private MethodType() {
+    }
+
+    public Class returnType() {
+        return this.rtype;
+    }
+
+    public Class parameterType(int num) {
+        return this.ptypes[num];
+    }
+
+    public int parameterCount() {
+        return this.ptypes.length;
+    }
+
+    public Class[] parameterArray() {
+        Class[] ptypes = new Class[this.ptypes.length];
+
+        for(int i = 0; i < ptypes.length; i++) {
+            ptypes[i] = this.ptypes[i];
+        }
+
+        return ptypes;
+    }
+
+    @Override
+    public String toString() {
+        StringBuilder builder = new StringBuilder().append("(");
+
+        for(int i = 0; i < this.ptypes.length; i++) {
+            if(i > 0) {
+                builder.append(",");
+            }
+
+            builder.append(this.ptypes[i].getSimpleName());
+        }
+
+        return builder.append(")").append(this.rtype.getSimpleName()).toString();
+    }
+}
@@ -0,0 +1,80 @@
+package java.lang.ref;
+
+public final class Cleaner {
+    // Registered cleanables stay strongly reachable until they are cleaned or cleared.
+    private static PhantomCleanable cleanables;
+
+    final ReferenceQueue<Object> queue = new ReferenceQueue<Object>();
+
+    private Cleaner() {
+    }
+
+    public static Cleaner create() {
+        return new Cleaner();
+    }
+
+    public Cleanable register(Object object, Runnable action) {
+        if(object == null) {
+            throw new NullPointerException("object");
+        }
+        if(action == null) {
+            throw new NullPointerException("action");
+        }
+
+        return new PhantomCleanable(object, this, action);
+    }
+
+    public interface Cleanable {
+        void clean();
+    }
+
+    private static final class PhantomCleanable extends PhantomReference<Object> implements Cleanable {
+        private final Runnable action;
+        private PhantomCleanable previous;
+        private PhantomCleanable following;
+
+        PhantomCleanable(Object referent, Cleaner cleaner, Runnable action) {
+            super(referent, cleaner.queue);
+            this.action = action;
+
+            this.following = cleanables;
+            if(cleanables != null) {
+                cleanables.previous = this;
+            }
+            cleanables = this;
+        }
+
+        private boolean remove() {
+            if(this.previous == null && cleanables != this) {
+                return false;
+            }
+
+            if(this.previous == null) {
+                cleanables = this.following;
+            } else {
+                this.previous.following = this.following;
+            }
+            if(this.following != null) {
+                this.following.previous = this.previous;
+            }
+
+            this.previous = null;
+            this.following = null;
+
+            return true;
+        }
+
+        public void clean() {
+            if(this.remove()) {
+                super.clear();
+                this.action.run();
+            }
+        }
+
+        public void clear() {
+            if(this.remove()) {
+                super.clear();
+            }
+        }
+    }
+}
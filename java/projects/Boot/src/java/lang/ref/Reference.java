@@ -0,0 +1,64 @@
+package java.lang.ref;
+
+public abstract class Reference<T> {
+    private T referent;
+    ReferenceQueue<? super T> queue;
+    Reference<?> next;
+
+    Reference(T referent) {
+        this(referent, null);
+    }
+
+    Reference(T referent, ReferenceQueue<? super T> queue) {
+        this.referent = referent;
+        this.queue = queue;
+    }
+
+    public T get() {
+        return this.referent;
+    }
+
+    public final boolean refersTo(T object) {
+        return this.referent == object;
+    }
+
+    public void clear() {
+        this.referent = null;
+    }
+
+    public boolean isEnqueued() {
+        return this.queue == ReferenceQueue.ENQUEUED;
+    }
+
+    public boolean enqueue() {
+        this.referent = null;
+
+        ReferenceQueue<? super T> queue = this.queue;
+        return queue != null && queue.enqueue(this);
+    }
+
+    public static void reachabilityFence(Object object) {
+    }
+
+    private static native Reference<?> getPendingReference();
+
+    // Runs on the reference handler thread after a collection cleared some referents.
+    static void processPendingReferences() {
+        Reference<?> reference;
+
+        while((reference = getPendingReference()) != null) {
+            if(reference instanceof Cleaner.Cleanable) {
+                try {
+                    ((Cleaner.Cleanable) reference).clean();
+                } catch(Throwable throwable) {
+                }
+            } else {
+                ReferenceQueue queue = reference.queue;
+
+                if(queue != null) {
+                    queue.enqueue(reference);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+package java.lang.ref;
+
+public class ReferenceQueue<T> {
+    static final ReferenceQueue<Object> ENQUEUED = new ReferenceQueue<Object>();
+
+    private Reference<? extends T> head;
+    private Reference<? extends T> tail;
+
+    public ReferenceQueue() {
+    }
+
+    boolean enqueue(Reference<? extends T> reference) {
+        if(reference.queue != this) {
+            return false;
+        }
+
+        reference.queue = ENQUEUED;
+        reference.next = null;
+
+        if(this.tail == null) {
+            this.head = reference;
+        } else {
+            this.tail.next = reference;
+        }
+        this.tail = reference;
+
+        return true;
+    }
+
+    public Reference<? extends T> poll() {
+        Reference<? extends T> reference = this.head;
+
+        if(reference != null) {
+            this.head = (Reference<? extends T>) reference.next;
+            if(this.head == null) {
+                this.tail = null;
+            }
+
+            reference.next = null;
+            reference.queue = null;
+        }
+
+        return reference;
+    }
+
+    public Reference<? extends T> remove() throws InterruptedException {
+        return this.remove(0);
+    }
+
+    // There is no clock to wait on, so a timeout counts the collections requested while waiting.
+    public Reference<? extends T> remove(long timeout) throws InterruptedException {
+        if(timeout < 0) {
+            throw new IllegalArgumentException("Negative timeout value");
+        }
+
+        Reference<? extends T> reference = this.poll();
+
+        for(long waited = 0; reference == null && (timeout == 0 || waited < timeout); waited++) {
+            System.gc();
+            reference = this.poll();
+        }
+
+        return reference;
+    }
+}
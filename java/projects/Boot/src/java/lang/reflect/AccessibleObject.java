@@ -0,0 +1,24 @@
+package java.lang.reflect;
+
+public class AccessibleObject {
+    boolean override;
+
+    public void setAccessible(boolean flag) {
+        this.override = flag;
+    }
+
+    public boolean isAccessible() {
+        return this.override;
+    }
+
+    private static native String checkAccess(Class clazz, int modifiers);
+
+    void verifyAccess(Class clazz, int modifiers) throws IllegalAccessException {
+        if(this.override) return;
+
+        String error = checkAccess(clazz, modifiers);
+        if(error != null) {
+            throw new IllegalAccessException(error);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+package java.lang.reflect;
+
+public final class Constructor extends AccessibleObject {
+    private Class clazz;
+    private String descriptor;
+    private int modifiers;
+
+    private Constructor() {
+    }
+
+    public Class getDeclaringClass() {
+        return this.clazz;
+    }
+
+    public String getName() {
+        return this.clazz.getName();
+    }
+
+    public int getModifiers() {
+        return this.modifiers;
+    }
+
+    public native int getParameterCount();
+    public native Class[] getParameterTypes();
+
+    private native String checkArguments(Object[] args);
+    private native Object allocate();
+    private native void construct(Object instance, Object[] args);
+
+    public Object newInstance(Object... args) throws InstantiationException, IllegalAccessException, InvocationTargetException {
+        this.verifyAccess(this.clazz, this.modifiers);
+
+        if(Modifier.isAbstract(this.clazz.getModifiers())) {
+            throw new InstantiationException(this.clazz.getName());
+        }
+
+        if(args == null) {
+            args = new Object[0];
+        }
+
+        String error = this.checkArguments(args);
+        if(error != null) {
+            throw new IllegalArgumentException(error);
+        }
+
+        Object instance = this.allocate();
+
+        try {
+            this.construct(instance, args);
+        } catch(Throwable throwable) {
+            throw new InvocationTargetException(throwable);
+        }
+
+        return instance;
+    }
+
+    @Override
+    public native String toString();
+}
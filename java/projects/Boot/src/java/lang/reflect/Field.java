@@ -0,0 +1,64 @@
+package java.lang.reflect;
+
+public final class Field extends AccessibleObject {
+    private Class clazz;
+    private String name;
+    private String descriptor;
+    private int modifiers;
+
+    private Field() {
+    }
+
+    public Class getDeclaringClass() {
+        return this.clazz;
+    }
+
+    public String getName() {
+        return this.name;
+    }
+
+    public int getModifiers() {
+        return this.modifiers;
+    }
+
+    public native Class getType();
+
+    private native Object get0(Object obj);
+    private native boolean set0(Object obj, Object value);
+
+    private void checkReceiver(Object obj) {
+        if(Modifier.isStatic(this.modifiers)) return;
+
+        if(obj == null) {
+            throw new NullPointerException(new StringBuilder().append("Cannot access \"").append(this.toString()).append("\" on null").toString());
+        }
+
+        if(!this.clazz.isInstance(obj)) {
+            throw new IllegalArgumentException(new StringBuilder().append("Can not access ").append(this.toString()).append(" on ").append(obj.getClass().getName()).toString());
+        }
+    }
+
+    public Object get(Object obj) throws IllegalAccessException {
+        this.verifyAccess(this.clazz, this.modifiers);
+        this.checkReceiver(obj);
+
+        return this.get0(obj);
+    }
+
+    public void set(Object obj, Object value) throws IllegalAccessException {
+        this.verifyAccess(this.clazz, this.modifiers);
+
+        if(Modifier.isFinal(this.modifiers) && (!this.override || Modifier.isStatic(this.modifiers))) {
+            throw new IllegalAccessException(new StringBuilder().append("Can not set final field ").append(this.toString()).toString());
+        }
+
+        this.checkReceiver(obj);
+
+        if(!this.set0(obj, value)) {
+            throw new IllegalArgumentException(new StringBuilder().append("Can not set ").append(this.toString()).append(" to ").append(value == null ? "null value" : value.getClass().getName()).toString());
+        }
+    }
+
+    @Override
+    public native String toString();
+}
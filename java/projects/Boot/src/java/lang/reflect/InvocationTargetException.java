@@ -0,0 +1,19 @@
+package java.lang.reflect;
+
+public class InvocationTargetException extends ReflectiveOperationException {
+    public InvocationTargetException() {
+        super();
+    }
+
+    public InvocationTargetException(Throwable target) {
+        super(null, target);
+    }
+
+    public InvocationTargetException(Throwable target, String message) {
+        super(message, target);
+    }
+
+    public Throwable getTargetException() {
+        return this.getCause();
+    }
+}
@@ -0,0 +1,62 @@
+package java.lang.reflect;
+
+public final class Method extends AccessibleObject {
+    private Class clazz;
+    private String name;
+    private String descriptor;
+    private int modifiers;
+
+    private Method() {
+    }
+
+    public Class getDeclaringClass() {
+        return this.clazz;
+    }
+
+    public String getName() {
+        return this.name;
+    }
+
+    public int getModifiers() {
+        return this.modifiers;
+    }
+
+    public native int getParameterCount();
+    public native Class[] getParameterTypes();
+    public native Class getReturnType();
+
+    private native String checkArguments(Object[] args);
+    private native Object invoke0(Object obj, Object[] args);
+
+    public Object invoke(Object obj, Object... args) throws IllegalAccessException, InvocationTargetException {
+        this.verifyAccess(this.clazz, this.modifiers);
+
+        if(!Modifier.isStatic(this.modifiers)) {
+            if(obj == null) {
+                throw new NullPointerException(new StringBuilder().append("Cannot invoke \"").append(this.toString()).append("\" on null").toString());
+            }
+
+            if(!this.clazz.isInstance(obj)) {
+                throw new IllegalArgumentException("object is not an instance of declaring class");
+            }
+        }
+
+        if(args == null) {
+            args = new Object[0];
+        }
+
+        String error = this.checkArguments(args);
+        if(error != null) {
+            throw new IllegalArgumentException(error);
+        }
+
+        try {
+            return this.invoke0(obj, args);
+        } catch(Throwable throwable) {
+            throw new InvocationTargetException(throwable);
+        }
+    }
+
+    @Override
+    public native String toString();
+}
@@ -0,0 +1,8 @@
+package java.security;
+
+// There is no security manager, so actions just run.
+public final class AccessController {
+    private AccessController() {}
+
+    public static native <T> T doPrivileged(PrivilegedAction<T> action);
+}
@@ -0,0 +1,87 @@
+package java.util;
+
+public class Properties {
+    private String[] keys;
+    private String[] values;
+    private int size;
+
+    public Properties() {
+        this.keys = new String[16];
+        this.values = new String[16];
+        this.size = 0;
+    }
+
+    private int indexOf(String key) {
+        for(int i = 0; i < this.size; i++) {
+            if(this.keys[i].equals(key)) {
+                return i;
+            }
+        }
+
+        return -1;
+    }
+
+    public String getProperty(String key) {
+        int index = this.indexOf(key);
+
+        return index < 0 ? null : this.values[index];
+    }
+
+    public String getProperty(String key, String defaultValue) {
+        String value = this.getProperty(key);
+
+        return value == null ? defaultValue : value;
+    }
+
+    public Object setProperty(String key, String value) {
+        if(key == null || value == null) {
+            throw new NullPointerException();
+        }
+
+        int index = this.indexOf(key);
+
+        if(index >= 0) {
+            String previous = this.values[index];
+            this.values[index] = value;
+
+            return previous;
+        }
+
+        if(this.size == this.keys.length) {
+            String[] keys = new String[this.size * 2];
+            String[] values = new String[this.size * 2];
+            System.arraycopy(this.keys, 0, keys, 0, this.size);
+            System.arraycopy(this.values, 0, values, 0, this.size);
+            this.keys = keys;
+            this.values = values;
+        }
+
+        this.keys[this.size] = key;
+        this.values[this.size] = value;
+        this.size++;
+
+        return null;
+    }
+
+    public int size() {
+        return this.size;
+    }
+
+    public boolean isEmpty() {
+        return this.size == 0;
+    }
+
+    public Enumeration<?> propertyNames() {
+        return new Enumeration<String>() {
+            private int position = 0;
+
+            public boolean hasMoreElements() {
+                return this.position < Properties.this.size;
+            }
+
+            public String nextElement() {
+                return Properties.this.keys[this.position++];
+            }
+        };
+    }
+}
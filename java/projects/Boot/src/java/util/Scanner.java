@@ -10,18 +10,35 @@ public class Scanner {
     }
 
     public String nextLine() {
-        StringBuilder stringBuilder = new StringBuilder();
+        byte[] buffer = new byte[64];
+        int length = 0;
 
         while(true) {
             int next = inputStream.read();
             
             if(next == '\0' || next == '\n') {
                 break;
-            } else {
-                stringBuilder.append((char)next);
             }
+
+            if(length == buffer.length) {
+                byte[] grown = new byte[buffer.length * 2];
+
+                for(int i = 0; i < length; i++) {
+                    grown[i] = buffer[i];
+                }
+
+                buffer = grown;
+            }
+
+            buffer[length++] = (byte)next;
+        }
+
+        byte[] line = new byte[length];
+
+        for(int i = 0; i < length; i++) {
+            line[i] = buffer[i];
         }
 
-        return stringBuilder.toString();
+        return new String(line);
     }
 }
@@ -15,6 +15,32 @@ public class Main {
             test = new TestClass();
         } else if(testName.equals("math")) {
             test = new TestMath();
+        } else if(testName.equals("exception")) {
+            test = new TestException();
+        } else if(testName.equals("array")) {
+            test = new TestArray();
+        } else if(testName.equals("init")) {
+            test = new TestInit();
+        } else if(testName.equals("string")) {
+            test = new TestString();
+        } else if(testName.equals("reflection")) {
+            test = new TestReflection();
+        } else if(testName.equals("access")) {
+            test = new TestAccess();
+        } else if(testName.equals("reference")) {
+            test = new TestReference();
+        } else if(testName.equals("object")) {
+            test = new TestObject();
+        } else if(testName.equals("number")) {
+            test = new TestNumber();
+        } else if(testName.equals("system")) {
+            test = new TestSystem();
+        } else if(testName.equals("shutdown")) {
+            test = new TestShutdown();
+        } else if(testName.equals("collections")) {
+            test = new TestCollections();
+        } else if(testName.equals("time")) {
+            test = new TestTime();
         }
 
         if(test == null) {
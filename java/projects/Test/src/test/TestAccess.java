@@ -0,0 +1,94 @@
+package test;
+
+import java.lang.reflect.*;
+import test.plugin.*;
+
+public class TestAccess extends Test {
+    private static int secret = 40;
+    private int instanceSecret = 1;
+
+    private static int reveal() {
+        return secret + 2;
+    }
+
+    private static class Vault {
+        private int contents;
+
+        private Vault(int contents) {
+            this.contents = contents;
+        }
+
+        private int open() {
+            return this.contents + reveal();
+        }
+
+        private static class Lock {
+            private boolean locked = true;
+
+            private void unlock(Vault vault) {
+                this.locked = false;
+                vault.contents += secret;
+            }
+        }
+    }
+
+    private class Inner {
+        private int read() {
+            return instanceSecret;
+        }
+    }
+
+    private static class Counter extends Greeter {
+        private long bump() {
+            this.count += 10;
+            return this.count;
+        }
+    }
+
+    private void testNestmates() {
+        System.out.println("[Test Nestmates]");
+
+        Vault vault = new Vault(5);
+        System.out.println(new StringBuilder().append("contents = ").append(vault.contents).toString());
+        System.out.println(new StringBuilder().append("open = ").append(vault.open()).toString());
+
+        Vault.Lock lock = new Vault.Lock();
+        lock.unlock(vault);
+        System.out.println(new StringBuilder().append("unlocked = ").append(!lock.locked).append(" ").append(vault.contents).toString());
+
+        this.instanceSecret = 7;
+        System.out.println(new StringBuilder().append("inner = ").append(new Inner().read()).toString());
+    }
+
+    private void testProtected() {
+        System.out.println("[Test Protected]");
+
+        Counter counter = new Counter();
+        System.out.println(new StringBuilder().append("bump = ").append(counter.bump()).toString());
+        System.out.println(new StringBuilder().append("public = ").append(counter.greet("access")).toString());
+    }
+
+    private void testReflection() throws Exception {
+        System.out.println("[Test Reflection]");
+
+        Method open = Vault.class.getDeclaredMethods()[0];
+        System.out.println(new StringBuilder().append("nestmate = ").append(((Integer)open.invoke(new Vault(1))).intValue()).toString());
+
+        try {
+            Greeter.class.getDeclaredField("greeting").get(new Greeter());
+        } catch(IllegalAccessException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    @Override
+    public void run() {
+        try {
+            testNestmates();
+            testProtected();
+            testReflection();
+        } catch(Exception exception) {
+            System.out.println(exception.toString());
+        }
+    }
+}
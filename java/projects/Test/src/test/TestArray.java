@@ -0,0 +1,101 @@
+package test;
+
+public class TestArray extends Test {
+    private void testDefaults() {
+        System.out.println("[Test Defaults]");
+
+        int[] ints = new int[3];
+        long[] longs = new long[2];
+        boolean[] booleans = new boolean[2];
+        char[] chars = new char[2];
+        String[] strings = new String[2];
+
+        System.out.println(new StringBuilder().append("int: ").append(ints[0]).append(" ").append(ints.length).toString());
+        System.out.println(new StringBuilder().append("long: ").append(longs[1]).toString());
+        System.out.println(new StringBuilder().append("boolean: ").append(booleans[1]).toString());
+        System.out.println(new StringBuilder().append("char: ").append((int) chars[0]).toString());
+        System.out.println(new StringBuilder().append("String: ").append(strings[0] == null).toString());
+    }
+
+    private void testTypes() {
+        System.out.println("[Test Types]");
+
+        byte[] bytes = new byte[1];
+        bytes[0] = (byte) 200;
+        short[] shorts = new short[1];
+        shorts[0] = (short) 40000;
+        char[] chars = new char[1];
+        chars[0] = (char) 65601;
+
+        System.out.println(new StringBuilder().append("byte: ").append(bytes[0]).toString());
+        System.out.println(new StringBuilder().append("short: ").append(shorts[0]).toString());
+        System.out.println(new StringBuilder().append("char: ").append((int) chars[0]).toString());
+
+        byte[] large = new byte[1000000];
+        large[999999] = 7;
+        System.out.println(new StringBuilder().append("large: ").append(large[999999]).append(" ").append(large.length).toString());
+    }
+
+    private void testClasses() {
+        System.out.println("[Test Classes]");
+
+        int[] ints = new int[1];
+        String[] strings = new String[1];
+        int[][] matrix = new int[2][3];
+
+        System.out.println(ints.getClass().getName());
+        System.out.println(strings.getClass().getName());
+        System.out.println(matrix.getClass().getName());
+        System.out.println(matrix[1].getClass().getName());
+        System.out.println(new StringBuilder().append("matrix: ").append(matrix[1][2]).append(" ").append(matrix[1].length).toString());
+
+        Object object = strings;
+        System.out.println(new StringBuilder().append("Object[]: ").append(object instanceof Object[]).toString());
+        System.out.println(new StringBuilder().append("Cloneable: ").append(object instanceof Cloneable).toString());
+        System.out.println(new StringBuilder().append("int[]: ").append(object instanceof int[]).toString());
+    }
+
+    private void testExceptions() {
+        System.out.println("[Test Exceptions]");
+
+        try {
+            int[] ints = new int[-1];
+        } catch(NegativeArraySizeException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            Object[] objects = new String[1];
+            objects[0] = new Object();
+        } catch(ArrayStoreException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            int[] ints = new int[2];
+            ints[2] = 1;
+        } catch(ArrayIndexOutOfBoundsException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            Object[] objects = new Object[Integer.MAX_VALUE];
+        } catch(OutOfMemoryError error) {
+            System.out.println(error.getClass().getName());
+        }
+
+        try {
+            long[][] longs = new long[1][Integer.MAX_VALUE];
+        } catch(OutOfMemoryError error) {
+            System.out.println(error.getClass().getName());
+        }
+    }
+
+    @Override
+    public void run() {
+        testDefaults();
+        testTypes();
+        testClasses();
+        testExceptions();
+    }
+}
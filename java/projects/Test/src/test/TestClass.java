@@ -35,6 +35,21 @@ public class TestClass extends Test {
         }
     }
 
+    private interface Shape {
+    }
+
+    private interface Polygon extends Shape {
+    }
+
+    private interface Named {
+    }
+
+    private static class Square implements Polygon, Named {
+    }
+
+    private static class BigSquare extends Square {
+    }
+
     private class GenericClass<T> {
         private T t; 
 
@@ -47,6 +62,63 @@ public class TestClass extends Test {
         }
     }
 
+    private class ParentFields {
+        protected int value = 1;
+        protected boolean flag;
+        protected double ratio;
+
+        public int parentValue() {
+            return this.value;
+        }
+    }
+
+    private class ChildFields extends ParentFields {
+        protected int value = 2;
+        protected long count;
+
+        public int childValue() {
+            return this.value;
+        }
+    }
+
+    private interface Constants {
+        int[] VALUES = new int[] { 3, 4 };
+        StringBuilder NAME = new StringBuilder().append("Constants");
+    }
+
+    private static class StaticParent implements Constants {
+        static int counter = 10;
+    }
+
+    private static class StaticChild extends StaticParent {
+        static int childCounter = 20;
+    }
+
+    private void testStaticFields() {
+        System.out.println("[Test Static Fields]");
+
+        StaticChild.counter += 1;
+        System.out.println(new StringBuilder().append("StaticChild.counter = ").append(StaticChild.counter).append(", StaticParent.counter = ").append(StaticParent.counter).toString());
+        System.out.println(new StringBuilder().append("StaticChild.VALUES[1] = ").append(StaticChild.VALUES[1]).toString());
+        System.out.println(new StringBuilder().append("StaticParent.NAME = ").append(StaticParent.NAME.toString()).toString());
+        System.out.println(new StringBuilder().append("StaticChild.childCounter = ").append(StaticChild.childCounter).toString());
+    }
+
+    private void testFields() {
+        System.out.println("[Test Fields]");
+
+        ChildFields child = new ChildFields();
+        ParentFields parent = child;
+
+        System.out.println(new StringBuilder().append("parent.value = ").append(parent.value).append(", child.value = ").append(child.value).toString());
+        System.out.println(new StringBuilder().append("parentValue() = ").append(child.parentValue()).append(", childValue() = ").append(child.childValue()).toString());
+        System.out.println(new StringBuilder().append("flag = ").append(child.flag).append(", count = ").append(child.count).append(", ratio == 0 = ").append(child.ratio == 0.0).toString());
+
+        child.count += 5;
+        child.flag = !child.flag;
+        System.out.println(new StringBuilder().append("flag = ").append(child.flag).append(", count = ").append(child.count).toString());
+    }
+
     private void testInheritance() {
         System.out.println("[Test Inheritance]");
 
@@ -88,6 +160,35 @@ public class TestClass extends Test {
         System.out.println(new StringBuilder().append("ExtendClass instanceof AbstractClass = ").append(extendClass instanceof AbstractClass).toString());
         System.out.println(new StringBuilder().append("ExtendClass instanceof Interface = ").append(extendClass instanceof Interface).toString());
         System.out.println(new StringBuilder().append("ExtendClass instanceof Test = ").append(extendClass instanceof Test).toString());
+
+        Object square = new BigSquare();
+        System.out.println(new StringBuilder().append("BigSquare instanceof Shape = ").append(square instanceof Shape).append(", Named = ").append(square instanceof Named).append(", Polygon = ").append(square instanceof Polygon).toString());
+
+        Object squares = new BigSquare[2];
+        System.out.println(new StringBuilder().append("BigSquare[] instanceof Shape[] = ").append(squares instanceof Shape[]).append(", Object[] = ").append(squares instanceof Object[]).append(", Named[] = ").append(squares instanceof Named[]).toString());
+        System.out.println(new StringBuilder().append("BigSquare[] instanceof Cloneable = ").append(squares instanceof Cloneable).append(", Serializable = ").append(squares instanceof java.io.Serializable).toString());
+
+        Object matrix = new int[2][2];
+        System.out.println(new StringBuilder().append("int[][] instanceof Object[] = ").append(matrix instanceof Object[]).append(", long[][] = ").append(matrix instanceof long[][]).append(", Cloneable[] = ").append(matrix instanceof Cloneable[]).toString());
+        System.out.println(new StringBuilder().append("int[] instanceof Object[] = ").append(((Object)new int[1]) instanceof Object[]).toString());
+
+        System.out.println(new StringBuilder().append("Shape.isAssignableFrom(BigSquare) = ").append(Shape.class.isAssignableFrom(BigSquare.class)).append(", reverse = ").append(BigSquare.class.isAssignableFrom(Shape.class)).toString());
+        System.out.println(new StringBuilder().append("Object.isAssignableFrom(int) = ").append(Object.class.isAssignableFrom(int.class)).append(", Object.isAssignableFrom(Shape) = ").append(Object.class.isAssignableFrom(Shape.class)).toString());
+        System.out.println(new StringBuilder().append("Object[].isAssignableFrom(Named[][]) = ").append(Object[].class.isAssignableFrom(Named[][].class)).toString());
+
+        try {
+            Object text = "text";
+            Named named = (Named)text;
+        } catch(ClassCastException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            Object[] shapes = new Shape[1];
+            shapes[0] = "text";
+        } catch(ArrayStoreException exception) {
+            System.out.println(exception.toString());
+        }
     }
 
     @Override
@@ -97,5 +198,7 @@ public class TestClass extends Test {
         testGeneric();
         testInterface();
         testInstanceOf();
+        testFields();
+        testStaticFields();
     }
 }
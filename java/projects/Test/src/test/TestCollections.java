@@ -0,0 +1,83 @@
+package test;
+
+import java.util.ArrayDeque;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.HashMap;
+import java.util.Iterator;
+import java.util.List;
+import java.util.Map;
+import java.util.TreeMap;
+
+// Runs with the JDK's java.base loaded after the boot jar, which supplies these classes.
+public class TestCollections extends Test {
+    private void testList() {
+        System.out.println("[Test List]");
+
+        List<String> list = new ArrayList<String>();
+        list.add("pear");
+        list.add("apple");
+        list.add("fig");
+        list.add(1, "kiwi");
+
+        System.out.println(new StringBuilder().append("size = ").append(list.size()).toString());
+        System.out.println(new StringBuilder().append("get(1) = ").append(list.get(1)).toString());
+        System.out.println(new StringBuilder().append("contains fig = ").append(list.contains("fig")).toString());
+        System.out.println(new StringBuilder().append("list = ").append(list).toString());
+
+        Collections.sort(list);
+        System.out.println(new StringBuilder().append("sorted = ").append(list).toString());
+
+        list.remove("kiwi");
+        StringBuilder builder = new StringBuilder();
+        for(Iterator<String> iterator = list.iterator(); iterator.hasNext();) {
+            builder.append(iterator.next()).append(";");
+        }
+        System.out.println(new StringBuilder().append("iterated = ").append(builder.toString()).toString());
+
+        try {
+            list.get(10);
+        } catch(IndexOutOfBoundsException exception) {
+            System.out.println(new StringBuilder().append("IndexOutOfBoundsException: ").append(exception.getMessage()).toString());
+        }
+    }
+
+    private void testMap() {
+        System.out.println("[Test Map]");
+
+        Map<String, Integer> map = new HashMap<String, Integer>();
+        for(int i = 0; i < 20; i++) {
+            map.put(new StringBuilder().append("key").append(i).toString(), Integer.valueOf(i * i));
+        }
+
+        System.out.println(new StringBuilder().append("size = ").append(map.size()).toString());
+        System.out.println(new StringBuilder().append("key7 = ").append(map.get("key7")).toString());
+        System.out.println(new StringBuilder().append("missing = ").append(map.get("missing")).toString());
+        System.out.println(new StringBuilder().append("remove key3 = ").append(map.remove("key3")).toString());
+        System.out.println(new StringBuilder().append("contains key3 = ").append(map.containsKey("key3")).toString());
+
+        Map<String, Integer> sorted = new TreeMap<String, Integer>();
+        sorted.put("c", Integer.valueOf(3));
+        sorted.put("a", Integer.valueOf(1));
+        sorted.put("b", Integer.valueOf(2));
+        System.out.println(new StringBuilder().append("tree = ").append(sorted).toString());
+    }
+
+    private void testDeque() {
+        System.out.println("[Test Deque]");
+
+        ArrayDeque<Integer> deque = new ArrayDeque<Integer>();
+        deque.addFirst(Integer.valueOf(2));
+        deque.addFirst(Integer.valueOf(1));
+        deque.addLast(Integer.valueOf(3));
+
+        System.out.println(new StringBuilder().append("deque = ").append(deque).toString());
+        System.out.println(new StringBuilder().append("poll = ").append(deque.pollFirst()).append(" ").append(deque.pollLast()).toString());
+    }
+
+    public void run() {
+        this.testList();
+        this.testMap();
+        this.testDeque();
+    }
+}
@@ -0,0 +1,107 @@
+package test;
+
+public class TestException extends Test {
+    private void thrower(int depth) {
+        if(depth == 0) {
+            throw new RuntimeException("Thrown at depth 0");
+        }
+
+        thrower(depth - 1);
+    }
+
+    private void testCatch() {
+        System.out.println("[Test Catch]");
+
+        try {
+            thrower(2);
+        } catch(RuntimeException exception) {
+            System.out.println(new StringBuilder().append("Caught: ").append(exception.getMessage()).toString());
+            System.out.println(new StringBuilder().append("Top: ").append(exception.getStackTrace()[0].toString()).toString());
+        }
+    }
+
+    private void testFinally() {
+        System.out.println("[Test Finally]");
+
+        try {
+            try {
+                thrower(0);
+            } finally {
+                System.out.println("Finally ran");
+            }
+        } catch(Exception exception) {
+            System.out.println(new StringBuilder().append("Caught after finally: ").append(exception.toString()).toString());
+        }
+    }
+
+    private void testPrintStackTrace() {
+        System.out.println("[Test PrintStackTrace]");
+
+        try {
+            thrower(1);
+        } catch(RuntimeException exception) {
+            exception.printStackTrace(System.out);
+        }
+
+        try {
+            wrapper();
+        } catch(RuntimeException exception) {
+            exception.printStackTrace(System.out);
+        }
+    }
+
+    private void wrapper() {
+        try {
+            thrower(2);
+        } catch(RuntimeException exception) {
+            throw new RuntimeException("Wrapped", exception);
+        }
+    }
+
+    private Object missing() {
+        return null;
+    }
+
+    private void printNull(String label, NullPointerException exception) {
+        System.out.println(new StringBuilder().append(label).append(": NullPointerException at ").append(exception.getStackTrace()[0].toString()).toString());
+    }
+
+    private void testNull() {
+        System.out.println("[Test Null]");
+
+        try {
+            String string = (String)missing();
+            System.out.println(string.length());
+        } catch(NullPointerException exception) {
+            printNull("length", exception);
+        }
+
+        try {
+            System.out.println(missing().hashCode());
+        } catch(NullPointerException exception) {
+            printNull("hashCode", exception);
+        }
+
+        try {
+            RuntimeException thrown = (RuntimeException)missing();
+            throw thrown;
+        } catch(NullPointerException exception) {
+            printNull("throw", exception);
+        }
+    }
+
+    private void testUncaught() {
+        System.out.println("[Test Uncaught]");
+
+        throw new RuntimeException("Uncaught", new Exception("Cause"));
+    }
+
+    @Override
+    public void run() {
+        testCatch();
+        testFinally();
+        testPrintStackTrace();
+        testNull();
+        testUncaught();
+    }
+}
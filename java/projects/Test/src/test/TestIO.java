@@ -29,9 +29,20 @@ public class TestIO extends Test {
         } catch (Exception exception) {}
     }
 
+    private void testMissingFile() {
+        System.out.println("[Test Missing File]");
+        try {
+            new FileInputStream("/nonexistent/missing.txt");
+            System.out.println("Opened");
+        } catch (FileNotFoundException exception) {
+            System.out.println(new StringBuilder().append("FileNotFoundException: ").append(exception.getMessage()).toString());
+        }
+    }
+
     @Override
     public void run() {
         testSystem();
         testFile();
+        testMissingFile();
     }
 }
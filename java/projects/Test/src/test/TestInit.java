@@ -0,0 +1,78 @@
+package test;
+
+public class TestInit extends Test {
+    private static StringBuilder log = new StringBuilder();
+
+    private static class Parent {
+        static int parentValue = record("Parent");
+    }
+
+    private static class Child extends Parent {
+        static int childValue = record("Child");
+
+        static void touch() {
+            record("Child.touch");
+        }
+    }
+
+    private static class Unused {
+        static int value = record("Unused");
+    }
+
+    private static class Cycle {
+        static int first = record("Cycle");
+        static int second = Cycle.first + 1;
+    }
+
+    private static class Broken {
+        static int value = fail();
+
+        static int fail() {
+            throw new IllegalStateException("Broken init");
+        }
+    }
+
+    private static int record(String name) {
+        log.append(name).append(";");
+
+        return 1;
+    }
+
+    private void testOrder() {
+        System.out.println("[Test Order]");
+
+        Unused unused = null;
+        System.out.println(new StringBuilder().append("Before: ").append(log.toString()).toString());
+
+        Child.touch();
+        System.out.println(new StringBuilder().append("After touch: ").append(log.toString()).toString());
+
+        int value = Child.childValue + Parent.parentValue;
+        System.out.println(new StringBuilder().append("After read: ").append(log.toString()).append(" value=").append(value).toString());
+
+        System.out.println(new StringBuilder().append("Cycle.second = ").append(Cycle.second).toString());
+    }
+
+    private void testFailure() {
+        System.out.println("[Test Failure]");
+
+        try {
+            System.out.println(Broken.value);
+        } catch(ExceptionInInitializerError error) {
+            System.out.println(error.toString());
+            System.out.println(new StringBuilder().append("Cause: ").append(error.getCause().toString()).toString());
+        }
+
+        try {
+            System.out.println(Broken.value);
+        } catch(NoClassDefFoundError error) {
+            System.out.println(error.toString());
+        }
+    }
+
+    @Override
+    public void run() {
+        testOrder();
+        testFailure();
+    }
+}
@@ -63,10 +63,164 @@ public class TestMath extends Test {
         System.out.println(new StringBuilder().append(VALUE).append(" != ").append(COPY_VALUE).append(" = ").append(VALUE != COPY_VALUE).toString());
     }
 
+    private void testOverflow() {
+        int maxInt = 2147483647;
+        long maxLong = 9223372036854775807L;
+        int minInt = maxInt + 1;
+        int negativeOne = -1;
+
+        System.out.println(new StringBuilder().append("MAX_INT + 1 = ").append(maxInt + 1).toString());
+        System.out.println(new StringBuilder().append("MAX_INT * 2 = ").append(maxInt * 2).toString());
+        System.out.println(new StringBuilder().append("MIN_INT / -1 = ").append(minInt / negativeOne).toString());
+        System.out.println(new StringBuilder().append("MIN_INT % -1 = ").append(minInt % negativeOne).toString());
+        System.out.println(new StringBuilder().append("-MIN_INT = ").append(-minInt).toString());
+        System.out.println(new StringBuilder().append("MAX_LONG + 1 < 0 = ").append(maxLong + 1 < 0).toString());
+
+        int zero = 0;
+        try {
+            System.out.println(1 / zero);
+        } catch(ArithmeticException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            System.out.println(1L % zero);
+        } catch(ArithmeticException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    private void testShift() {
+        int one = 1;
+        int negative = -8;
+        long oneLong = 1L;
+
+        System.out.println(new StringBuilder().append("1 << 33 = ").append(one << 33).toString());
+        System.out.println(new StringBuilder().append("1L << 65 = ").append(oneLong << 65).toString());
+        System.out.println(new StringBuilder().append("-8 >> 1 = ").append(negative >> 1).toString());
+        System.out.println(new StringBuilder().append("-8 >>> 28 = ").append(negative >>> 28).toString());
+        System.out.println(new StringBuilder().append("-8L >>> 60 = ").append(((long) negative) >>> 60).toString());
+    }
+
+    private void testFloat() {
+        float zero = 0.0f;
+        float nan = zero / zero;
+        double large = 1e30;
+
+        System.out.println(new StringBuilder().append("NaN < 1 = ").append(nan < 1.0f).toString());
+        System.out.println(new StringBuilder().append("NaN > 1 = ").append(nan > 1.0f).toString());
+        System.out.println(new StringBuilder().append("NaN == NaN = ").append(nan == nan).toString());
+        System.out.println(new StringBuilder().append("NaN != NaN = ").append(nan != nan).toString());
+        System.out.println(new StringBuilder().append("(int) NaN = ").append((int) nan).toString());
+        System.out.println(new StringBuilder().append("(int) 1e30 = ").append((int) large).toString());
+        System.out.println(new StringBuilder().append("(int) -1e30 = ").append((int) -large).toString());
+        System.out.println(new StringBuilder().append("(long) 1e30 > 0 = ").append((long) large > 0).toString());
+        System.out.println(new StringBuilder().append("(int) 2.9 = ").append((int) (large / large + 1.9)).toString());
+        System.out.println(new StringBuilder().append("1 / 0.0 > MAX = ").append(1.0 / zero > large).toString());
+    }
+
+    private void testNarrowing() {
+        int value = 200;
+        int wide = 65601;
+        byte negative = -1;
+        short shortNegative = -2;
+
+        System.out.println(new StringBuilder().append("(byte) 200 = ").append((byte) value).toString());
+        System.out.println(new StringBuilder().append("(short) 40000 = ").append((short) (value * 200)).toString());
+        System.out.println(new StringBuilder().append("(char) 65601 = ").append((int) (char) wide).toString());
+        System.out.println(new StringBuilder().append("(char) -200 = ").append((int) (char) -value).toString());
+        System.out.println(new StringBuilder().append("byte -1 = ").append(negative).toString());
+        System.out.println(new StringBuilder().append("short -2 = ").append(shortNegative).toString());
+        System.out.println(new StringBuilder().append("(long) 200 * MAX_INT = ").append((long) value * 2147483647).toString());
+    }
+
+    private static long mix(long a, int b, double c, int d) {
+        return a * 1000 + b * 100 + (long) c * 10 + d;
+    }
+
+    private long chain(double a, long b, float c) {
+        long local = b;
+        double scaled = a * 2;
+        return local + (long) scaled + (long) c;
+    }
+
+    private long[] values = new long[2];
+    private long last;
+
+    private void testWide() {
+        System.out.println(new StringBuilder().append("mix = ").append(mix(7L, 3, 5.5, 1)).toString());
+        System.out.println(new StringBuilder().append("chain = ").append(chain(1.5, 40L, 2.0f)).toString());
+
+        values[1] = 5L;
+        values[1] += 10L;
+        long copy = last = values[1]++;
+        System.out.println(new StringBuilder().append("values[1] = ").append(values[1]).append(", copy = ").append(copy).append(", last = ").append(last).toString());
+    }
+
+    private static final double[] INPUTS = { 0.5, -1.25, 3.0, 100.0, 1e-8, 1e22, -0.0, 1.0 / 0.0, 0.0 / 0.0 };
+
+    private static long bits(double value) {
+        return Double.doubleToLongBits(value);
+    }
+
+    private void testStrictMath() {
+        for(int i = 0; i < INPUTS.length; i++) {
+            double x = INPUTS[i];
+            double y = INPUTS[(i + 1) % INPUTS.length];
+
+            System.out.println(new StringBuilder().append("trig[").append(i).append("] ")
+                .append(bits(StrictMath.sin(x))).append(" ").append(bits(StrictMath.cos(x))).append(" ")
+                .append(bits(StrictMath.tan(x))).append(" ").append(bits(StrictMath.asin(x / 200))).append(" ")
+                .append(bits(StrictMath.atan(x))).append(" ").append(bits(StrictMath.atan2(x, y))).toString());
+            System.out.println(new StringBuilder().append("exp[").append(i).append("] ")
+                .append(bits(StrictMath.exp(x))).append(" ").append(bits(StrictMath.log(x))).append(" ")
+                .append(bits(StrictMath.log10(x))).append(" ").append(bits(StrictMath.pow(x, y))).append(" ")
+                .append(bits(StrictMath.sqrt(x))).append(" ").append(bits(StrictMath.cbrt(x))).toString());
+            System.out.println(new StringBuilder().append("round[").append(i).append("] ")
+                .append(bits(StrictMath.floor(x))).append(" ").append(bits(StrictMath.ceil(x))).append(" ")
+                .append(bits(StrictMath.rint(x))).append(" ").append(bits(StrictMath.hypot(x, y))).append(" ")
+                .append(bits(StrictMath.IEEEremainder(x, y))).toString());
+        }
+
+        System.out.println(new StringBuilder().append("Math.sin == StrictMath.sin = ").append(bits(Math.sin(2.5)) == bits(StrictMath.sin(2.5))).toString());
+        System.out.println(new StringBuilder().append("Math.max(-0.0, 0.0) = ").append(bits(Math.max(-0.0, 0.0))).toString());
+        System.out.println(new StringBuilder().append("Math.min(-0.0, 0.0) = ").append(bits(Math.min(-0.0, 0.0))).toString());
+        System.out.println(new StringBuilder().append("Math.min(1, NaN) is NaN = ").append(Double.isNaN(Math.min(1.0, Double.NaN))).toString());
+        System.out.println(new StringBuilder().append("Math.abs(-0.0) = ").append(bits(Math.abs(-0.0))).toString());
+    }
+
+    private void testFloatBits() {
+        float one = 1.0f;
+        double third = 1.0 / 3.0;
+
+        System.out.println(new StringBuilder().append("floatToRawIntBits(1) = ").append(Float.floatToRawIntBits(one)).toString());
+        System.out.println(new StringBuilder().append("intBitsToFloat(0x40490fdb) == 3.1415927f = ").append(Float.intBitsToFloat(0x40490fdb) == 3.1415927f).toString());
+        System.out.println(new StringBuilder().append("doubleToRawLongBits(1/3) = ").append(Double.doubleToRawLongBits(third)).toString());
+        System.out.println(new StringBuilder().append("longBitsToDouble round trip = ").append(Double.longBitsToDouble(Double.doubleToRawLongBits(third)) == third).toString());
+        System.out.println(new StringBuilder().append("raw NaN bits = ").append(Double.doubleToRawLongBits(Double.longBitsToDouble(0x7ff0000000000001L))).toString());
+        System.out.println(new StringBuilder().append("canonical NaN bits = ").append(Double.doubleToLongBits(Double.longBitsToDouble(0x7ff0000000000001L))).toString());
+        System.out.println(new StringBuilder().append("canonical float NaN bits = ").append(Float.floatToIntBits(Float.intBitsToFloat(0x7f800001))).toString());
+
+        System.out.println(new StringBuilder().append("Double(1.5).hashCode() = ").append(Double.valueOf(1.5).hashCode()).toString());
+        System.out.println(new StringBuilder().append("Double(0.25).hashCode() = ").append(Double.valueOf(0.25).hashCode()).toString());
+        System.out.println(new StringBuilder().append("Float(1.5f).hashCode() = ").append(Float.valueOf(1.5f).hashCode()).toString());
+        System.out.println(new StringBuilder().append("Double(NaN).equals(NaN) = ").append(Double.valueOf(Double.NaN).equals(Double.valueOf(0.0 / 0.0))).toString());
+        System.out.println(new StringBuilder().append("Double(0.0).equals(-0.0) = ").append(Double.valueOf(0.0).equals(Double.valueOf(-0.0))).toString());
+        System.out.println(new StringBuilder().append("Float(NaN).equals(NaN) = ").append(Float.valueOf(Float.NaN).equals(Float.valueOf(0.0f / 0.0f))).toString());
+        System.out.println(new StringBuilder().append("Double.isInfinite(-1/0) = ").append(Double.isInfinite(-1.0 / 0.0)).toString());
+    }
+
     @Override
     public void run() {
         testPrimes();
         testFactorial();
         testLogic();
+        testOverflow();
+        testShift();
+        testFloat();
+        testNarrowing();
+        testWide();
+        testStrictMath();
+        testFloatBits();
     }
 }
@@ -0,0 +1,131 @@
+package test;
+
+public class TestNumber extends Test {
+    private static void parseInt(String string, int radix) {
+        try {
+            System.out.println(new StringBuilder().append("parseInt ").append(string).append(" ").append(radix).append(" = ").append(Integer.parseInt(string, radix)).toString());
+        } catch(NumberFormatException exception) {
+            System.out.println(new StringBuilder().append("NumberFormatException: ").append(exception.getMessage()).toString());
+        }
+    }
+
+    private static void parseLong(String string, int radix) {
+        try {
+            System.out.println(new StringBuilder().append("parseLong ").append(string).append(" ").append(radix).append(" = ").append(Long.parseLong(string, radix)).toString());
+        } catch(NumberFormatException exception) {
+            System.out.println(new StringBuilder().append("NumberFormatException: ").append(exception.getMessage()).toString());
+        }
+    }
+
+    private static void parseDouble(String string) {
+        try {
+            System.out.println(new StringBuilder().append("parseDouble ").append(string).append(" = ").append(Double.parseDouble(string)).toString());
+        } catch(NumberFormatException exception) {
+            System.out.println(new StringBuilder().append("NumberFormatException: ").append(exception.getMessage()).toString());
+        }
+    }
+
+    private static void parseFloat(String string) {
+        try {
+            System.out.println(new StringBuilder().append("parseFloat ").append(string).append(" = ").append(Float.parseFloat(string)).toString());
+        } catch(NumberFormatException exception) {
+            System.out.println(new StringBuilder().append("NumberFormatException: ").append(exception.getMessage()).toString());
+        }
+    }
+
+    private void testIntegers() {
+        System.out.println("[Test Integers]");
+
+        parseInt("42", 10);
+        parseInt("-2147483648", 10);
+        parseInt("+7f", 16);
+        parseInt("zik0zj", 36);
+        parseInt("2147483648", 10);
+        parseInt("fg", 16);
+        parseInt("", 10);
+        parseInt("-", 10);
+        parseInt(null, 10);
+        parseInt("1", 1);
+        parseInt("1", 37);
+        parseLong("-9223372036854775808", 10);
+        parseLong("7fffffffffffffff", 16);
+        parseLong("9223372036854775808", 10);
+
+        System.out.println(new StringBuilder().append(Integer.MIN_VALUE).append(" ").append(Integer.MAX_VALUE).toString());
+        System.out.println(new StringBuilder().append(Long.MIN_VALUE).append(" ").append(Long.MAX_VALUE).toString());
+        System.out.println(new StringBuilder().append(Integer.toString(255, 16)).append(" ").append(Integer.toString(-255, 2)).append(" ").append(Integer.toString(Integer.MIN_VALUE, 36)).toString());
+        System.out.println(new StringBuilder().append(Long.toString(Long.MIN_VALUE, 16)).append(" ").append(Long.toString(-255, 37)).toString());
+        System.out.println(new StringBuilder().append(Integer.valueOf("123").intValue() + 1).append(" ").append(Long.valueOf("-5").toString()).toString());
+    }
+
+    private void testDoubles() {
+        System.out.println("[Test Doubles]");
+
+        double[] values = new double[] {
+            0.0, -0.0, 1.0, -1.5, 0.1, 0.1 + 0.2, 1.0 / 3.0, 100.0, 1234567.0, 9999999.0, 1.0E7,
+            0.001, 9.9E-4, 1.0E-5, 123456789012.0, Math.PI, Math.E, Double.MAX_VALUE, Double.MIN_VALUE,
+            Double.MIN_NORMAL, Double.POSITIVE_INFINITY, Double.NEGATIVE_INFINITY, Double.NaN, 2.0E-3, 5.0E-324
+        };
+
+        for(int i = 0; i < values.length; i++) {
+            System.out.println(new StringBuilder().append("double ").append(values[i]).toString());
+        }
+
+        parseDouble("1.5");
+        parseDouble("  -0.25e2d ");
+        parseDouble(".5");
+        parseDouble("5.");
+        parseDouble("1e400");
+        parseDouble("1e-400");
+        parseDouble("-Infinity");
+        parseDouble("NaN");
+        parseDouble("0x1.8p1");
+        parseDouble("0x.1P-2");
+        parseDouble("2.2250738585072011e-308");
+        parseDouble("1.5x");
+        parseDouble("");
+        parseDouble("e5");
+        parseDouble("0x1.8");
+
+        System.out.println(new StringBuilder().append(Double.valueOf("2.5").doubleValue() * 2).toString());
+        System.out.println(String.valueOf(0.5));
+    }
+
+    private void testFloats() {
+        System.out.println("[Test Floats]");
+
+        float[] values = new float[] {
+            0.0f, -0.0f, 1.0f, 0.1f, 1.0f / 3.0f, 16777216.0f, 1.0E-5f,
+            Float.MAX_VALUE, Float.MIN_VALUE, Float.MIN_NORMAL, Float.NaN, Float.NEGATIVE_INFINITY
+        };
+
+        for(int i = 0; i < values.length; i++) {
+            System.out.println(new StringBuilder().append("float ").append(values[i]).toString());
+        }
+
+        parseFloat("3.4028236e38");
+        parseFloat("3.5e38");
+        parseFloat("1e-46");
+        parseFloat("0x1p-149");
+        parseFloat("0.1f");
+        parseFloat("--1");
+    }
+
+    private void testAppend() {
+        System.out.println("[Test Append]");
+
+        Object nothing = null;
+        String missing = null;
+
+        System.out.println(new StringBuilder().append(true).append(" ").append(false).append(" ").append(nothing).append(" ").append(missing).toString());
+        System.out.println(new StringBuilder().append(Integer.valueOf(7)).append(" ").append(Double.valueOf(0.75)).append(" ").append('c').toString());
+        System.out.println(new StringBuilder().append(String.valueOf(12L)).append(String.valueOf(true)).append(String.valueOf(nothing)).toString());
+    }
+
+    public void run() {
+        this.testIntegers();
+        this.testDoubles();
+        this.testFloats();
+        this.testAppend();
+    }
+}
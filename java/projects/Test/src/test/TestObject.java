@@ -0,0 +1,171 @@
+package test;
+
+public class TestObject extends Test {
+    private static class Point implements Cloneable {
+        private int x;
+        private int[] tags = new int[] { 1, 2 };
+
+        private Point(int x) {
+            this.x = x;
+        }
+
+        @Override
+        public Point clone() throws CloneNotSupportedException {
+            return (Point)super.clone();
+        }
+    }
+
+    private static class Failure extends RuntimeException implements Cloneable {
+        private Failure(String message) {
+            super(message);
+        }
+
+        @Override
+        public Failure clone() throws CloneNotSupportedException {
+            return (Failure)super.clone();
+        }
+    }
+
+    private static class Worker extends Thread implements Cloneable {
+        private Object copy() throws CloneNotSupportedException {
+            return this.clone();
+        }
+    }
+
+    private static class Plain {
+        private Object copy() throws CloneNotSupportedException {
+            return this.clone();
+        }
+    }
+
+    private static String join(int[] values) {
+        StringBuilder builder = new StringBuilder();
+
+        for(int i = 0; i < values.length; i++) {
+            builder.append(i == 0 ? "" : ",").append(values[i]);
+        }
+
+        return builder.toString();
+    }
+
+    private void testHashCode() {
+        System.out.println("[Test HashCode]");
+
+        Object first = new Object();
+        Object second = new Object();
+
+        System.out.println(new StringBuilder().append("stable: ").append(first.hashCode() == first.hashCode() ? "yes" : "no").toString());
+        System.out.println(new StringBuilder().append("identity: ").append(first.hashCode() == System.identityHashCode(first) ? "yes" : "no").toString());
+        System.out.println(new StringBuilder().append("distinct: ").append(first.hashCode() != second.hashCode() ? "yes" : "no").toString());
+        System.out.println(new StringBuilder().append("non-negative: ").append(first.hashCode() >= 0 ? "yes" : "no").toString());
+        System.out.println(new StringBuilder().append("null: ").append(System.identityHashCode(null)).toString());
+
+        String text = "text";
+        System.out.println(new StringBuilder().append("string identity differs: ").append(System.identityHashCode(text) != text.hashCode() ? "yes" : "no").toString());
+
+        String expected = new StringBuilder().append("java.lang.Object@").append(Integer.toHexString(first.hashCode())).toString();
+        System.out.println(new StringBuilder().append("toString: ").append(first.toString().equals(expected) ? "yes" : "no").toString());
+    }
+
+    private void testClone() throws CloneNotSupportedException {
+        System.out.println("[Test Clone]");
+
+        int[] numbers = new int[] { 1, 2, 3 };
+        int[] numbersCopy = numbers.clone();
+        numbersCopy[0] = 9;
+        System.out.println(new StringBuilder().append("int[]: ").append(join(numbers)).append(" / ").append(join(numbersCopy)).toString());
+
+        String[] words = new String[] { "a", "b" };
+        String[] wordsCopy = words.clone();
+        System.out.println(new StringBuilder().append("String[]: ").append(wordsCopy != words ? "new" : "same").append(" ").append(wordsCopy[1]).append(" ").append(wordsCopy.getClass().getName()).toString());
+
+        Point point = new Point(4);
+        Point pointCopy = point.clone();
+        pointCopy.x = 5;
+        System.out.println(new StringBuilder().append("Point: ").append(point.x).append(" / ").append(pointCopy.x).append(", shallow: ").append(point.tags == pointCopy.tags ? "yes" : "no").toString());
+
+        Failure failure = new Failure("copied");
+        Failure failureCopy = failure.clone();
+        System.out.println(new StringBuilder().append("Failure: ").append(failureCopy != failure ? "new" : "same").append(" ").append(failureCopy.getMessage()).append(" ").append(failureCopy.getStackTrace()[0].getMethodName()).toString());
+
+        try {
+            new Worker().copy();
+        } catch(CloneNotSupportedException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            new Plain().copy();
+        } catch(CloneNotSupportedException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    private void testArrayCopy() {
+        System.out.println("[Test ArrayCopy]");
+
+        int[] numbers = new int[] { 1, 2, 3, 4, 5 };
+        int[] target = new int[5];
+        System.arraycopy(numbers, 1, target, 0, 3);
+        System.out.println(new StringBuilder().append("copy: ").append(join(target)).toString());
+
+        System.arraycopy(numbers, 0, numbers, 1, 4);
+        System.out.println(new StringBuilder().append("overlap right: ").append(join(numbers)).toString());
+
+        System.arraycopy(numbers, 2, numbers, 0, 3);
+        System.out.println(new StringBuilder().append("overlap left: ").append(join(numbers)).toString());
+
+        Object[] mixed = new Object[] { "a", "b", Integer.valueOf(3), "d" };
+        String[] strings = new String[] { "w", "x", "y", "z" };
+
+        try {
+            System.arraycopy(mixed, 0, strings, 0, 4);
+        } catch(ArrayStoreException exception) {
+            System.out.println(exception.toString());
+        }
+        System.out.println(new StringBuilder().append("partial: ").append(strings[0]).append(strings[1]).append(strings[2]).append(strings[3]).toString());
+
+        try {
+            System.arraycopy(numbers, 0, new long[5], 0, 1);
+        } catch(ArrayStoreException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            System.arraycopy("text", 0, numbers, 0, 1);
+        } catch(ArrayStoreException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            System.arraycopy(numbers, 3, target, 0, 3);
+        } catch(IndexOutOfBoundsException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            System.arraycopy(numbers, 0, target, 0, -1);
+        } catch(IndexOutOfBoundsException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            System.arraycopy(null, 0, target, 0, 1);
+        } catch(NullPointerException exception) {
+            System.out.println(exception.toString());
+        }
+
+        System.out.println(new StringBuilder().append("String(char[], int, int): ").append(new String(new char[] { 'a', 'b', 'c', 'd' }, 1, 2)).toString());
+    }
+
+    @Override
+    public void run() {
+        try {
+            testHashCode();
+            testClone();
+            testArrayCopy();
+        } catch(CloneNotSupportedException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+}
@@ -0,0 +1,95 @@
+package test;
+
+import java.lang.ref.*;
+
+public class TestReference extends Test {
+    private static int cleaned = 0;
+
+    private static String state(Reference<?> reference) {
+        return reference.get() == null ? "cleared" : "alive";
+    }
+
+    private void testWeak() throws InterruptedException {
+        System.out.println("[Test Weak]");
+
+        Object value = new Object();
+        ReferenceQueue<Object> queue = new ReferenceQueue<>();
+        WeakReference<Object> weak = new WeakReference<>(value, queue);
+        WeakReference<Object> unqueued = new WeakReference<>(value);
+
+        System.gc();
+        System.out.println(new StringBuilder().append("reachable: ").append(state(weak)).append(" ").append(state(unqueued)).toString());
+
+        value = null;
+        System.gc();
+        System.out.println(new StringBuilder().append("unreachable: ").append(state(weak)).append(" ").append(state(unqueued)).toString());
+
+        Reference<?> removed = queue.remove();
+        System.out.println(new StringBuilder().append("enqueued: ").append(removed == weak ? "weak" : "other").toString());
+        System.out.println(new StringBuilder().append("empty: ").append(queue.poll() == null ? "yes" : "no").toString());
+    }
+
+    private void testSoft() {
+        System.out.println("[Test Soft]");
+
+        SoftReference<String> soft = new SoftReference<>(new StringBuilder().append("soft").append("value").toString());
+
+        System.gc();
+        System.out.println(new StringBuilder().append("soft: ").append(state(soft)).toString());
+    }
+
+    private void testPhantom() throws InterruptedException {
+        System.out.println("[Test Phantom]");
+
+        Object value = new Object();
+        ReferenceQueue<Object> queue = new ReferenceQueue<>();
+        PhantomReference<Object> phantom = new PhantomReference<>(value, queue);
+
+        System.out.println(new StringBuilder().append("get: ").append(state(phantom)).append(" refersTo: ").append(phantom.refersTo(value) ? "yes" : "no").toString());
+
+        value = null;
+        System.out.println(new StringBuilder().append("enqueued: ").append(queue.remove() == phantom ? "phantom" : "other").toString());
+        System.out.println(new StringBuilder().append("refersTo null: ").append(phantom.refersTo(null) ? "yes" : "no").toString());
+    }
+
+    private void testCleaner() {
+        System.out.println("[Test Cleaner]");
+
+        Cleaner cleaner = Cleaner.create();
+        Runnable action = new Runnable() {
+            public void run() {
+                cleaned++;
+            }
+        };
+
+        Object value = new Object();
+        cleaner.register(value, action);
+        value = null;
+
+        while(cleaned == 0) {
+            System.gc();
+        }
+        System.out.println(new StringBuilder().append("cleaned by handler: ").append(cleaned).toString());
+
+        Object kept = new Object();
+        Cleaner.Cleanable cleanable = cleaner.register(kept, action);
+        cleanable.clean();
+        cleanable.clean();
+        System.gc();
+        System.out.println(new StringBuilder().append("cleaned explicitly: ").append(cleaned).toString());
+
+        Reference.reachabilityFence(kept);
+    }
+
+    @Override
+    public void run() {
+        try {
+            testWeak();
+            testSoft();
+            testPhantom();
+            testCleaner();
+        } catch(InterruptedException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+}
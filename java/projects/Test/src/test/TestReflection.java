@@ -0,0 +1,203 @@
+package test;
+
+import java.lang.reflect.*;
+import test.plugin.*;
+
+public class TestReflection extends Test {
+    private void testClass() throws Exception {
+        System.out.println("[Test Class]");
+
+        Class clazz = Class.forName("test.plugin.Greeter");
+        System.out.println(new StringBuilder().append("name = ").append(clazz.getName()).toString());
+        System.out.println(new StringBuilder().append("super = ").append(clazz.getSuperclass().getName()).toString());
+        System.out.println(new StringBuilder().append("interfaces = ").append(clazz.getInterfaces().length).append(" ").append(clazz.getInterfaces()[0].getName()).toString());
+        System.out.println(new StringBuilder().append("modifiers = ").append(Modifier.toString(clazz.getModifiers())).toString());
+        System.out.println(new StringBuilder().append("interface modifiers = ").append(Modifier.toString(Class.forName("test.plugin.Plugin").getModifiers())).toString());
+        System.out.println(new StringBuilder().append("Object super = ").append(new Object().getClass().getSuperclass() == null).toString());
+        System.out.println(new StringBuilder().append("isInstance = ").append(Class.forName("test.plugin.Plugin").isInstance(new Greeter())).append(" ").append(clazz.isInstance("text")).toString());
+        System.out.println(new StringBuilder().append("array = ").append(Class.forName("[Ljava.lang.String;").getName()).toString());
+
+        try {
+            Class.forName("test.plugin.Missing");
+        } catch(ClassNotFoundException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    private void testMembers() throws Exception {
+        System.out.println("[Test Members]");
+
+        Class clazz = Class.forName("test.plugin.Greeter");
+
+        Constructor[] constructors = clazz.getDeclaredConstructors();
+        for(int i = 0; i < constructors.length; i++) {
+            System.out.println(constructors[i].toString());
+        }
+
+        Method[] methods = clazz.getDeclaredMethods();
+        for(int i = 0; i < methods.length; i++) {
+            System.out.println(methods[i].toString());
+        }
+
+        Field[] fields = clazz.getDeclaredFields();
+        for(int i = 0; i < fields.length; i++) {
+            System.out.println(fields[i].toString());
+        }
+    }
+
+    private Method method(Class clazz, String name) {
+        Method[] methods = clazz.getDeclaredMethods();
+
+        for(int i = 0; i < methods.length; i++) {
+            if(methods[i].getName().equals(name)) {
+                return methods[i];
+            }
+        }
+
+        return null;
+    }
+
+    private Constructor constructor(Class clazz, int parameterCount) {
+        Constructor[] constructors = clazz.getDeclaredConstructors();
+
+        for(int i = 0; i < constructors.length; i++) {
+            if(constructors[i].getParameterCount() == parameterCount && !Modifier.isPrivate(constructors[i].getModifiers())) {
+                return constructors[i];
+            }
+        }
+
+        return null;
+    }
+
+    private void testInvoke() throws Exception {
+        System.out.println("[Test Invoke]");
+
+        Plugin plugin = (Plugin)Class.forName("test.plugin.Greeter").newInstance();
+        plugin.run();
+
+        Object greeter = constructor(Class.forName("test.plugin.Greeter"), 1).newInstance("Hi");
+        System.out.println((String)method(Class.forName("test.plugin.Greeter"), "greet").invoke(greeter, "reflection"));
+
+        Object sum = method(Class.forName("test.plugin.Greeter"), "add").invoke(null, 2, 40L);
+        System.out.println(new StringBuilder().append("add = ").append(((Integer)sum).intValue()).toString());
+
+        Object widened = method(Class.forName("test.plugin.Greeter"), "add").invoke(null, (byte)1, 2);
+        System.out.println(new StringBuilder().append("widened = ").append(((Integer)widened).intValue()).toString());
+
+        Object run = method(Class.forName("test.plugin.Plugin"), "run").invoke(greeter);
+        System.out.println(new StringBuilder().append("void = ").append(run == null).toString());
+
+        try {
+            method(Class.forName("test.plugin.Greeter"), "fail").invoke(greeter);
+        } catch(InvocationTargetException exception) {
+            System.out.println(new StringBuilder().append("target = ").append(exception.getCause().toString()).toString());
+        }
+
+        try {
+            method(Class.forName("test.plugin.Greeter"), "add").invoke(null, "one", 2L);
+        } catch(IllegalArgumentException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            method(Class.forName("test.plugin.Greeter"), "greet").invoke(greeter);
+        } catch(IllegalArgumentException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            method(Class.forName("test.plugin.Greeter"), "greet").invoke("text", "x");
+        } catch(IllegalArgumentException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    private void testFields() throws Exception {
+        System.out.println("[Test Fields]");
+
+        Greeter greeter = new Greeter();
+        greeter.greet("a");
+
+        Field count = Class.forName("test.plugin.Greeter").getDeclaredField("count");
+        Field created = Class.forName("test.plugin.Greeter").getDeclaredField("created");
+        Field limit = Class.forName("test.plugin.Greeter").getDeclaredField("LIMIT");
+
+        count.setAccessible(true);
+        System.out.println(new StringBuilder().append("count = ").append(((Long)count.get(greeter)).longValue()).toString());
+        count.set(greeter, 41);
+        System.out.println(new StringBuilder().append("count set = ").append(((Long)count.get(greeter)).longValue()).toString());
+        System.out.println(new StringBuilder().append("created > 0 = ").append(((Integer)created.get(null)).intValue() > 0).toString());
+        System.out.println(new StringBuilder().append("limit = ").append(((Integer)limit.get(null)).intValue()).toString());
+
+        try {
+            limit.set(null, 4);
+        } catch(IllegalAccessException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            count.set(greeter, "many");
+        } catch(IllegalArgumentException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    private void testAccess() throws Exception {
+        System.out.println("[Test Access]");
+
+        Object greeter = new Greeter();
+        Method secret = method(Class.forName("test.plugin.Greeter"), "secret");
+
+        try {
+            secret.invoke(greeter);
+        } catch(IllegalAccessException exception) {
+            System.out.println(exception.toString());
+        }
+
+        secret.setAccessible(true);
+        System.out.println(new StringBuilder().append("accessible = ").append((String)secret.invoke(greeter)).toString());
+
+        try {
+            Class.forName("test.plugin.Greeter").getDeclaredField("packageValue").get(greeter);
+        } catch(IllegalAccessException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    private void testLiterals() throws Exception {
+        System.out.println("[Test Literals]");
+
+        System.out.println(new StringBuilder().append("int = ").append(int.class.getName()).append(" ").append(int.class.isPrimitive()).toString());
+        System.out.println(new StringBuilder().append("int[] = ").append(int[].class.getName()).append(" ").append(int[].class.isArray()).toString());
+        System.out.println(new StringBuilder().append("String[][] = ").append(String[][].class.getName()).append(" ").append(String[][].class.getSimpleName()).toString());
+        System.out.println(new StringBuilder().append("component = ").append(int[].class.getComponentType() == int.class).append(" ").append(String[][].class.getComponentType() == String[].class).toString());
+        System.out.println(new StringBuilder().append("getClass = ").append(new Greeter().getClass() == Greeter.class).append(" ").append(new int[2].getClass() == int[].class).toString());
+        System.out.println(new StringBuilder().append("void = ").append(void.class.getName()).append(" ").append(void.class == Void.TYPE).toString());
+        System.out.println(new StringBuilder().append("array super = ").append(String[].class.getSuperclass().getName()).append(" ").append(Object[].class.isInstance(new String[0])).toString());
+        System.out.println(new StringBuilder().append("interface = ").append(Plugin.class.isInterface()).append(" ").append(Greeter.class.isInterface()).toString());
+
+        Class[] parameters = method(Greeter.class, "add").getParameterTypes();
+        System.out.println(new StringBuilder().append("add = ").append(parameters[0] == int.class).append(" ").append(parameters[1].getName()).append(" ").append(method(Greeter.class, "add").getReturnType().getName()).toString());
+        System.out.println(new StringBuilder().append("count = ").append(Greeter.class.getDeclaredField("count").getType() == long.class).toString());
+
+        try {
+            Class.forName("int");
+        } catch(ClassNotFoundException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    @Override
+    public void run() {
+        try {
+            testClass();
+            testMembers();
+            testInvoke();
+            testFields();
+            testAccess();
+            testLiterals();
+        } catch(Exception exception) {
+            exception.printStackTrace();
+        }
+    }
+}
@@ -0,0 +1,54 @@
+package test;
+
+public class TestShutdown extends Test {
+    private static class Hook extends Thread {
+        @Override
+        public void run() {
+            System.out.println("hook ran");
+
+            try {
+                Runtime.getRuntime().addShutdownHook(new Thread());
+            } catch(IllegalStateException exception) {
+                System.out.println(new StringBuilder().append("IllegalStateException: ").append(exception.getMessage()).toString());
+            }
+        }
+    }
+
+    private void testHooks() {
+        System.out.println("[Test Hooks]");
+
+        Runtime runtime = Runtime.getRuntime();
+        Thread hook = new Hook();
+        Thread unused = new Thread(new Runnable() {
+            public void run() {
+                System.out.println("removed hook ran");
+            }
+        });
+
+        runtime.addShutdownHook(hook);
+        runtime.addShutdownHook(unused);
+
+        try {
+            runtime.addShutdownHook(hook);
+        } catch(IllegalArgumentException exception) {
+            System.out.println(new StringBuilder().append("IllegalArgumentException: ").append(exception.getMessage()).toString());
+        }
+
+        System.out.println(new StringBuilder().append("removed = ").append(runtime.removeShutdownHook(unused)).toString());
+        System.out.println(new StringBuilder().append("removed again = ").append(runtime.removeShutdownHook(unused)).toString());
+    }
+
+    private void testExit() {
+        System.out.println("[Test Exit]");
+        System.out.println("exiting with 3");
+
+        System.exit(3);
+
+        System.out.println("still running");
+    }
+
+    public void run() {
+        this.testHooks();
+        this.testExit();
+    }
+}
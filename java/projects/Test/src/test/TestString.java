@@ -0,0 +1,106 @@
+package test;
+
+public class TestString extends Test {
+    private static final String CONSTANT = "constant";
+
+    private void testUnicode() {
+        System.out.println("[Test Unicode]");
+
+        String accents = "déjà vu";
+        String cjk = "漢字";
+        String emoji = "😀!";
+
+        System.out.println(accents);
+        System.out.println(cjk);
+        System.out.println(emoji);
+        System.out.println(new StringBuilder().append("accents length = ").append(accents.length()).toString());
+        System.out.println(new StringBuilder().append("cjk length = ").append(cjk.length()).toString());
+        System.out.println(new StringBuilder().append("emoji length = ").append(emoji.length()).toString());
+        System.out.println(new StringBuilder().append("accents[1] = ").append(accents.charAt(1)).append(" ").append((int) accents.charAt(1)).toString());
+        System.out.println(new StringBuilder().append("cjk[1] = ").append(cjk.charAt(1)).append(" ").append((int) cjk.charAt(1)).toString());
+        System.out.println(new StringBuilder().append("emoji[0] = ").append((int) emoji.charAt(0)).append(", emoji[1] = ").append((int) emoji.charAt(1)).toString());
+        System.out.println(new StringBuilder().append("utf-8 bytes = ").append(cjk.getBytes().length).toString());
+        System.out.println(new String(accents.getBytes()));
+
+        String lone = new String(new char[] { 'a', (char) 0xD800, 'b', (char) 0xDC00, 'c' });
+        String upper = lone.toUpperCase();
+        String lower = upper.toLowerCase();
+        System.out.println(new StringBuilder().append("lone surrogates upper = ").append((int) upper.charAt(0)).append(" ").append((int) upper.charAt(1)).append(" ").append((int) upper.charAt(2)).append(" ").append((int) upper.charAt(3)).append(" ").append((int) upper.charAt(4)).toString());
+        System.out.println(new StringBuilder().append("lone surrogates lower = ").append(lower.equals(lone)).append(" ").append(emoji.toUpperCase().equals(emoji)).toString());
+    }
+
+    private void testIntern() {
+        System.out.println("[Test Intern]");
+
+        String built = new StringBuilder().append("con").append("stant").toString();
+
+        System.out.println(new StringBuilder().append("literal == literal = ").append("a" == "a").toString());
+        System.out.println(new StringBuilder().append("literal == constant = ").append("constant" == CONSTANT).toString());
+        System.out.println(new StringBuilder().append("built == constant = ").append(built == CONSTANT).toString());
+        System.out.println(new StringBuilder().append("built.equals(constant) = ").append(built.equals(CONSTANT)).toString());
+        System.out.println(new StringBuilder().append("built.intern() == constant = ").append(built.intern() == CONSTANT).toString());
+    }
+
+    private void testOperations() {
+        System.out.println("[Test Operations]");
+
+        String text = "Hello, wörld";
+        Object other = "Hello, wörld";
+
+        System.out.println(new StringBuilder().append("equals = ").append(text.equals(other)).append(" ").append(text.equals("Hello")).append(" ").append(text.equals(null)).toString());
+        System.out.println(new StringBuilder().append("hashCode = ").append(text.hashCode()).append(" ").append("".hashCode()).toString());
+        System.out.println(new StringBuilder().append("compareTo = ").append("apple".compareTo("banana")).append(" ").append("app".compareTo("apple")).append(" ").append(text.compareTo(text)).toString());
+        System.out.println(new StringBuilder().append("indexOf = ").append(text.indexOf('o')).append(" ").append(text.indexOf('o', 5)).append(" ").append(text.indexOf("wö")).append(" ").append(text.indexOf("x")).append(" ").append(text.indexOf("", 40)).toString());
+        System.out.println(new StringBuilder().append("substring = ").append(text.substring(7)).append("|").append(text.substring(0, 5)).append("|").append(text.substring(3, 3)).toString());
+        System.out.println(new StringBuilder().append("concat = ").append("foo".concat("bar")).toString());
+        System.out.println(new StringBuilder().append("startsWith = ").append(text.startsWith("Hello")).append(" ").append(text.startsWith("wö", 7)).append(" ").append(text.startsWith("x")).toString());
+        System.out.println(new StringBuilder().append("case = ").append(text.toUpperCase()).append(" ").append("ÀÉÎ straße".toLowerCase()).append(" ").append("straße".toUpperCase()).toString());
+
+        try {
+            text.charAt(12);
+        } catch(StringIndexOutOfBoundsException exception) {
+            System.out.println(exception.toString());
+        }
+
+        try {
+            text.substring(5, 2);
+        } catch(StringIndexOutOfBoundsException exception) {
+            System.out.println(exception.toString());
+        }
+    }
+
+    private void testFormat() {
+        System.out.println("[Test Format]");
+
+        System.out.println(String.format("[%s] [%10s] [%-10s] [%.3s] [%S]", "text", "right", "left", "truncate", "upper"));
+        System.out.println(String.format("[%d] [%5d] [%-5d|] [%05d] [%,d] [%+d] [% d] [%(d]", 42, 42, 42, -42, 1234567, 42, 42, -42));
+        System.out.println(String.format("[%x] [%X] [%#x] [%o] [%x] [%x] [%08X]", 255, 255, 255, 8, -1, -1L, 48879));
+        System.out.println(String.format("[%.2f] [%8.3f] [%-8.1f|] [%08.2f] [%,.2f] [%f] [%.0f]", 3.14159, 2.71828, 1.25, -3.5, 1234567.891, 0.1, 2.5));
+        System.out.println(String.format("[%.2f] [%.2f] [%.1f] [%.3f] [%.2f] [%f]", 0.125, 0.006, 0.05, 1e-10, 999.999, 1e20));
+        System.out.println(String.format("[%e] [%.2e] [%E] [%.0e] [%e]", 12345.678, 0.000123, 1e-300, 5.5, 0.0));
+        System.out.println(String.format("[%f] [%8f] [%+f] [%.1f] [%.1f]", Double.NaN, Double.POSITIVE_INFINITY, Double.NEGATIVE_INFINITY, 1.5f, -0.0));
+        System.out.println(String.format("[%c] [%C] [%b] [%b] [%B] [%%] [%5%]", 'x', 'y', null, "", true));
+        System.out.println(String.format("[%2$s %1$s] [%s %<s] [%h]", "world", "hello", "again", "hi"));
+        System.out.println("ab".repeat(3).concat("|").concat("x".repeat(0)).concat("|"));
+
+        try {
+            String.format("%q", 1);
+        } catch(IllegalArgumentException exception) {
+            System.out.println(exception.getMessage());
+        }
+
+        try {
+            String.format("%d", "text");
+        } catch(IllegalArgumentException exception) {
+            System.out.println(exception.getMessage());
+        }
+    }
+
+    @Override
+    public void run() {
+        testUnicode();
+        testIntern();
+        testOperations();
+        testFormat();
+    }
+}
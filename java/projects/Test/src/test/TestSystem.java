@@ -0,0 +1,160 @@
+package test;
+
+import java.security.AccessController;
+import java.security.PrivilegedAction;
+import java.util.Properties;
+
+public class TestSystem extends Test {
+    private static void print(String label, boolean value) {
+        System.out.println(new StringBuilder().append(label).append(" = ").append(value).toString());
+    }
+
+    private void testClock() {
+        System.out.println("[Test Clock]");
+
+        long millis = System.currentTimeMillis();
+        long start = System.nanoTime();
+        long sum = 0;
+
+        for(int i = 0; i < 1000; i++) {
+            sum += i;
+        }
+
+        long end = System.nanoTime();
+
+        print("after 2020", millis > 1577836800000L);
+        print("nanoTime monotonic", end >= start);
+        print("loop", sum == 499500);
+    }
+
+    private void testProperties() {
+        System.out.println("[Test Properties]");
+
+        print("java.version", System.getProperty("java.version") != null);
+        print("os.name", System.getProperty("os.name") != null);
+        print("user.dir", System.getProperty("user.dir") != null);
+        print("file.separator", System.getProperty("file.separator").length() == 1);
+        print("lineSeparator", System.lineSeparator().equals(System.getProperty("line.separator")));
+        System.out.println(new StringBuilder().append("missing = ").append(System.getProperty("test.missing")).toString());
+        System.out.println(new StringBuilder().append("default = ").append(System.getProperty("test.missing", "fallback")).toString());
+        System.out.println(new StringBuilder().append("set = ").append(System.setProperty("test.key", "one")).toString());
+        System.out.println(new StringBuilder().append("reset = ").append(System.setProperty("test.key", "two")).toString());
+        System.out.println(new StringBuilder().append("get = ").append(System.getProperty("test.key")).toString());
+
+        Properties properties = System.getProperties();
+        print("properties has key", "two".equals(properties.getProperty("test.key")));
+        print("properties size", properties.size() > 5);
+        properties.setProperty("test.key", "three");
+        System.out.println(new StringBuilder().append("after properties change = ").append(System.getProperty("test.key")).toString());
+
+        try {
+            System.getProperty(null);
+        } catch(NullPointerException exception) {
+            System.out.println(new StringBuilder().append("NullPointerException: ").append(exception.getMessage()).toString());
+        }
+
+        try {
+            System.getProperty("");
+        } catch(IllegalArgumentException exception) {
+            System.out.println(new StringBuilder().append("IllegalArgumentException: ").append(exception.getMessage()).toString());
+        }
+    }
+
+    private void testEnvironment() {
+        System.out.println("[Test Environment]");
+
+        print("PATH", System.getenv("PATH") != null);
+        System.out.println(new StringBuilder().append("missing = ").append(System.getenv("WASMJVM_TEST_MISSING")).toString());
+    }
+
+    private void testRuntime() {
+        System.out.println("[Test Runtime]");
+
+        Runtime runtime = Runtime.getRuntime();
+
+        print("same runtime", runtime == Runtime.getRuntime());
+        print("processors", runtime.availableProcessors() >= 1);
+        print("max >= total", runtime.maxMemory() >= runtime.totalMemory());
+        print("total >= free", runtime.totalMemory() >= runtime.freeMemory());
+        print("free > 0", runtime.freeMemory() > 0);
+    }
+
+    private void testLibrary() {
+        System.out.println("[Test Library]");
+
+        System.loadLibrary("java");
+        System.out.println("loaded java");
+
+        try {
+            System.loadLibrary("wasmjvm_test_missing");
+            System.out.println("loaded missing");
+        } catch(UnsatisfiedLinkError error) {
+            print("UnsatisfiedLinkError", error.getMessage().startsWith("no wasmjvm_test_missing in java.library.path"));
+        }
+
+        try {
+            System.loadLibrary(null);
+        } catch(NullPointerException exception) {
+            System.out.println("null name: NullPointerException");
+        }
+    }
+
+    private static class Configured {
+        static final String VALUE;
+
+        static {
+            System.out.println("Configured initialized");
+            VALUE = "configured";
+        }
+    }
+
+    private void testPrivileged() {
+        System.out.println("[Test Privileged]");
+
+        final StringBuilder builder = new StringBuilder().append("built");
+        String result = AccessController.doPrivileged(new PrivilegedAction<String>() {
+            public String run() {
+                return builder.append(" in action").toString();
+            }
+        });
+        System.out.println(new StringBuilder().append("result = ").append(result).toString());
+
+        Integer nested = AccessController.doPrivileged(new PrivilegedAction<Integer>() {
+            public Integer run() {
+                return AccessController.doPrivileged(new PrivilegedAction<Integer>() {
+                    public Integer run() {
+                        return Integer.valueOf(42);
+                    }
+                }) + 1;
+            }
+        });
+        System.out.println(new StringBuilder().append("nested = ").append(nested.intValue()).toString());
+
+        String value = AccessController.doPrivileged(new PrivilegedAction<String>() {
+            public String run() {
+                return Configured.VALUE;
+            }
+        });
+        System.out.println(new StringBuilder().append("value = ").append(value).toString());
+
+        try {
+            AccessController.doPrivileged(new PrivilegedAction<Object>() {
+                public Object run() {
+                    throw new IllegalStateException("action failed");
+                }
+            });
+            System.out.println("no exception");
+        } catch(IllegalStateException exception) {
+            System.out.println(new StringBuilder().append("IllegalStateException: ").append(exception.getMessage()).toString());
+        }
+    }
+
+    public void run() {
+        this.testClock();
+        this.testProperties();
+        this.testEnvironment();
+        this.testRuntime();
+        this.testLibrary();
+        this.testPrivileged();
+    }
+}
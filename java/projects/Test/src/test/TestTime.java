@@ -0,0 +1,62 @@
+package test;
+
+import java.time.DayOfWeek;
+import java.time.Duration;
+import java.time.LocalDate;
+import java.time.LocalDateTime;
+import java.time.LocalTime;
+import java.time.Month;
+import java.time.temporal.ChronoUnit;
+
+// Runs with the JDK's java.base loaded after the boot jar, which supplies java.time.
+public class TestTime extends Test {
+    private void testDate() {
+        System.out.println("[Test Date]");
+
+        LocalDate date = LocalDate.of(2024, Month.FEBRUARY, 28);
+        LocalDate later = date.plusDays(2);
+
+        System.out.println(new StringBuilder().append("date = ").append(date).toString());
+        System.out.println(new StringBuilder().append("plusDays(2) = ").append(later).toString());
+        System.out.println(new StringBuilder().append("leap year = ").append(date.isLeapYear()).toString());
+        System.out.println(new StringBuilder().append("day of week = ").append(later.getDayOfWeek()).toString());
+        System.out.println(new StringBuilder().append("day of year = ").append(later.getDayOfYear()).toString());
+        System.out.println(new StringBuilder().append("plusMonths(12) = ").append(date.plusMonths(12)).toString());
+        System.out.println(new StringBuilder().append("between = ").append(ChronoUnit.DAYS.between(date, LocalDate.of(2025, 1, 1))).toString());
+        System.out.println(new StringBuilder().append("ofEpochDay = ").append(LocalDate.ofEpochDay(10957).plusDays(-1)).toString());
+        System.out.println(new StringBuilder().append("next monday = ").append(DayOfWeek.SUNDAY.plus(1)).toString());
+    }
+
+    private void testTime() {
+        System.out.println("[Test Time]");
+
+        LocalTime time = LocalTime.of(23, 45, 30);
+        LocalDateTime dateTime = LocalDateTime.of(2024, 12, 31, 23, 59);
+        Duration duration = Duration.ofMinutes(135).plusSeconds(7);
+
+        System.out.println(new StringBuilder().append("time = ").append(time).toString());
+        System.out.println(new StringBuilder().append("plusMinutes(20) = ").append(time.plusMinutes(20)).toString());
+        System.out.println(new StringBuilder().append("dateTime = ").append(dateTime).toString());
+        System.out.println(new StringBuilder().append("plusMinutes(1) = ").append(dateTime.plusMinutes(1)).toString());
+        System.out.println(new StringBuilder().append("duration = ").append(duration).toString());
+        System.out.println(new StringBuilder().append("duration minutes = ").append(duration.toMinutes()).toString());
+    }
+
+    private void testFormat() {
+        System.out.println("[Test Format]");
+
+        LocalDate date = LocalDate.of(2024, 3, 1);
+        Duration duration = Duration.ofSeconds(3725);
+
+        System.out.println(String.format("%04d/%02d/%02d %-9s|", date.getYear(), date.getMonthValue(), date.getDayOfMonth(), date.getDayOfWeek()));
+        System.out.println(String.format("%d:%02d:%02d", duration.toHours(), duration.toMinutesPart(), duration.toSecondsPart()));
+        System.out.println(String.format("%.1f%% of the year", date.getDayOfYear() * 100.0 / date.lengthOfYear()));
+    }
+
+    @Override
+    public void run() {
+        testDate();
+        testTime();
+        testFormat();
+    }
+}
@@ -0,0 +1,48 @@
+package test.plugin;
+
+public class Greeter implements Plugin {
+    public static int created;
+    public static final int LIMIT = 3;
+
+    static {
+        System.out.println("Greeter initialized");
+    }
+
+    private String greeting;
+    protected long count;
+    int packageValue = 7;
+
+    public Greeter() {
+        this("Hello");
+    }
+
+    public Greeter(String greeting) {
+        this.greeting = greeting;
+        created++;
+    }
+
+    private Greeter(int count) {
+        this.count = count;
+    }
+
+    public String greet(String name) {
+        this.count++;
+        return new StringBuilder().append(this.greeting).append(", ").append(name).toString();
+    }
+
+    public static int add(int left, long right) {
+        return (int)(left + right);
+    }
+
+    public void fail() {
+        throw new IllegalStateException("Greeter failed");
+    }
+
+    private String secret() {
+        return "secret";
+    }
+
+    public void run() {
+        System.out.println(this.greet("run"));
+    }
+}
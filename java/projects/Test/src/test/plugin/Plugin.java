@@ -0,0 +1,5 @@
+package test.plugin;
+
+public interface Plugin {
+    void run();
+}
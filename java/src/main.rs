@@ -35,6 +35,8 @@ fn main() {
                 "1.8".to_string(),
                 "-target".to_string(),
                 "1.8".to_string(),
+                "-encoding".to_string(),
+                "UTF-8".to_string(),
                 "-extdirs".to_string(),
                 project_src_path_buf.to_str().unwrap().to_string(),
                 "-sourcepath".to_string(),
@@ -47,9 +49,12 @@ fn main() {
         } else {
             vec![
                 "-source".to_string(),
-                "1.8".to_string(),
+                "11".to_string(),
                 "-target".to_string(),
-                "1.8".to_string(),
+                "11".to_string(),
+                "-XDstringConcat=inline".to_string(),
+                "-encoding".to_string(),
+                "UTF-8".to_string(),
                 "-d".to_string(),
                 project_class_path_buf.to_str().unwrap().to_string(),
                 "-classpath".to_string(),
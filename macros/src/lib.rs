@@ -0,0 +1,282 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    parse::Parser, parse_macro_input, punctuated::Punctuated, spanned::Spanned, Expr, ExprLit, FnArg,
+    GenericArgument, Ident, ItemFn, Lit, Pat, PathArguments, ReturnType, Token, Type,
+};
+use wasmjvm_class::{Descriptor, SingleType, Type as JavaType};
+
+/// Declares a native method: `#[native("java/io/FileOutputStream", "nativeWrite")]`.
+///
+/// The Java descriptor comes from the Rust parameter and return types, which have to implement
+/// `NativeType`. An optional leading `&mut NativeEnv` parameter gets the native's environment
+/// and a parameter named `this` (or `_this`) gets the receiver of an instance method. The
+/// function may be `async` and may return a `Result`; errors are thrown like those of
+/// hand-written natives, and arguments that don't fit the Rust types throw an
+/// `IllegalArgumentException` or `NullPointerException`.
+///
+/// The method is linked with the `java` native library, or with the one named by a trailing
+/// `library = "name"` argument. A `descriptor = "(Ljava/lang/Thread;)V"` argument replaces the
+/// derived descriptor, for references more specific than `JObject`'s `java/lang/Object`.
+#[proc_macro_attribute]
+pub fn native(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(item as ItemFn);
+
+    match expand(attribute, &function) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => {
+            let error = error.to_compile_error();
+
+            quote!(#function #error).into()
+        }
+    }
+}
+
+/// Where a Rust parameter's value comes from.
+enum Parameter {
+    Env,
+    This(Type),
+    Argument(Type),
+}
+
+fn expand(attribute: TokenStream, function: &ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let arguments = Punctuated::<Expr, Token![,]>::parse_terminated.parse(attribute)?;
+    let usage = || {
+        syn::Error::new(
+            Span::call_site(),
+            "expected #[native(\"class\", \"method\")], optionally followed by library = \"name\" and descriptor = \"(...)...\"",
+        )
+    };
+
+    let mut names = Vec::new();
+    let mut library = None;
+    let mut declared = None;
+    for argument in arguments.iter() {
+        match argument {
+            Expr::Lit(ExprLit { lit: Lit::Str(name), .. }) if library.is_none() && declared.is_none() => {
+                names.push(name.clone())
+            }
+            Expr::Assign(assign) => match (&*assign.left, &*assign.right) {
+                (Expr::Path(key), Expr::Lit(ExprLit { lit: Lit::Str(value), .. }))
+                    if key.path.is_ident("library") && library.is_none() =>
+                {
+                    library = Some(value.clone())
+                }
+                (Expr::Path(key), Expr::Lit(ExprLit { lit: Lit::Str(value), .. }))
+                    if key.path.is_ident("descriptor") && declared.is_none() =>
+                {
+                    let descriptor = Descriptor::from_str(&value.value())
+                        .map_err(|error| syn::Error::new(value.span(), format!("invalid descriptor: {}", error)))?;
+
+                    declared = Some((value.span(), descriptor))
+                }
+                _ => return Err(usage()),
+            },
+            _ => return Err(usage()),
+        }
+    }
+
+    let (class, name) = match &names[..] {
+        [class, name] => (class, name),
+        _ => return Err(usage()),
+    };
+    let library = match library {
+        Some(library) => quote!(#library),
+        None => quote!(::wasmjvm_native::JAVA_LIBRARY),
+    };
+
+    let mut parameters = Vec::new();
+    for (index, input) in function.sig.inputs.iter().enumerate() {
+        let input = match input {
+            FnArg::Typed(input) => input,
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new(receiver.span(), "natives can't take self"));
+            }
+        };
+
+        let is_this = matches!(&*input.pat, Pat::Ident(pattern) if pattern.ident == "this" || pattern.ident == "_this");
+
+        parameters.push(match &*input.ty {
+            Type::Reference(reference) if index == 0 && reference.mutability.is_some() => Parameter::Env,
+            r#type if is_this => {
+                // The receiver is the first variable, so it can only follow the environment.
+                let first = matches!(parameters[..], [] | [Parameter::Env]);
+                if !first {
+                    return Err(syn::Error::new(
+                        input.pat.span(),
+                        "`this` has to be the first parameter, after the `&mut NativeEnv` if there is one",
+                    ));
+                }
+
+                Parameter::This(r#type.clone())
+            }
+            r#type => Parameter::Argument(r#type.clone()),
+        });
+    }
+
+    let (output, is_result) = match &function.sig.output {
+        ReturnType::Default => (syn::parse_quote!(()), false),
+        ReturnType::Type(_, r#type) => match result_ok_type(r#type) {
+            Some(ok) => (ok, true),
+            None => ((**r#type).clone(), false),
+        },
+    };
+
+    let function_name = &function.sig.ident;
+    let wrapper = format_ident!("__native_{}", function_name);
+    let descriptor = format_ident!("__native_descriptor_{}", function_name);
+
+    let count = parameters.iter().filter(|parameter| !matches!(parameter, Parameter::Env)).count();
+
+    if let Some((span, descriptor)) = &declared {
+        let expected = parameters.iter().filter(|parameter| matches!(parameter, Parameter::Argument(..))).count();
+
+        if descriptor.parameters().len() != expected {
+            return Err(syn::Error::new(
+                *span,
+                format!(
+                    "the descriptor has {} parameters but the function takes {} arguments",
+                    descriptor.parameters().len(),
+                    expected
+                ),
+            ));
+        }
+    }
+
+    let mut position = 0usize;
+    let mut bindings = Vec::new();
+    let mut arguments = Vec::new();
+    let mut descriptor_types = Vec::new();
+
+    for (index, parameter) in parameters.iter().enumerate() {
+        let binding = Ident::new(&format!("argument{}", index), Span::call_site());
+
+        let r#type = match parameter {
+            Parameter::Env => {
+                arguments.push(quote!(env));
+                continue;
+            }
+            Parameter::This(r#type) => r#type,
+            Parameter::Argument(r#type) => {
+                descriptor_types.push(quote!(<#r#type as ::wasmjvm_native::NativeType>::java_type()));
+                r#type
+            }
+        };
+
+        bindings.push(quote! {
+            let #binding = <#r#type as ::wasmjvm_native::FromNative>::from_native(env, &variables[#position])?;
+        });
+
+        arguments.push(quote!(#binding));
+        position += 1;
+    }
+
+    let signature = match &declared {
+        Some((_, descriptor)) => {
+            let parameters = descriptor.parameters().map(type_tokens);
+            let output = type_tokens(descriptor.output());
+
+            quote!((::std::vec![#(#parameters),*], #output))
+        }
+        None => quote! {
+            (
+                ::std::vec![#(#descriptor_types),*],
+                <#output as ::wasmjvm_native::NativeType>::java_type(),
+            )
+        },
+    };
+
+    let mut call = quote!(#function_name(#(#arguments),*));
+    if function.sig.asyncness.is_some() {
+        call = quote!(#call.await);
+    }
+    if is_result {
+        call = quote!(#call?);
+    }
+
+    Ok(quote! {
+        #function
+
+        #[doc(hidden)]
+        fn #wrapper(env: &mut ::wasmjvm_native::NativeEnv) -> ::wasmjvm_native::NativeFuture<'_> {
+            ::std::boxed::Box::pin(async move {
+                let variables = ::wasmjvm_native::native_variables(env, #count)?;
+                #(#bindings)*
+
+                let result: #output = #call;
+
+                ::wasmjvm_native::IntoNative::into_native(result, env)
+            })
+        }
+
+        #[doc(hidden)]
+        fn #descriptor() -> ::wasmjvm_native::NativeSignature {
+            #signature
+        }
+
+        ::wasmjvm_native::inventory::submit! {
+            ::wasmjvm_native::NativeRegistration {
+                library: #library,
+                class: #class,
+                name: #name,
+                signature: #descriptor,
+                method: #wrapper,
+            }
+        }
+    })
+}
+
+/// The `T` of a `Result<T, E>` return type.
+fn result_ok_type(r#type: &Type) -> Option<Type> {
+    let path = match r#type {
+        Type::Path(path) => path,
+        _ => return None,
+    };
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != "Result" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(ok) => Some(ok.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Builds `r#type` again in the expanded code.
+fn type_tokens(r#type: &JavaType) -> proc_macro2::TokenStream {
+    let single = |single: &SingleType| {
+        let variant = match single {
+            SingleType::Byte => quote!(Byte),
+            SingleType::Char => quote!(Char),
+            SingleType::Double => quote!(Double),
+            SingleType::Float => quote!(Float),
+            SingleType::Int => quote!(Int),
+            SingleType::Long => quote!(Long),
+            SingleType::Short => quote!(Short),
+            SingleType::Boolean => quote!(Boolean),
+            SingleType::Void => quote!(Void),
+            SingleType::Object(name) => quote!(Object(::std::string::String::from(#name))),
+        };
+
+        quote!(::wasmjvm_native::wasmjvm_class::SingleType::#variant)
+    };
+
+    match r#type {
+        JavaType::Single(component) => {
+            let component = single(component);
+
+            quote!(::wasmjvm_native::wasmjvm_class::Type::Single(#component))
+        }
+        JavaType::Array(component, dimensions) => {
+            let component = single(component);
+
+            quote!(::wasmjvm_native::wasmjvm_class::Type::Array(#component, #dimensions))
+        }
+    }
+}
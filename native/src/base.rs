@@ -1,16 +1,25 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, Mutex},
 };
 
-use wasmjvm_class::{Descriptor, FieldRef, MethodRef, WithFields};
+use wasmjvm_class::{
+    AccessFlagType, AccessFlags, Class, Constant, Descriptor, FieldRef, MethodRef, SingleType,
+    Type, WithAccessFlags, WithDescriptor, WithFields, WithInterfaces,
+};
 use wasmjvm_common::WasmJVMError;
 
 use crate::{
-    ClassInstance, Loader, NativeEnv, NativeFn, NativeInterface, Object, Primitive, RustObject,
-    Thread, ThreadResult, JAVA_STRING,
+    Array, ClassInstance, ClassState, FieldSlot, Loader, NativeContinuation, NativeEnv, NativeFn,
+    NativeInterface, Object, Primitive, RustObject, StackTraceEntry, Thread, ThreadResult,
+    box_class_name, unbox_type, DEFAULT_PROPERTIES, JAVA_CLASS, JAVA_CLONEABLE, JAVA_METHOD_HANDLE, JAVA_METHOD_TYPE, JAVA_OBJECT,
+    JAVA_SERIALIZABLE, JAVA_STRING,
 };
 
+fn package(name: &str) -> &str {
+    name.rsplit_once('/').map_or("", |(package, _)| package)
+}
+
 pub type RegisterFn = Box<dyn Fn(&mut NativeInterface)>;
 
 static mut HEAP: Option<Vec<Option<Object>>> = None;
@@ -28,7 +37,17 @@ pub struct GlobalData {
     loader_index: Option<usize>,
     classes: HashMap<String, usize>,
     threads: Vec<usize>,
+    current_thread: Option<usize>,
     thread_priority: usize,
+    thread_count: usize,
+    strings: HashMap<Vec<u16>, usize>,
+    pending_references: Vec<usize>,
+    reference_handler: Option<usize>,
+    collection_requested: bool,
+    allocations: usize,
+    properties: HashMap<String, String>,
+    environment: HashMap<String, String>,
+    exit_status: Option<i32>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -76,7 +95,7 @@ impl Heap {
                         Ok(*index)
                     }
                 } else {
-                    unreachable!();
+                    Err(WasmJVMError::InternalError(format!("Heap index lock poisoned")))
                 }
             } else {
                 Ok(0)
@@ -84,6 +103,15 @@ impl Heap {
         }
     }
 
+    /// The number of slots handed out so far.
+    pub fn size(self: &Self) -> usize {
+        self.index.lock().map(|index| *index).unwrap_or(HEAP_SIZE)
+    }
+
+    pub fn capacity(self: &Self) -> usize {
+        HEAP_SIZE
+    }
+
     pub fn alloc(self: &mut Self, object: Object) -> Result<usize, WasmJVMError> {
         unsafe {
             if HEAP.is_none() {
@@ -112,7 +140,7 @@ impl Heap {
 
             *index - 1
         } else {
-            unreachable!();
+            return Err(WasmJVMError::InternalError(format!("Heap index lock poisoned")));
         };
 
         Ok(index)
@@ -121,9 +149,17 @@ impl Heap {
 
 impl Global {
     pub fn new() -> Global {
-        Global {
+        let global = Global {
             ..Default::default()
+        };
+
+        if let Ok(mut data) = global.data.lock() {
+            for (key, value) in DEFAULT_PROPERTIES {
+                data.properties.insert(key.to_string(), value.to_string());
+            }
         }
+
+        global
     }
 
     pub fn index(self: &Self) -> Result<usize, WasmJVMError> {
@@ -162,12 +198,6 @@ impl Global {
         }
     }
 
-    pub fn register_native(self: &mut Self, r#fn: RegisterFn) -> Result<(), WasmJVMError> {
-        r#fn(self.native_mut()?);
-
-        Ok(())
-    }
-
     pub fn class(self: &Self, index: usize) -> Result<&ClassInstance, WasmJVMError> {
         let object = self.reference(index)?;
 
@@ -221,6 +251,10 @@ impl Global {
     }
 
     pub async fn thread_tick(self: &mut Self, thread_ref: usize) -> Result<ThreadResult, WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            data.current_thread = Some(thread_ref);
+        }
+
         let object_mut = self.reference_mut(thread_ref)?;
 
         if let RustObject::Thread(thread) = object_mut.inner_mut() {
@@ -231,11 +265,7 @@ impl Global {
     }
 
     pub fn threads(self: &mut Self) -> Vec<usize> {
-        if let Ok(data) = self.data.lock() {
-            data.threads.clone()
-        } else {
-            unreachable!()
-        }
+        self.data.lock().map(|data| data.threads.clone()).unwrap_or_default()
     }
 
     pub fn native_register(
@@ -267,10 +297,12 @@ impl Global {
         self: &mut Self,
         method_ref: &MethodRef,
         variables: Vec<Primitive>,
-    ) -> Result<Primitive, WasmJVMError> {
+    ) -> Result<(Primitive, Option<NativeContinuation>), WasmJVMError> {
         let method = self.native_mut()?.method(method_ref)?;
         let mut env = NativeEnv::new(self.clone(), variables);
-        Ok(method.invoke(&mut env).await)
+        let result = method.invoke(&mut env).await?;
+
+        Ok((result, env.continuation()))
     }
 
     pub fn reference_p(self: &Self, reference: &Primitive) -> Result<&Object, WasmJVMError> {
@@ -300,6 +332,61 @@ impl Global {
         self.heap.get_mut(reference)
     }
 
+    pub fn thread(self: &Self, index: usize) -> Result<&Thread, WasmJVMError> {
+        let object = self.reference(index)?;
+
+        if let RustObject::Thread(thread) = object.inner() {
+            Ok(thread)
+        } else {
+            Err(WasmJVMError::TODO(18))
+        }
+    }
+
+    pub fn current_thread(self: &Self) -> Result<&Thread, WasmJVMError> {
+        let thread_index = if let Ok(data) = self.data.lock() {
+            data.current_thread
+        } else {
+            None
+        };
+
+        if let Some(thread_index) = thread_index {
+            self.thread(thread_index)
+        } else {
+            Err(WasmJVMError::IllegalStateException(format!("No thread is running")))
+        }
+    }
+
+    /// The heap index of the thread being ticked.
+    pub fn current_thread_index(self: &Self) -> Result<usize, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            data.current_thread
+                .ok_or_else(|| WasmJVMError::IllegalStateException(format!("No thread is running")))
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn set_current_thread(self: &mut Self, thread_ref: usize) -> Result<(), WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            data.current_thread = Some(thread_ref);
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    /// Stops scheduling the thread, so it is collected once nothing else refers to it.
+    pub fn remove_thread(self: &mut Self, thread_ref: usize) -> Result<(), WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            data.threads.retain(|index| *index != thread_ref);
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
     pub fn thread_mut(self: &mut Self, index: usize) -> Result<&mut Thread, WasmJVMError> {
         let object = self.reference_mut(index)?;
 
@@ -310,6 +397,15 @@ impl Global {
         }
     }
 
+    pub fn new_thread_id(self: &mut Self) -> usize {
+        if let Ok(mut data) = self.data.lock() {
+            data.thread_count += 1;
+            data.thread_count
+        } else {
+            0
+        }
+    }
+
     pub fn thread_lock(self: &mut Self, priority: usize) -> Result<bool, WasmJVMError> {
         if let Ok(mut data) = self.data.lock() {
             if priority > data.thread_priority {
@@ -344,236 +440,1530 @@ impl Global {
         }
     }
 
-    pub fn array_set(
-        self: &mut Self,
-        reference: Primitive,
-        index: Primitive,
-        value: Primitive,
-    ) -> Result<(), WasmJVMError> {
-        let object = self.reference_p_mut(&reference)?;
+    pub fn array_class(self: &mut Self, r#type: &Type) -> Result<usize, WasmJVMError> {
+        let name = r#type.descriptor();
 
-        if let (RustObject::Array(array), Primitive::Int(index)) = (object.inner_mut(), index) {
-            if index < 0 {
-                return Err(WasmJVMError::IndexOutOfBoundException(format!("{} < 0", index)));
-            }
+        if let Ok(class_index) = self.class_index(&name) {
+            Ok(class_index)
+        } else {
+            self.loader_mut()?.load_class(Class::new_array(&name))
+        }
+    }
 
-            if index >= array.len() as i32 {
-                return Err(WasmJVMError::IndexOutOfBoundException(format!("{} >= {}", index, array.len())));
-            }
+    pub fn primitive_class(self: &mut Self, r#type: &SingleType) -> Result<usize, WasmJVMError> {
+        let name = r#type.name();
 
-            array[index as usize] = value;
+        if let Ok(class_index) = self.class_index(&name) {
+            Ok(class_index)
+        } else {
+            self.loader_mut()?.load_class(Class::new_primitive(&name))
+        }
+    }
 
-            return Ok(());
+    /// The class mirror for any field or return type, including primitives and arrays.
+    pub fn type_class(self: &mut Self, r#type: &Type) -> Result<usize, WasmJVMError> {
+        match r#type {
+            Type::Single(SingleType::Object(name)) => self.resolve_class(name),
+            Type::Single(single) => self.primitive_class(single),
+            Type::Array(..) => self.array_class(r#type),
         }
+    }
 
-        Err(WasmJVMError::TODO(19))
+    /// Resolves the class named by a `CONSTANT_Class` entry, which uses descriptor syntax for arrays.
+    pub fn constant_class(self: &mut Self, name: &str) -> Result<usize, WasmJVMError> {
+        self.type_class(&Type::from_class_name(name)?)
     }
 
-    pub fn static_field(self: &mut Self, field_ref: &FieldRef) -> Result<Primitive, WasmJVMError> {
-        if let Ok(class_ref) = self.class_index(field_ref.class.as_str()) {
-            if let Some(field) = self.class(class_ref)?.statics.get(&field_ref.name) {
-                Ok(field.clone())
-            } else {
-                Err(WasmJVMError::TODO(19))
+    pub fn new_method_type(self: &mut Self, descriptor: &Descriptor) -> Result<usize, WasmJVMError> {
+        let rtype = self.type_class(descriptor.output())?;
+        let ptypes = descriptor
+            .parameters()
+            .map(|parameter| self.type_class(parameter).map(Some))
+            .collect::<Result<Vec<Option<usize>>, WasmJVMError>>()?;
+
+        let class_type = Type::Single(SingleType::Object(JAVA_CLASS.to_string()));
+        let ptypes = self.new_array_from(&class_type, Array::Reference(ptypes))?;
+
+        let method_type = self.new_instance(JAVA_METHOD_TYPE)?;
+        let reference = Primitive::Reference(method_type);
+        self.field_named_set(&reference, "rtype", Primitive::Reference(rtype))?;
+        self.field_named_set(&reference, "ptypes", Primitive::Reference(ptypes))?;
+
+        Ok(method_type)
+    }
+
+    /// Resolves a `CONSTANT_MethodHandle` whose `reference_index` points at `member`.
+    pub fn new_method_handle(self: &mut Self, kind: u8, member: &Constant) -> Result<usize, WasmJVMError> {
+        let (class, name, descriptor) = match (kind, member) {
+            (1..=4, Constant::FieldRef(field_ref)) => {
+                (&field_ref.class, &field_ref.name, &field_ref.descriptor)
             }
-        } else {
-            self.loader_mut()?.load_class_name(field_ref.class.as_str())?;
+            (5..=8, Constant::MethodRef(method_ref))
+            | (6..=7, Constant::InterfaceMethodRef(method_ref))
+            | (9, Constant::InterfaceMethodRef(method_ref)) => {
+                (&method_ref.class, &method_ref.name, &method_ref.descriptor)
+            }
+            _ => {
+                return Err(WasmJVMError::ClassFormatError(format!(
+                    "Method handle kind {} for {:?}",
+                    kind, member
+                )))
+            }
+        };
 
-            Err(WasmJVMError::LinkageError(format!("Class {} not linked.", field_ref.class)))
+        let receiver = Type::Single(SingleType::Object(class.clone()));
+        let void = Type::Single(SingleType::Void);
+        let parameters: Vec<Type> = descriptor.parameters().cloned().collect();
+        let output = descriptor.output().clone();
+
+        let handle_type = match kind {
+            1 => Descriptor::new(vec![receiver], output),
+            2 => Descriptor::new(Vec::new(), output),
+            3 => Descriptor::new(vec![receiver, output], void),
+            4 => Descriptor::new(vec![output], void),
+            6 => Descriptor::new(parameters, output),
+            8 => Descriptor::new(parameters, receiver),
+            _ => Descriptor::new([vec![receiver], parameters].concat(), output),
+        };
+
+        let clazz = self.constant_class(class)?;
+        let name = self.new_java_string(name.clone())?;
+        let method_type = self.new_method_type(&handle_type)?;
+
+        let method_handle = self.new_instance(JAVA_METHOD_HANDLE)?;
+        let reference = Primitive::Reference(method_handle);
+        self.field_named_set(&reference, "kind", Primitive::Int(kind as i32))?;
+        self.field_named_set(&reference, "clazz", Primitive::Reference(clazz))?;
+        self.field_named_set(&reference, "name", Primitive::Reference(name))?;
+        self.field_named_set(&reference, "type", Primitive::Reference(method_type))?;
+
+        Ok(method_handle)
+    }
+
+    pub fn new_array(self: &mut Self, component: &Type, size: i32) -> Result<usize, WasmJVMError> {
+        if size < 0 {
+            return Err(WasmJVMError::NegativeArraySizeException(format!("{}", size)));
         }
+
+        self.new_array_from(component, Array::new(component, size as usize)?)
     }
 
-    pub fn static_field_set(
-        self: &mut Self,
-        field_ref: &FieldRef,
-        value: Primitive,
-    ) -> Result<(), WasmJVMError> {
-        let class_index = self.class_index(&field_ref.class)?;
-        let class = self.class_mut(class_index)?;
+    pub fn new_array_from(self: &mut Self, component: &Type, array: Array) -> Result<usize, WasmJVMError> {
+        let class_index = self.array_class(&component.array())?;
 
-        class.statics.insert(field_ref.name.clone(), value);
+        self.new_object(Object::new_array(class_index, array)?)
+    }
 
-        Ok(())
+    pub fn array(self: &Self, reference: &Primitive) -> Result<&Array, WasmJVMError> {
+        if reference.is_null() {
+            return Err(WasmJVMError::NullPointerException(format!("Array is null")));
+        }
+
+        if let RustObject::Array(array) = self.reference_p(reference)?.inner() {
+            Ok(array)
+        } else {
+            Err(WasmJVMError::ClassCastException(format!("{:?} is not an array", reference)))
+        }
     }
 
-    pub fn field_set(
-        self: &mut Self,
-        this_ref: Primitive,
-        field_ref: &FieldRef,
-        value: Primitive,
-    ) -> Result<(), WasmJVMError> {
-        // TODO: Null field?
-        if !this_ref.is_null() {
-            let object_mut = self.reference_p_mut(&this_ref)?;
+    pub fn array_mut(self: &mut Self, reference: &Primitive) -> Result<&mut Array, WasmJVMError> {
+        if reference.is_null() {
+            return Err(WasmJVMError::NullPointerException(format!("Array is null")));
+        }
 
-            object_mut.fields.insert(field_ref.name.clone(), value);
+        if let RustObject::Array(array) = self.reference_p_mut(reference)?.inner_mut() {
+            Ok(array)
+        } else {
+            Err(WasmJVMError::ClassCastException(format!("{:?} is not an array", reference)))
         }
+    }
 
-        Ok(())
+    pub fn array_get(self: &Self, reference: &Primitive, index: i32) -> Result<Primitive, WasmJVMError> {
+        self.array(reference)?.get(index)
     }
 
-    pub fn resolve_fields(self: &mut Self, this_ref: usize) -> Result<Vec<String>, WasmJVMError> {
-        let object = self.reference(this_ref)?;
+    pub fn array_set(
+        self: &mut Self,
+        reference: Primitive,
+        index: Primitive,
+        value: Primitive,
+    ) -> Result<(), WasmJVMError> {
+        let index = index.as_int()?;
 
-        let mut fields = Vec::new();
-        let mut class_index = object.class();
-        loop {
-            if let Some(index) = class_index {
-                let class = self.class(index)?;
+        if let Primitive::Reference(_) = &value {
+            let array_class = self.object_class_name(&reference)?;
 
-                fields.append(&mut class.metadata().field_names());
+            if let Some(component) = Type::from_class_name(&array_class)?.component() {
+                let value_class = self.object_class_name(&value)?;
 
-                if let Some(super_name) = class.metadata().super_class().clone() {
-                    class_index = Some(self.class_index(&super_name)?);
-                } else {
-                    class_index = None;
+                if !self.is_assignable(&value_class, &component.class_name())? {
+                    return Err(WasmJVMError::ArrayStoreException(value_class.replace('/', ".")));
                 }
-            } else {
-                break;
             }
         }
 
-        Ok(fields)
+        self.array_mut(&reference)?.set(index, value)
     }
 
-    pub fn set_main_class(self: &mut Self, class_name: &str) -> Result<(), WasmJVMError> {
-        let class_ref = self.class_index(class_name)?;
-
-        if let Ok(mut data) = self.data.lock() {
-            data.main_class_index = Some(class_ref);
+    /// `Object.hashCode` and `System.identityHashCode`: derived from the heap slot, which never
+    /// moves, and spread so that neighbouring objects do not get neighbouring hashes.
+    pub fn identity_hash_code(self: &Self, reference: &Primitive) -> Result<i32, WasmJVMError> {
+        match reference {
+            Primitive::Null => Ok(0),
+            Primitive::Reference(index) => {
+                let hash = (*index as u32 + 1).wrapping_mul(0x9e37_79b9);
 
-            return Ok(());
+                Ok(((hash ^ (hash >> 16)) & 0x7fff_ffff) as i32)
+            }
+            _ => Err(WasmJVMError::IllegalArgumentException(format!(
+                "{:?} is not a reference",
+                reference
+            ))),
         }
-
-        Err(WasmJVMError::ClassNotFoundException(format!(
-            "Could not find main class {}",
-            class_name
-        )))
     }
 
-    pub fn main_class_index(self: &Self) -> Result<usize, WasmJVMError> {
-        if let Ok(data) = self.data.lock() {
-            if let Some(index) = data.main_class_index {
-                return Ok(index);
+    /// `Object.clone`: a shallow copy of an array or of an instance of a `Cloneable` class.
+    pub fn clone_object(self: &mut Self, reference: &Primitive) -> Result<usize, WasmJVMError> {
+        let is_cloneable = self.is_instance(reference, JAVA_CLONEABLE)?;
+        let object = self.reference_p(reference)?;
+
+        let class_index = object.class().ok_or_else(|| {
+            WasmJVMError::IllegalStateException(format!("{:?} has no class", reference))
+        })?;
+
+        let inner = match object.inner() {
+            RustObject::Array(array) => RustObject::Array(array.clone()),
+            _ if !is_cloneable => {
+                return Err(WasmJVMError::CloneNotSupportedException(
+                    self.object_class_name(reference)?.replace('/', "."),
+                ))
             }
-        }
+            RustObject::String(chars) => RustObject::String(chars.clone()),
+            RustObject::Backtrace(entries) => RustObject::Backtrace(entries.clone()),
+            RustObject::Null => RustObject::Null,
+            // Thread.clone throws before getting here, and the other classes are final.
+            RustObject::Class(..) | RustObject::Thread(..) | RustObject::Loader(..) | RustObject::Native(..) => {
+                return Err(WasmJVMError::InternalError(format!(
+                    "{} holds VM state that can't be cloned",
+                    self.object_class_name(reference)?.replace('/', ".")
+                )))
+            }
+        };
 
-        Err(WasmJVMError::ClassNotFoundException(format!(
-            "No main class set"
-        )))
+        let clone = Object::new(class_index, object.fields().clone(), inner)?;
+
+        self.new_object(clone)
     }
 
-    pub fn class_index(self: &Self, name: &str) -> Result<usize, WasmJVMError> {
-        if let Ok(data) = self.data.lock() {
-            if let Some(index) = data.classes.get(name) {
-                return Ok(*index);
-            }
+    /// `System.arraycopy`, copying through a temporary so overlapping ranges of one array work.
+    pub fn array_copy(
+        self: &mut Self,
+        source: &Primitive,
+        source_position: i32,
+        destination: &Primitive,
+        destination_position: i32,
+        length: i32,
+    ) -> Result<(), WasmJVMError> {
+        if source.is_null() || destination.is_null() {
+            return Err(WasmJVMError::NullPointerException(format!("arraycopy: null array")));
         }
 
-        Err(WasmJVMError::ClassNotFoundException(format!("Class {} was not loaded", name)))
-    }
+        let source_type = Type::from_class_name(&self.object_class_name(source)?)?;
+        let destination_type = Type::from_class_name(&self.object_class_name(destination)?)?;
 
-    pub fn default_init(self: &mut Self, index: usize) -> Result<(), WasmJVMError> {
-        let loader_index = self.loader_index()?;
+        let (source_component, destination_component) =
+            match (source_type.component(), destination_type.component()) {
+                (Some(source_component), Some(destination_component)) => {
+                    (source_component, destination_component)
+                }
+                (None, _) => {
+                    return Err(WasmJVMError::ArrayStoreException(format!(
+                        "arraycopy: source type {} is not an array",
+                        crate::reflection::type_name(&source_type)
+                    )))
+                }
+                (_, None) => {
+                    return Err(WasmJVMError::ArrayStoreException(format!(
+                        "arraycopy: destination type {} is not an array",
+                        crate::reflection::type_name(&destination_type)
+                    )))
+                }
+            };
 
-        let object = self.heap.get_mut(loader_index)?;
+        let is_reference = |component: &Type| match component {
+            Type::Single(SingleType::Object(_)) | Type::Array(..) => true,
+            _ => false,
+        };
 
-        if let Some(class) = object.class() {
-            let loader = if let RustObject::Loader(loader) = object.inner_mut() {
-                loader
-            } else {
-                return Err(WasmJVMError::TODO(20));
-            };
+        let is_compatible = match (is_reference(&source_component), is_reference(&destination_component)) {
+            (true, true) => true,
+            (false, false) => source_component == destination_component,
+            _ => false,
+        };
 
-            loader.default_init(class, index)?;
+        if !is_compatible {
+            return Err(WasmJVMError::ArrayStoreException(format!(
+                "arraycopy: type mismatch: can not copy {} into {}",
+                crate::reflection::type_name(&source_type),
+                crate::reflection::type_name(&destination_type)
+            )));
+        }
 
-            Ok(())
-        } else {
-            Err(WasmJVMError::TODO(21))
+        let source_length = self.array(source)?.len() as i64;
+        let destination_length = self.array(destination)?.len() as i64;
+
+        let bounds_error = |name: &str, index: i64, r#type: &Type, array_length: i64| {
+            WasmJVMError::IndexOutOfBoundException(format!(
+                "arraycopy: {} index {} out of bounds for {}",
+                name,
+                index,
+                crate::reflection::type_name(r#type)
+                    .replacen("[]", &format!("[{}]", array_length), 1)
+            ))
+        };
+
+        if length < 0 {
+            return Err(WasmJVMError::IndexOutOfBoundException(format!(
+                "arraycopy: length {} is negative",
+                length
+            )));
+        }
+        if source_position < 0 {
+            return Err(bounds_error("source", source_position as i64, &source_type, source_length));
+        }
+        if destination_position < 0 {
+            return Err(bounds_error(
+                "destination",
+                destination_position as i64,
+                &destination_type,
+                destination_length,
+            ));
+        }
+        if source_position as i64 + length as i64 > source_length {
+            return Err(bounds_error(
+                "last source",
+                source_position as i64 + length as i64,
+                &source_type,
+                source_length,
+            ));
+        }
+        if destination_position as i64 + length as i64 > destination_length {
+            return Err(bounds_error(
+                "last destination",
+                destination_position as i64 + length as i64,
+                &destination_type,
+                destination_length,
+            ));
         }
-    }
 
-    pub fn method(
-        self: &mut Self,
-        method_ref: &MethodRef,
-    ) -> Result<(usize, usize, Descriptor), WasmJVMError> {
-        let class_index = self.class_index(&method_ref.class)?;
-        let class = self.class(class_index)?;
+        let mut elements = self
+            .array(source)?
+            .range(source_position as usize, length as usize);
+
+        // Elements are stored up to the first one the destination cannot hold.
+        let mut store_error = None;
+        if let Array::Reference(references) = &mut elements {
+            if !self.is_assignable(&source_component.class_name(), &destination_component.class_name())? {
+                for (i, element) in references.iter().enumerate() {
+                    if let Some(element) = element {
+                        let element_class = self.object_class_name(&Primitive::Reference(*element))?;
+
+                        if !self.is_assignable(&element_class, &destination_component.class_name())? {
+                            store_error = Some(WasmJVMError::ArrayStoreException(format!(
+                                "arraycopy: element type mismatch: can not cast one of the elements of {} to the type of the destination array, {}",
+                                crate::reflection::type_name(&source_type),
+                                crate::reflection::type_name(&destination_component)
+                            )));
+                            references.truncate(i);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
 
-        let method_index = class.metadata().method_index(&method_ref)?;
-        let descriptor = method_ref.descriptor.clone();
+        self.array_mut(destination)?
+            .write(destination_position as usize, &elements)?;
 
-        Ok((class_index, method_index, descriptor))
+        match store_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
 
-    pub fn new_object(self: &mut Self, object: Object) -> Result<usize, WasmJVMError> {
-        if let Ok(mut data) = self.data.lock() {
-            let index = self.heap.index()?;
+    pub fn object_class_name(self: &Self, reference: &Primitive) -> Result<String, WasmJVMError> {
+        if reference.is_null() {
+            return Err(WasmJVMError::NullPointerException(format!("Object is null")));
+        }
 
-            match object.inner() {
-                RustObject::Class(class) => {
-                    let class_name = class.metadata().this_class().to_string();
+        if let Some(class_index) = self.reference_p(reference)?.class() {
+            Ok(self.class(class_index)?.metadata().this_class().to_string())
+        } else {
+            Err(WasmJVMError::IllegalStateException(format!("{:?} has no class", reference)))
+        }
+    }
 
-                    if data.classes.contains_key(&class_name) {
-                        panic!("Duplicate class {}", class_name);
-                    }
+    /// JVMS 6.5 `checkcast`: whether a value of class `from` may be treated as an instance of `to`.
+    pub fn is_assignable(self: &Self, from: &str, to: &str) -> Result<bool, WasmJVMError> {
+        if from == to {
+            return Ok(true);
+        }
 
-                    data.classes.insert(class_name, index);
-                }
-                RustObject::Loader(_loader) => {
-                    if data.loader_index.is_some() {
-                        return Err(WasmJVMError::TODO(22));
+        // Primitive mirrors are only ever assignable to themselves.
+        if SingleType::primitive(from).is_some() || SingleType::primitive(to).is_some() {
+            return Ok(false);
+        }
+
+        if to == JAVA_OBJECT {
+            return Ok(true);
+        }
+
+        if from.starts_with('[') {
+            let from_component = Type::from_class_name(from)?.component();
+
+            return Ok(match (from_component, Type::from_class_name(to)?.component()) {
+                (Some(from_component), Some(to_component)) => match (&from_component, &to_component) {
+                    (Type::Single(SingleType::Object(..)), _) | (Type::Array(..), _) => {
+                        match &to_component {
+                            Type::Single(SingleType::Object(..)) | Type::Array(..) => self.is_assignable(
+                                &from_component.class_name(),
+                                &to_component.class_name(),
+                            )?,
+                            _ => false,
+                        }
                     }
+                    _ => from_component == to_component,
+                },
+                _ => to == JAVA_CLONEABLE || to == JAVA_SERIALIZABLE,
+            });
+        }
 
-                    data.loader_index = Some(index)
+        let mut queue = vec![from.to_string()];
+        while let Some(name) = queue.pop() {
+            if name == to {
+                return Ok(true);
+            }
+
+            if let Ok(class_index) = self.class_index(&name) {
+                let metadata = self.class(class_index)?.metadata();
+
+                if let Some(super_class) = metadata.super_class() {
+                    queue.push(super_class.clone());
                 }
-                RustObject::Native(_native) => {
-                    if data.native_index.is_some() {
-                        return Err(WasmJVMError::TODO(23));
-                    }
 
-                    data.native_index = Some(index)
+                if let Some(interfaces) = metadata.interfaces() {
+                    for interface in interfaces {
+                        queue.push(interface.name().to_string());
+                    }
                 }
-                RustObject::Thread(_thread) => data.threads.push(index),
-                _ => {}
             }
-
-            self.heap.alloc(object)
-        } else {
-            unreachable!()
         }
+
+        Ok(false)
     }
 
-    pub fn new_instance(self: &mut Self, class: &str) -> Result<usize, WasmJVMError> {
-        self.new_rust_instance(class, RustObject::Null)
+    pub fn is_instance(self: &Self, reference: &Primitive, class_name: &str) -> Result<bool, WasmJVMError> {
+        if reference.is_null() {
+            return Ok(false);
+        }
+
+        let object_class = self.object_class_name(reference)?;
+
+        self.is_assignable(&object_class, class_name)
     }
 
-    pub fn new_rust_instance(
-        self: &mut Self,
-        class: &str,
-        inner: RustObject,
-    ) -> Result<usize, WasmJVMError> {
-        let class_index = if let Ok(class_index) = self.class_index(class) {
-            class_index
-        } else {
-            self.loader_mut()?.load_class_name(class)?
+    pub fn box_primitive(self: &mut Self, r#type: &Type, value: Primitive) -> Result<Primitive, WasmJVMError> {
+        let primitive = match r#type {
+            Type::Single(SingleType::Void) => return Ok(Primitive::Null),
+            Type::Single(primitive) => primitive,
+            Type::Array(..) => return Ok(value),
         };
 
-        let fields = self.resolve_fields(class_index)?;
+        let class_name = match box_class_name(primitive) {
+            Some(class_name) => class_name,
+            None => return Ok(value),
+        };
+
+        let value = match primitive {
+            SingleType::Long => value.into_long()?,
+            SingleType::Float => value.into_float()?,
+            SingleType::Double => value.into_double()?,
+            _ => value.into_int()?,
+        };
+
+        let boxed = Primitive::Reference(self.new_instance(class_name)?);
+        self.field_named_set(&boxed, "value", value)?;
+
+        Ok(boxed)
+    }
+
+    /// Converts a reflective argument to `r#type`, unboxing and widening primitives.
+    pub fn unbox_primitive(self: &Self, r#type: &Type, value: &Primitive) -> Result<Primitive, WasmJVMError> {
+        let mismatch = || WasmJVMError::IllegalArgumentException(format!("argument type mismatch"));
+
+        let target = match r#type {
+            Type::Single(SingleType::Object(..)) | Type::Array(..) => {
+                return if value.is_null() || self.is_instance(value, &r#type.class_name())? {
+                    Ok(value.clone())
+                } else {
+                    Err(mismatch())
+                };
+            }
+            Type::Single(target) => target,
+        };
+
+        if value.is_null() {
+            return Err(mismatch());
+        }
+
+        let source = unbox_type(&self.object_class_name(value)?).ok_or_else(mismatch)?;
+
+        let widens = source == *target
+            || match (&source, target) {
+                (SingleType::Byte, SingleType::Short) => true,
+                (SingleType::Byte | SingleType::Short | SingleType::Char, SingleType::Int) => true,
+                (
+                    SingleType::Byte | SingleType::Short | SingleType::Char | SingleType::Int,
+                    SingleType::Long | SingleType::Float | SingleType::Double,
+                ) => true,
+                (SingleType::Long, SingleType::Float | SingleType::Double) => true,
+                (SingleType::Float, SingleType::Double) => true,
+                _ => false,
+            };
+
+        if !widens {
+            return Err(mismatch());
+        }
+
+        self.field_named(value, "value")?.into_type(r#type)
+    }
+
+    /// Whether code in `accessor` may use a member of `declaring` with `flags` (JVMS 5.4.4).
+    /// Reflective access as checked by `AccessibleObject`: the declaring class must be accessible too.
+    pub fn can_access(self: &mut Self, accessor: &str, declaring: &str, flags: &AccessFlags) -> Result<bool, WasmJVMError> {
+        Ok(self.can_access_class(accessor, declaring)?
+            && self.can_access_member(accessor, declaring, flags)?)
+    }
+
+    /// JVMS 5.4.4: a class is accessible if it is public or in the accessor's runtime package.
+    pub fn can_access_class(self: &Self, accessor: &str, class: &str) -> Result<bool, WasmJVMError> {
+        let class = match Type::from_class_name(class)? {
+            Type::Array(SingleType::Object(name), _) => name,
+            Type::Array(..) => return Ok(true),
+            _ => class.to_string(),
+        };
+
+        if accessor == class || package(accessor) == package(&class) {
+            return Ok(true);
+        }
+
+        let metadata = self.class(self.class_index(&class)?)?.metadata();
+
+        Ok(metadata.access_flags().has_type(&AccessFlagType::Public))
+    }
+
+    /// JVMS 5.4.4: access to a member of `declaring` with `flags`, with private access shared by nestmates.
+    pub fn can_access_member(
+        self: &mut Self,
+        accessor: &str,
+        declaring: &str,
+        flags: &AccessFlags,
+    ) -> Result<bool, WasmJVMError> {
+        if accessor == declaring || flags.has_type(&AccessFlagType::Public) {
+            Ok(true)
+        } else if flags.has_type(&AccessFlagType::Private) {
+            Ok(self.nest_host(accessor)? == self.nest_host(declaring)?)
+        } else if package(accessor) == package(declaring) {
+            Ok(true)
+        } else if flags.has_type(&AccessFlagType::Protected) {
+            self.is_assignable(accessor, declaring)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// JVMS 5.4.4: the nest host named by `NestHost`, or the class itself when it has none or the
+    /// host does not list it as a member.
+    pub fn nest_host(self: &mut Self, class: &str) -> Result<String, WasmJVMError> {
+        let host = match self.class(self.class_index(class)?)?.metadata().nest_host() {
+            Some(host) => host.to_string(),
+            None => return Ok(class.to_string()),
+        };
+
+        if package(&host) != package(class) {
+            return Ok(class.to_string());
+        }
+
+        let host_index = match self.resolve_class(&host) {
+            Ok(host_index) => host_index,
+            Err(_) => return Ok(class.to_string()),
+        };
+
+        if self.class(host_index)?.metadata().nest_members().contains(&class) {
+            Ok(host)
+        } else {
+            Ok(class.to_string())
+        }
+    }
+
+    pub fn check_class_access(self: &Self, accessor: &str, class: &str) -> Result<(), WasmJVMError> {
+        if self.can_access_class(accessor, class)? {
+            Ok(())
+        } else {
+            Err(WasmJVMError::IllegalAccessError(format!(
+                "tried to access class {} from class {}",
+                class.replace('/', "."),
+                accessor.replace('/', ".")
+            )))
+        }
+    }
+
+    pub fn check_field_access(self: &mut Self, accessor: &str, field_ref: &FieldRef) -> Result<(), WasmJVMError> {
+        self.check_class_access(accessor, &field_ref.class)?;
+
+        let is_static = self.lookup_field(self.class_index(&field_ref.class)?, field_ref)?;
+        let declaring_index = match is_static {
+            Some((declaring_index, _)) => declaring_index,
+            None => return Err(WasmJVMError::NoSuchFieldError(field_ref.name.clone())),
+        };
+
+        let metadata = self.class(declaring_index)?.metadata();
+        let declaring = metadata.this_class().to_string();
+        let flags = match metadata
+            .fields()
+            .into_iter()
+            .flatten()
+            .find(|field| field.name() == field_ref.name && field.descriptor() == &field_ref.descriptor)
+        {
+            Some(field) => field.access_flags().clone(),
+            None => return Err(WasmJVMError::NoSuchFieldError(field_ref.name.clone())),
+        };
+
+        if self.can_access_member(accessor, &declaring, &flags)? {
+            Ok(())
+        } else {
+            Err(WasmJVMError::IllegalAccessError(format!(
+                "tried to access field {}.{} from class {}",
+                declaring.replace('/', "."),
+                field_ref.name,
+                accessor.replace('/', ".")
+            )))
+        }
+    }
+
+    pub fn check_method_access(self: &mut Self, accessor: &str, method_ref: &MethodRef) -> Result<(), WasmJVMError> {
+        self.check_class_access(accessor, &method_ref.class)?;
+
+        // Methods invoked on arrays are the public members of Object, with clone made public.
+        if method_ref.class.starts_with('[') {
+            return Ok(());
+        }
+
+        let (declaring, flags) = match self.lookup_method(&method_ref.class, method_ref)? {
+            Some(method) => method,
+            None => return Ok(()),
+        };
+
+        if self.can_access_member(accessor, &declaring, &flags)? {
+            Ok(())
+        } else {
+            Err(WasmJVMError::IllegalAccessError(format!(
+                "tried to access method {}.{}{} from class {}",
+                declaring.replace('/', "."),
+                method_ref.name,
+                method_ref.descriptor.descriptor(),
+                accessor.replace('/', ".")
+            )))
+        }
+    }
+
+    /// JVMS 5.4.3.3: the class declaring the method `method_ref` resolves to, searching superclasses
+    /// before superinterfaces.
+    fn lookup_method(
+        self: &Self,
+        class: &str,
+        method_ref: &MethodRef,
+    ) -> Result<Option<(String, AccessFlags)>, WasmJVMError> {
+        let mut queue = vec![class.to_string()];
+        let mut interfaces = Vec::new();
+
+        while let Some(name) = queue.pop() {
+            let metadata = self.class(self.class_index(&name)?)?.metadata();
+
+            if let Ok(method_index) = metadata.method_index(method_ref) {
+                let flags = metadata.method(method_index).access_flags().clone();
+
+                return Ok(Some((name, flags)));
+            }
+
+            if let Some(super_class) = metadata.super_class() {
+                queue.push(super_class.clone());
+            }
+
+            if let Some(class_interfaces) = metadata.interfaces() {
+                interfaces.extend(class_interfaces.map(|interface| interface.name().to_string()));
+            }
+
+            if queue.is_empty() {
+                queue.append(&mut interfaces);
+                queue.reverse();
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn static_field(self: &mut Self, field_ref: &FieldRef) -> Result<Primitive, WasmJVMError> {
+        let class_index = self.resolve_field(field_ref, true)?;
+
+        if let Some(field) = self.class(class_index)?.statics.get(&field_ref.name) {
+            Ok(field.clone())
+        } else {
+            Err(WasmJVMError::NoSuchFieldError(field_ref.name.clone()))
+        }
+    }
+
+    pub fn static_field_set(
+        self: &mut Self,
+        field_ref: &FieldRef,
+        value: Primitive,
+    ) -> Result<(), WasmJVMError> {
+        let class_index = self.resolve_field(field_ref, true)?;
+        let class = self.class_mut(class_index)?;
+
+        class.statics.insert(field_ref.name.clone(), value);
+
+        Ok(())
+    }
+
+    pub fn resolve_class(self: &mut Self, name: &str) -> Result<usize, WasmJVMError> {
+        if let Ok(class_index) = self.class_index(name) {
+            Ok(class_index)
+        } else {
+            self.loader_mut()?.load_class_name(name)
+        }
+    }
+
+    fn lookup_field(
+        self: &Self,
+        class_index: usize,
+        field_ref: &FieldRef,
+    ) -> Result<Option<(usize, bool)>, WasmJVMError> {
+        let metadata = self.class(class_index)?.metadata();
+
+        for field in metadata.fields().into_iter().flatten() {
+            if field.name() == field_ref.name && field.descriptor() == &field_ref.descriptor {
+                let is_static = field.access_flags().has_type(&AccessFlagType::Static);
+
+                return Ok(Some((class_index, is_static)));
+            }
+        }
+
+        for interface in metadata.interfaces().into_iter().flatten() {
+            let interface_index = self.class_index(interface.name())?;
+
+            if let Some(field) = self.lookup_field(interface_index, field_ref)? {
+                return Ok(Some(field));
+            }
+        }
+
+        if let Some(super_class) = metadata.super_class() {
+            let super_index = self.class_index(super_class)?;
+
+            return self.lookup_field(super_index, field_ref);
+        }
+
+        Ok(None)
+    }
+
+    pub fn resolve_field(
+        self: &Self,
+        field_ref: &FieldRef,
+        is_static: bool,
+    ) -> Result<usize, WasmJVMError> {
+        let class_index = self.class_index(&field_ref.class)?;
+
+        match self.lookup_field(class_index, field_ref)? {
+            Some((declaring_index, declared_static)) if declared_static == is_static => {
+                Ok(declaring_index)
+            }
+            Some((declaring_index, _)) => {
+                Err(WasmJVMError::IncompatibleClassChangeError(format!(
+                    "Expected {} field {}.{}",
+                    if is_static { "static" } else { "non-static" },
+                    self.class(declaring_index)?.metadata().this_class().replace('/', "."),
+                    field_ref.name
+                )))
+            }
+            None => Err(WasmJVMError::NoSuchFieldError(field_ref.name.clone())),
+        }
+    }
+
+    pub fn field_index(self: &Self, field_ref: &FieldRef) -> Result<usize, WasmJVMError> {
+        let declaring_index = self.resolve_field(field_ref, false)?;
+        let class = self.class(declaring_index)?;
+        let declaring_class = class.metadata().this_class();
+
+        if let Some(index) = class
+            .layout()
+            .iter()
+            .rposition(|slot| slot.class == declaring_class && slot.name == field_ref.name)
+        {
+            Ok(index)
+        } else {
+            Err(WasmJVMError::NoSuchFieldError(field_ref.name.clone()))
+        }
+    }
+
+    pub fn field(self: &Self, this_ref: &Primitive, field_ref: &FieldRef) -> Result<Primitive, WasmJVMError> {
+        if this_ref.is_null() {
+            return Err(WasmJVMError::NullPointerException(format!(
+                "Cannot read field \"{}\" because value is null",
+                field_ref.name
+            )));
+        }
+
+        let index = self.field_index(field_ref)?;
+
+        self.reference_p(this_ref)?.field(index)
+    }
+
+    pub fn field_set(
+        self: &mut Self,
+        this_ref: Primitive,
+        field_ref: &FieldRef,
+        value: Primitive,
+    ) -> Result<(), WasmJVMError> {
+        if this_ref.is_null() {
+            return Err(WasmJVMError::NullPointerException(format!(
+                "Cannot assign field \"{}\" because value is null",
+                field_ref.name
+            )));
+        }
+
+        let index = self.field_index(field_ref)?;
+
+        self.reference_p_mut(&this_ref)?.set_field(index, value)
+    }
+
+    fn field_named_index(self: &Self, this_ref: &Primitive, name: &str) -> Result<usize, WasmJVMError> {
+        let object = self.reference_p(this_ref)?;
+
+        let class_index = if let Some(class_index) = object.class() {
+            class_index
+        } else {
+            return Err(WasmJVMError::NoSuchFieldError(format!("{}", name)));
+        };
+
+        let class = self.class(class_index)?;
+
+        if let Some(index) = class.field_index(name) {
+            Ok(index)
+        } else {
+            Err(WasmJVMError::NoSuchFieldError(format!(
+                "{}.{}",
+                class.metadata().this_class(),
+                name
+            )))
+        }
+    }
+
+    pub fn field_named(self: &Self, this_ref: &Primitive, name: &str) -> Result<Primitive, WasmJVMError> {
+        let index = self.field_named_index(this_ref, name)?;
+
+        self.reference_p(this_ref)?.field(index)
+    }
+
+    pub fn field_named_set(
+        self: &mut Self,
+        this_ref: &Primitive,
+        name: &str,
+        value: Primitive,
+    ) -> Result<(), WasmJVMError> {
+        let index = self.field_named_index(this_ref, name)?;
+
+        self.reference_p_mut(this_ref)?.set_field(index, value)
+    }
+
+    pub fn initialize_class(
+        self: &mut Self,
+        class_index: usize,
+        thread_id: usize,
+    ) -> Result<Option<Vec<MethodRef>>, WasmJVMError> {
+        let class = self.class(class_index)?;
+        let metadata = class.metadata();
+
+        match class.state() {
+            ClassState::Initialized => return Ok(None),
+            ClassState::Initializing(owner) if owner == thread_id => return Ok(None),
+            ClassState::Initializing(..) => return Ok(Some(Vec::new())),
+            ClassState::Erroneous => {
+                return Err(WasmJVMError::NoClassDefFoundError(format!(
+                    "Could not initialize class {}",
+                    metadata.this_class().replace('/', ".")
+                )))
+            }
+            ClassState::Linked => {}
+        }
+
+        let clinit = MethodRef::new(
+            metadata.this_class().to_string(),
+            "<clinit>".to_string(),
+            Descriptor::void(),
+        );
+        let has_clinit = metadata.method_index(&clinit).is_ok();
+
+        let super_class = if metadata.access_flags().has_type(&AccessFlagType::Interface) {
+            None
+        } else {
+            metadata.super_class().clone()
+        };
+
+        let mut frames = Vec::new();
+        if let Some(super_class) = super_class {
+            let super_index = self.class_index(&super_class)?;
+
+            if let Some(super_frames) = self.initialize_class(super_index, thread_id)? {
+                if super_frames.is_empty() {
+                    return Ok(Some(super_frames));
+                }
+
+                frames = super_frames;
+            }
+        }
+
+        let class = self.class_mut(class_index)?;
+        if has_clinit {
+            class.set_state(ClassState::Initializing(thread_id));
+            frames.insert(0, clinit);
+        } else {
+            class.set_state(ClassState::Initialized);
+        }
+
+        if frames.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(frames))
+        }
+    }
+
+    pub fn set_class_state(self: &mut Self, class_name: &str, state: ClassState) -> Result<(), WasmJVMError> {
+        let class_index = self.class_index(class_name)?;
+
+        self.class_mut(class_index)?.set_state(state);
+
+        Ok(())
+    }
+
+    pub fn instance_fields(self: &Self, class_index: usize) -> Result<Vec<Primitive>, WasmJVMError> {
+        Ok(self.class(class_index)?.defaults())
+    }
+
+    pub fn link_class(self: &mut Self, class_index: usize) -> Result<(), WasmJVMError> {
+        let metadata = self.class(class_index)?.metadata();
+
+        let mut layout = if let Some(super_class) = metadata.super_class() {
+            self.class(self.class_index(super_class)?)?.layout().clone()
+        } else {
+            Vec::new()
+        };
+
+        let mut constants = Vec::new();
+        for field in metadata.fields().into_iter().flatten() {
+            if field.access_flags().has_type(&AccessFlagType::Static) {
+                if let Some(constant) = field.constant_value() {
+                    constants.push((field.name().to_string(), constant.clone()));
+                }
+            } else {
+                layout.push(FieldSlot {
+                    class: metadata.this_class().to_string(),
+                    name: field.name().to_string(),
+                    r#type: field.r#type().clone(),
+                });
+            }
+        }
+
+        let mut statics = Vec::new();
+        for (name, constant) in constants {
+            let value = match constant {
+                Constant::String(string) => Primitive::Reference(self.intern_string(&string)?),
+                constant => Primitive::try_from(constant)?,
+            };
+
+            statics.push((name, value));
+        }
+
+        let class = self.class_mut(class_index)?;
+        class.set_layout(layout);
+
+        for (name, value) in statics {
+            class.statics.insert(name, value);
+        }
+
+        Ok(())
+    }
+
+    pub fn class_names(self: &Self, reference: &Primitive) -> Result<HashSet<String>, WasmJVMError> {
+        let object = self.reference_p(reference)?;
+
+        let mut class_names = HashSet::new();
+        let mut class_index = object.class();
+        while let Some(index) = class_index {
+            let metadata = self.class(index)?.metadata();
+
+            class_names.insert(metadata.this_class().to_string());
+
+            class_index = if let Some(super_class) = metadata.super_class() {
+                Some(self.class_index(super_class)?)
+            } else {
+                None
+            };
+        }
+
+        Ok(class_names)
+    }
+
+    pub fn java_string(self: &Self, reference: &Primitive) -> Result<Option<String>, WasmJVMError> {
+        if reference.is_null() {
+            return Ok(None);
+        }
+
+        if let RustObject::String(string) = self.reference_p(reference)?.inner() {
+            Ok(Some(String::from_utf16_lossy(string)))
+        } else {
+            Err(WasmJVMError::ClassCastException(format!("{:?} is not a string", reference)))
+        }
+    }
+
+    pub fn throwable_trace(self: &Self, throwable: &Primitive) -> Result<String, WasmJVMError> {
+        let mut lines = Vec::new();
+        let mut seen = HashSet::new();
+        let mut next = throwable.clone();
+        let mut enclosing: Vec<StackTraceEntry> = Vec::new();
+
+        while let Primitive::Reference(index) = next {
+            if !seen.insert(index) {
+                break;
+            }
+
+            let object = self.reference(index)?;
+            let class_name = if let Some(class_index) = object.class() {
+                self.class(class_index)?.metadata().this_class().replace('/', ".")
+            } else {
+                format!("{:?}", object)
+            };
+
+            let message = match self.field_named(&next, "detailMessage") {
+                Ok(message) => self.java_string(&message)?,
+                Err(..) => None,
+            };
+
+            let header = match message {
+                Some(message) => format!("{}: {}", class_name, message),
+                None => class_name,
+            };
+
+            if lines.is_empty() {
+                lines.push(header);
+            } else {
+                lines.push(format!("Caused by: {}", header));
+            }
+
+            let entries = match object.inner() {
+                RustObject::Backtrace(entries) => entries.clone(),
+                _ => Vec::new(),
+            };
+
+            // Frames shared with the enclosing trace are folded like HotSpot does.
+            let common = entries
+                .iter()
+                .rev()
+                .zip(enclosing.iter().rev())
+                .take_while(|(entry, enclosing)| entry == enclosing)
+                .count();
+
+            for entry in entries[..entries.len() - common].iter() {
+                lines.push(format!("\tat {}", entry));
+            }
+            if common > 0 {
+                lines.push(format!("\t... {} more", common));
+            }
+
+            enclosing = entries;
+
+            next = self.field_named(&next, "cause").unwrap_or(Primitive::Null);
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    pub fn set_main_class(self: &mut Self, class_name: &str) -> Result<(), WasmJVMError> {
+        let class_ref = self.class_index(class_name)?;
+
+        if let Ok(mut data) = self.data.lock() {
+            data.main_class_index = Some(class_ref);
+
+            return Ok(());
+        }
+
+        Err(WasmJVMError::ClassNotFoundException(format!(
+            "Could not find main class {}",
+            class_name
+        )))
+    }
+
+    pub fn main_class_index(self: &Self) -> Result<usize, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            if let Some(index) = data.main_class_index {
+                return Ok(index);
+            }
+        }
+
+        Err(WasmJVMError::ClassNotFoundException(format!(
+            "No main class set"
+        )))
+    }
+
+    pub fn class_index(self: &Self, name: &str) -> Result<usize, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            if let Some(index) = data.classes.get(name) {
+                return Ok(*index);
+            }
+        }
+
+        Err(WasmJVMError::ClassNotFoundException(format!("Class {} was not loaded", name)))
+    }
+
+    pub fn default_init(self: &mut Self, index: usize) -> Result<(), WasmJVMError> {
+        let loader_index = self.loader_index()?;
+
+        let object = self.heap.get_mut(loader_index)?;
+
+        if let Some(class) = object.class() {
+            let loader = if let RustObject::Loader(loader) = object.inner_mut() {
+                loader
+            } else {
+                return Err(WasmJVMError::TODO(20));
+            };
+
+            loader.default_init(class, index)?;
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::TODO(21))
+        }
+    }
+
+    pub fn method(
+        self: &Self,
+        method_ref: &MethodRef,
+    ) -> Result<(usize, usize, Descriptor), WasmJVMError> {
+        let class_index = self.class_index(&method_ref.class)?;
+        let class = self.class(class_index)?;
+
+        let method_index = class.metadata().method_index(&method_ref)?;
+        let descriptor = method_ref.descriptor.clone();
+
+        Ok((class_index, method_index, descriptor))
+    }
+
+    /// The class whose implementation a virtual call of `method_ref` on `receiver` runs.
+    pub fn dispatch_class(
+        self: &Self,
+        receiver: &Primitive,
+        method_ref: &MethodRef,
+    ) -> Result<String, WasmJVMError> {
+        if let Ok((class_index, method_index, _)) = self.method(method_ref) {
+            let method = self.class(class_index)?.metadata().method(method_index);
+
+            if method.access_flags().has_type(&AccessFlagType::Private) {
+                return Ok(method_ref.class.clone());
+            }
+        }
+
+        let mut class_name = self.object_class_name(receiver)?;
+
+        loop {
+            let metadata = self.class(self.class_index(&class_name)?)?.metadata();
+
+            if let Ok(method_index) = metadata.method_index(method_ref) {
+                if !metadata.method(method_index).access_flags().has_type(&AccessFlagType::Abstract) {
+                    return Ok(class_name);
+                }
+            }
+
+            match metadata.super_class() {
+                Some(super_class) => class_name = super_class.clone(),
+                None => return Ok(method_ref.class.clone()),
+            }
+        }
+    }
+
+    /// Checks the receiver and arguments of a call made from Rust against `method_ref`'s class
+    /// and descriptor, so a bad call fails before any frame is pushed.
+    pub fn check_call(
+        self: &mut Self,
+        method_ref: &MethodRef,
+        this: Option<&Primitive>,
+        arguments: &Vec<Primitive>,
+    ) -> Result<(), WasmJVMError> {
+        if let Some(this) = this {
+            let is_instance = match this {
+                Primitive::Null => true,
+                Primitive::Reference(..) => self.is_instance(this, &method_ref.class)?,
+                _ => false,
+            };
+
+            if !is_instance {
+                return Err(WasmJVMError::IllegalArgumentException(format!(
+                    "{:?} is not an instance of {}",
+                    this,
+                    method_ref.class.replace('/', ".")
+                )));
+            }
+        }
+
+        let parameters: Vec<&Type> = method_ref.descriptor.parameters().collect();
+        if parameters.len() != arguments.len() {
+            return Err(WasmJVMError::IllegalArgumentException(format!(
+                "wrong number of arguments: expected {} but got {}",
+                parameters.len(),
+                arguments.len()
+            )));
+        }
+
+        for (r#type, argument) in parameters.into_iter().zip(arguments.iter()) {
+            let matches = match (r#type, argument) {
+                (Type::Single(SingleType::Boolean), Primitive::Boolean(..))
+                | (Type::Single(SingleType::Byte), Primitive::Byte(..))
+                | (Type::Single(SingleType::Char), Primitive::Char(..))
+                | (Type::Single(SingleType::Short), Primitive::Short(..))
+                | (Type::Single(SingleType::Int), Primitive::Int(..))
+                | (Type::Single(SingleType::Long), Primitive::Long(..))
+                | (Type::Single(SingleType::Float), Primitive::Float(..))
+                | (Type::Single(SingleType::Double), Primitive::Double(..))
+                | (Type::Single(SingleType::Object(..)) | Type::Array(..), Primitive::Null) => true,
+                (Type::Single(SingleType::Object(..)) | Type::Array(..), Primitive::Reference(..)) => {
+                    let class_index = self.type_class(r#type)?;
+                    let class_name = self.class(class_index)?.metadata().this_class().to_string();
+
+                    self.is_instance(argument, &class_name)?
+                }
+                _ => false,
+            };
+
+            if !matches {
+                return Err(WasmJVMError::IllegalArgumentException(format!(
+                    "argument type mismatch: expected {:?} but got {:?}",
+                    r#type, argument
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn new_object(self: &mut Self, object: Object) -> Result<usize, WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            let index = self.heap.index()?;
+
+            match object.inner() {
+                RustObject::Class(class) => {
+                    let class_name = class.metadata().this_class().to_string();
+
+                    if data.classes.contains_key(&class_name) {
+                        return Err(WasmJVMError::LinkageError(format!(
+                            "duplicate class definition for {}",
+                            class_name
+                        )));
+                    }
+
+                    data.classes.insert(class_name, index);
+                }
+                RustObject::Loader(_loader) => {
+                    if data.loader_index.is_some() {
+                        return Err(WasmJVMError::TODO(22));
+                    }
+
+                    data.loader_index = Some(index)
+                }
+                RustObject::Native(_native) => {
+                    if data.native_index.is_some() {
+                        return Err(WasmJVMError::TODO(23));
+                    }
+
+                    data.native_index = Some(index)
+                }
+                RustObject::Thread(_thread) => data.threads.push(index),
+                _ => {}
+            }
+
+            data.allocations += 1;
+
+            self.heap.alloc(object)
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn new_instance(self: &mut Self, class: &str) -> Result<usize, WasmJVMError> {
+        self.new_rust_instance(class, RustObject::Null)
+    }
+
+    pub fn new_rust_instance(
+        self: &mut Self,
+        class: &str,
+        inner: RustObject,
+    ) -> Result<usize, WasmJVMError> {
+        let class_index = self.resolve_class(class)?;
+
+        let fields = self.instance_fields(class_index)?;
 
         let object = Object::new(class_index, fields, inner)?;
 
         self.new_object(object)
     }
 
+    pub fn new_throwable(
+        self: &mut Self,
+        class: &str,
+        message: Option<String>,
+        backtrace: Vec<StackTraceEntry>,
+    ) -> Result<usize, WasmJVMError> {
+        let message = match message {
+            Some(message) => Primitive::Reference(self.new_java_string(message)?),
+            None => Primitive::Null,
+        };
+
+        let index = self.new_rust_instance(class, RustObject::Backtrace(backtrace))?;
+        let throwable = Primitive::Reference(index);
+
+        self.field_named_set(&throwable, "detailMessage", message)?;
+
+        Ok(index)
+    }
+
     pub fn new_java_string(self: &mut Self, string: String) -> Result<usize, WasmJVMError> {
-        let index = self.new_rust_instance(&JAVA_STRING.to_string(), RustObject::String(string))?;
+        self.new_java_chars(string.encode_utf16().collect())
+    }
+
+    pub fn new_java_chars(self: &mut Self, chars: Vec<u16>) -> Result<usize, WasmJVMError> {
+        let index = self.new_rust_instance(&JAVA_STRING.to_string(), RustObject::String(chars))?;
 
         self.default_init(index)?;
 
         Ok(index)
     }
 
+    pub fn java_chars(self: &Self, reference: &Primitive) -> Result<&Vec<u16>, WasmJVMError> {
+        if let RustObject::String(chars) = self.reference_p(reference)?.inner() {
+            Ok(chars)
+        } else {
+            Err(WasmJVMError::ClassCastException(format!("{:?} is not a string", reference)))
+        }
+    }
+
+    pub fn intern_chars(self: &mut Self, chars: Vec<u16>) -> Result<usize, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            if let Some(index) = data.strings.get(&chars) {
+                return Ok(*index);
+            }
+        }
+
+        let index = self.new_java_chars(chars.clone())?;
+
+        if let Ok(mut data) = self.data.lock() {
+            data.strings.insert(chars, index);
+        }
+
+        Ok(index)
+    }
+
+    pub fn intern_string(self: &mut Self, string: &String) -> Result<usize, WasmJVMError> {
+        self.intern_chars(string.encode_utf16().collect())
+    }
+
+    pub fn heap(self: &Self) -> &Heap {
+        &self.heap
+    }
+
+    /// Every class loaded so far, including array and primitive classes.
+    pub fn class_indices(self: &Self) -> Result<Vec<usize>, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            Ok(data.classes.values().copied().collect())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    /// Heap entries the collector always treats as reachable.
+    pub fn roots(self: &Self) -> Result<Vec<usize>, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            let mut roots: Vec<usize> = data.classes.values().copied().collect();
+
+            roots.extend(data.strings.values().copied());
+            roots.extend(data.threads.iter().copied());
+            roots.extend(data.pending_references.iter().copied());
+            roots.extend(data.main_class_index);
+            roots.extend(data.native_index);
+            roots.extend(data.loader_index);
+
+            Ok(roots)
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn request_collection(self: &mut Self) -> Result<(), WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            data.collection_requested = true;
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    /// Whether a collection was requested or `interval` objects were allocated since the
+    /// last one, resetting both.
+    pub fn take_collection(self: &mut Self, interval: usize) -> Result<bool, WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            if data.collection_requested || data.allocations >= interval {
+                data.collection_requested = false;
+                data.allocations = 0;
+
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn push_pending_references(self: &mut Self, references: Vec<usize>) -> Result<(), WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            data.pending_references.extend(references);
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn pop_pending_reference(self: &mut Self) -> Result<Option<usize>, WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            if data.pending_references.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(data.pending_references.remove(0)))
+            }
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn has_pending_references(self: &Self) -> Result<bool, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            Ok(!data.pending_references.is_empty())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn reference_handler(self: &Self) -> Result<Option<usize>, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            Ok(data.reference_handler)
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn set_reference_handler(self: &mut Self, thread_index: usize) -> Result<(), WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            data.reference_handler = Some(thread_index);
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn property(self: &Self, key: &str) -> Result<Option<String>, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            Ok(data.properties.get(key).cloned())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    /// Returns the previous value, like `System.setProperty`.
+    pub fn set_property(self: &mut Self, key: String, value: String) -> Result<Option<String>, WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            Ok(data.properties.insert(key, value))
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    /// Property keys in sorted order, so listings don't depend on hashing.
+    pub fn property_keys(self: &Self) -> Result<Vec<String>, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            let mut keys: Vec<String> = data.properties.keys().cloned().collect();
+            keys.sort();
+
+            Ok(keys)
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn environment_variable(self: &Self, name: &str) -> Result<Option<String>, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            Ok(data.environment.get(name).cloned())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn set_environment_variable(self: &mut Self, name: String, value: String) -> Result<(), WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            data.environment.insert(name, value);
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    /// Asks the VM to stop every thread and finish with `status` after the current tick.
+    pub fn halt(self: &mut Self, status: i32) -> Result<(), WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            data.exit_status.get_or_insert(status);
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    /// The status `halt` was called with, left for the VM to take.
+    pub fn exit_status(self: &Self) -> Result<Option<i32>, WasmJVMError> {
+        if let Ok(data) = self.data.lock() {
+            Ok(data.exit_status)
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
+    pub fn take_exit_status(self: &mut Self) -> Result<Option<i32>, WasmJVMError> {
+        if let Ok(mut data) = self.data.lock() {
+            Ok(data.exit_status.take())
+        } else {
+            Err(WasmJVMError::InternalError(format!("Global lock poisoned")))
+        }
+    }
+
     pub fn heap_trace(self: &Self) -> Result<String, WasmJVMError> {
         unsafe {
             let mut entries: Vec<String> = Vec::new();
@@ -583,21 +1973,19 @@ impl Global {
                     if let Some(entry) = entry {
                         let mut output = String::new();
 
-                        if let Some(class) = entry.class() {
-                            let class_object = heap.get(class).unwrap().as_ref().unwrap();
-
+                        if let Some(Some(class_object)) = entry.class().and_then(|class| heap.get(class)) {
                             if let RustObject::Class(class) = class_object.inner() {
                                 output += format!("{} ", class.metadata().this_class()).as_str();
                             }
                         }
 
                         if let RustObject::Null = entry.inner() {
-                            output += format!("{:?} ", entry.fields).as_str();
+                            output += format!("{:?} ", entry.fields()).as_str();
                         } else {
                             let inner = entry.inner();
                             let inner_string = match &inner {
                                 &RustObject::Null => "Null".to_string(),
-                                &RustObject::String(string) => format!("{:?}", string),
+                                &RustObject::String(string) => format!("{:?}", String::from_utf16_lossy(string)),
                                 &RustObject::Array(array) => format!("{:?} ", array),
                                 _ => format!("{:?} ", inner)
                             };
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use wasmjvm_class::{Class, WithFields};
+use wasmjvm_class::{AccessFlagType, Class, SingleType, Type, WithAccessFlags, WithFields};
 
 use crate::Primitive;
 
@@ -10,10 +10,60 @@ pub const JAVA_CLASS: &str = "java/lang/Class";
 pub const JAVA_NATIVE: &str = "java/lang/Native";
 pub const JAVA_LOADER: &str = "java/lang/Loader";
 pub const JAVA_THREAD: &str = "java/lang/Thread";
+pub const JAVA_CLONEABLE: &str = "java/lang/Cloneable";
+pub const JAVA_SERIALIZABLE: &str = "java/io/Serializable";
+pub const JAVA_THROWABLE: &str = "java/lang/Throwable";
+pub const JAVA_ERROR: &str = "java/lang/Error";
+pub const JAVA_EXCEPTION_IN_INITIALIZER_ERROR: &str = "java/lang/ExceptionInInitializerError";
+pub const JAVA_NULL_POINTER_EXCEPTION: &str = "java/lang/NullPointerException";
+pub const JAVA_STACK_TRACE_ELEMENT: &str = "java/lang/StackTraceElement";
+pub const JAVA_METHOD: &str = "java/lang/reflect/Method";
+pub const JAVA_FIELD: &str = "java/lang/reflect/Field";
+pub const JAVA_CONSTRUCTOR: &str = "java/lang/reflect/Constructor";
+pub const JAVA_METHOD_TYPE: &str = "java/lang/invoke/MethodType";
+pub const JAVA_METHOD_HANDLE: &str = "java/lang/invoke/MethodHandle";
+pub const JAVA_REFERENCE: &str = "java/lang/ref/Reference";
+pub const JAVA_SOFT_REFERENCE: &str = "java/lang/ref/SoftReference";
+
+const BOXES: [(SingleType, &str); 8] = [
+    (SingleType::Boolean, "java/lang/Boolean"),
+    (SingleType::Byte, "java/lang/Byte"),
+    (SingleType::Char, "java/lang/Character"),
+    (SingleType::Short, "java/lang/Short"),
+    (SingleType::Int, "java/lang/Integer"),
+    (SingleType::Long, "java/lang/Long"),
+    (SingleType::Float, "java/lang/Float"),
+    (SingleType::Double, "java/lang/Double"),
+];
+
+pub fn box_class_name(primitive: &SingleType) -> Option<&'static str> {
+    BOXES.iter().find(|(r#type, _)| r#type == primitive).map(|(_, name)| *name)
+}
+
+pub fn unbox_type(class_name: &str) -> Option<SingleType> {
+    BOXES.iter().find(|(_, name)| *name == class_name).map(|(r#type, _)| r#type.clone())
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldSlot {
+    pub class: String,
+    pub name: String,
+    pub r#type: Type,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClassState {
+    Linked,
+    Initializing(usize),
+    Initialized,
+    Erroneous,
+}
 
 #[derive(Debug)]
 pub struct ClassInstance {
     metadata: Class,
+    state: ClassState,
+    layout: Vec<FieldSlot>,
     pub statics: HashMap<String, Primitive>
 }
 
@@ -21,12 +71,16 @@ impl ClassInstance {
     pub fn new(metadata: Class) -> Self {
         let mut statics = HashMap::new();
 
-        for field in metadata.static_field_names() {
-            statics.insert(field, Primitive::Null);
+        for field in metadata.fields().into_iter().flatten() {
+            if field.access_flags().has_type(&AccessFlagType::Static) {
+                statics.insert(field.name().to_string(), Primitive::zero(field.r#type()));
+            }
         }
 
         Self {
             metadata,
+            state: ClassState::Linked,
+            layout: Vec::new(),
             statics
         }
     }
@@ -34,4 +88,28 @@ impl ClassInstance {
     pub fn metadata(self: &Self) -> &Class {
         &self.metadata
     }
+
+    pub fn state(self: &Self) -> ClassState {
+        self.state
+    }
+
+    pub fn set_state(self: &mut Self, state: ClassState) {
+        self.state = state;
+    }
+
+    pub fn layout(self: &Self) -> &Vec<FieldSlot> {
+        &self.layout
+    }
+
+    pub fn set_layout(self: &mut Self, layout: Vec<FieldSlot>) {
+        self.layout = layout;
+    }
+
+    pub fn field_index(self: &Self, name: &str) -> Option<usize> {
+        self.layout.iter().rposition(|slot| slot.name == name)
+    }
+
+    pub fn defaults(self: &Self) -> Vec<Primitive> {
+        self.layout.iter().map(|slot| Primitive::zero(&slot.r#type)).collect()
+    }
 }
@@ -0,0 +1,1373 @@
+//! A port of fdlibm 5.3, the library behind `java.lang.StrictMath`. Everything is computed with
+//! plain IEEE arithmetic so the results are bit-for-bit the same on every target.
+
+const TWO_54: f64 = 1.80143985094819840000e+16;
+const TWO_MINUS_54: f64 = 5.55111512312578270212e-17;
+const TWO_24: f64 = 1.67772160000000000000e+07;
+const TWO_MINUS_24: f64 = 5.96046447753906250000e-08;
+const HUGE: f64 = 1.0e+300;
+const TINY: f64 = 1.0e-300;
+
+fn high(x: f64) -> i32 {
+    (x.to_bits() >> 32) as i32
+}
+
+fn low(x: f64) -> u32 {
+    x.to_bits() as u32
+}
+
+fn from_words(high: i32, low: u32) -> f64 {
+    f64::from_bits(((high as u32 as u64) << 32) | low as u64)
+}
+
+fn with_high(x: f64, high: i32) -> f64 {
+    from_words(high, low(x))
+}
+
+fn with_low(x: f64, low: u32) -> f64 {
+    from_words(high(x), low)
+}
+
+fn scalbn(mut x: f64, n: i32) -> f64 {
+    let mut hx = high(x);
+    let lx = low(x);
+    let mut k = (hx & 0x7ff00000) >> 20;
+
+    if k == 0 {
+        if (lx | (hx & 0x7fffffff) as u32) == 0 {
+            return x;
+        }
+
+        x *= TWO_54;
+        hx = high(x);
+        k = ((hx & 0x7ff00000) >> 20) - 54;
+
+        if n < -50000 {
+            return TINY * x;
+        }
+    }
+
+    if k == 0x7ff {
+        return x + x;
+    }
+
+    k += n;
+
+    if k > 0x7fe {
+        return HUGE * HUGE.copysign(x);
+    }
+
+    if k > 0 {
+        return with_high(x, (hx & 0x800fffffu32 as i32) | (k << 20));
+    }
+
+    if k <= -54 {
+        return if n > 50000 {
+            HUGE * HUGE.copysign(x)
+        } else {
+            TINY * TINY.copysign(x)
+        };
+    }
+
+    k += 54;
+    with_high(x, (hx & 0x800fffffu32 as i32) | (k << 20)) * TWO_MINUS_54
+}
+
+const TWO_OVER_PI: [i32; 66] = [
+    0xA2F983, 0x6E4E44, 0x1529FC, 0x2757D1, 0xF534DD, 0xC0DB62, 0x95993C, 0x439041, 0xFE5163,
+    0xABDEBB, 0xC561B7, 0x246E3A, 0x424DD2, 0xE00649, 0x2EEA09, 0xD1921C, 0xFE1DEB, 0x1CB129,
+    0xA73EE8, 0x8235F5, 0x2EBB44, 0x84E99C, 0x7026B4, 0x5F7E41, 0x3991D6, 0x398353, 0x39F49C,
+    0x845F8B, 0xBDF928, 0x3B1FF8, 0x97FFDE, 0x05980F, 0xEF2F11, 0x8B5A0A, 0x6D1F6D, 0x367ECF,
+    0x27CB09, 0xB74F46, 0x3F669E, 0x5FEA2D, 0x7527BA, 0xC7EBE5, 0xF17B3D, 0x0739F7, 0x8A5292,
+    0xEA6BFB, 0x5FB11F, 0x8D5D08, 0x560330, 0x46FC7B, 0x6BABF0, 0xCFBC20, 0x9AF436, 0x1DA9E3,
+    0x91615E, 0xE61B08, 0x659985, 0x5F14A0, 0x68408D, 0xFFD880, 0x4D7327, 0x310606, 0x1556CA,
+    0x73A8C9, 0x60E27B, 0xC08C6B,
+];
+
+const PI_OVER_2: [f64; 8] = [
+    1.57079625129699707031e+00,
+    7.54978941586159635335e-08,
+    5.39030252995776476554e-15,
+    3.28200341580791294123e-22,
+    1.27065575308067607349e-29,
+    1.22933308981111328932e-36,
+    2.73370053816464559624e-44,
+    2.16741683877804819444e-51,
+];
+
+/// `__kernel_rem_pio2` for a double precision result, returns the quadrant and writes `y`.
+fn kernel_rem_pio2(x: &[f64], y: &mut [f64; 2], e0: i32) -> i32 {
+    let jk = 4usize;
+    let jp = jk;
+    let jx = x.len() - 1;
+    let jv = ((e0 - 3) / 24).max(0) as usize;
+    let mut q0 = e0 - 24 * (jv as i32 + 1);
+
+    let mut f = [0.0f64; 20];
+    let mut q = [0.0f64; 20];
+    let mut fq = [0.0f64; 20];
+    let mut iq = [0i32; 20];
+
+    let m = jx + jk;
+    for (i, value) in f.iter_mut().enumerate().take(m + 1) {
+        let j = jv as i32 - jx as i32 + i as i32;
+        *value = if j < 0 { 0.0 } else { TWO_OVER_PI[j as usize] as f64 };
+    }
+
+    for i in 0..=jk {
+        let mut fw = 0.0;
+        for j in 0..=jx {
+            fw += x[j] * f[jx + i - j];
+        }
+        q[i] = fw;
+    }
+
+    let mut jz = jk;
+
+    loop {
+        let mut z = q[jz];
+        let mut j = jz;
+        let mut i = 0;
+        while j > 0 {
+            let fw = ((TWO_MINUS_24 * z) as i32) as f64;
+            iq[i] = (z - TWO_24 * fw) as i32;
+            z = q[j - 1] + fw;
+            i += 1;
+            j -= 1;
+        }
+
+        z = scalbn(z, q0);
+        z -= 8.0 * (z * 0.125).floor();
+        let mut n = z as i32;
+        z -= n as f64;
+
+        let mut ih = 0;
+        if q0 > 0 {
+            let i = iq[jz - 1] >> (24 - q0);
+            n += i;
+            iq[jz - 1] -= i << (24 - q0);
+            ih = iq[jz - 1] >> (23 - q0);
+        } else if q0 == 0 {
+            ih = iq[jz - 1] >> 23;
+        } else if z >= 0.5 {
+            ih = 2;
+        }
+
+        if ih > 0 {
+            n += 1;
+            let mut carry = 0;
+            for value in iq.iter_mut().take(jz) {
+                let j = *value;
+                if carry == 0 {
+                    if j != 0 {
+                        carry = 1;
+                        *value = 0x1000000 - j;
+                    }
+                } else {
+                    *value = 0xffffff - j;
+                }
+            }
+
+            match q0 {
+                1 => iq[jz - 1] &= 0x7fffff,
+                2 => iq[jz - 1] &= 0x3fffff,
+                _ => {}
+            }
+
+            if ih == 2 {
+                z = 1.0 - z;
+                if carry != 0 {
+                    z -= scalbn(1.0, q0);
+                }
+            }
+        }
+
+        if z == 0.0 && iq[jk..jz].iter().fold(0, |j, value| j | value) == 0 {
+            let mut k = 1;
+            while iq[jk - k] == 0 {
+                k += 1;
+            }
+
+            for i in jz + 1..=jz + k {
+                f[jx + i] = TWO_OVER_PI[jv + i] as f64;
+                let mut fw = 0.0;
+                for j in 0..=jx {
+                    fw += x[j] * f[jx + i - j];
+                }
+                q[i] = fw;
+            }
+
+            jz += k;
+            continue;
+        }
+
+        if z == 0.0 {
+            jz -= 1;
+            q0 -= 24;
+            while iq[jz] == 0 {
+                jz -= 1;
+                q0 -= 24;
+            }
+        } else {
+            z = scalbn(z, -q0);
+            if z >= TWO_24 {
+                let fw = ((TWO_MINUS_24 * z) as i32) as f64;
+                iq[jz] = (z - TWO_24 * fw) as i32;
+                jz += 1;
+                q0 += 24;
+                iq[jz] = fw as i32;
+            } else {
+                iq[jz] = z as i32;
+            }
+        }
+
+        let mut fw = scalbn(1.0, q0);
+        for i in (0..=jz).rev() {
+            q[i] = fw * iq[i] as f64;
+            fw *= TWO_MINUS_24;
+        }
+
+        for i in (0..=jz).rev() {
+            let mut fw = 0.0;
+            let mut k = 0;
+            while k <= jp && k <= jz - i {
+                fw += PI_OVER_2[k] * q[i + k];
+                k += 1;
+            }
+            fq[jz - i] = fw;
+        }
+
+        let mut fw = 0.0;
+        for i in (0..=jz).rev() {
+            fw += fq[i];
+        }
+        y[0] = if ih == 0 { fw } else { -fw };
+
+        fw = fq[0] - fw;
+        for value in fq.iter().take(jz + 1).skip(1) {
+            fw += value;
+        }
+        y[1] = if ih == 0 { fw } else { -fw };
+
+        return n & 7;
+    }
+}
+
+const NPIO2_HW: [i32; 32] = [
+    0x3FF921FB, 0x400921FB, 0x4012D97C, 0x401921FB, 0x401F6A7A, 0x4022D97C, 0x4025FDBB,
+    0x402921FB, 0x402C463A, 0x402F6A7A, 0x4031475C, 0x4032D97C, 0x40346B9C, 0x4035FDBB,
+    0x40378FDB, 0x403921FB, 0x403AB41B, 0x403C463A, 0x403DD85A, 0x403F6A7A, 0x40407E4C,
+    0x4041475C, 0x4042106C, 0x4042D97C, 0x4043A28C, 0x40446B9C, 0x404534AC, 0x4045FDBB,
+    0x4046C6CB, 0x40478FDB, 0x404858EB, 0x404921FB,
+];
+
+const INV_PIO2: f64 = 6.36619772367581382433e-01;
+const PIO2_1: f64 = 1.57079632673412561417e+00;
+const PIO2_1T: f64 = 6.07710050650619224932e-11;
+const PIO2_2: f64 = 6.07710050630396597660e-11;
+const PIO2_2T: f64 = 2.02226624879595063154e-21;
+const PIO2_3: f64 = 2.02226624871116645580e-21;
+const PIO2_3T: f64 = 8.47842766036889956997e-32;
+
+/// `__ieee754_rem_pio2`, reduces `x` to `y[0] + y[1]` in [-pi/4, pi/4] and returns the quadrant.
+fn rem_pio2(x: f64, y: &mut [f64; 2]) -> i32 {
+    let hx = high(x);
+    let ix = hx & 0x7fffffff;
+
+    if ix <= 0x3fe921fb {
+        y[0] = x;
+        y[1] = 0.0;
+        return 0;
+    }
+
+    if ix < 0x4002d97c {
+        return if hx > 0 {
+            let mut z = x - PIO2_1;
+            if ix != 0x3ff921fb {
+                y[0] = z - PIO2_1T;
+                y[1] = (z - y[0]) - PIO2_1T;
+            } else {
+                z -= PIO2_2;
+                y[0] = z - PIO2_2T;
+                y[1] = (z - y[0]) - PIO2_2T;
+            }
+            1
+        } else {
+            let mut z = x + PIO2_1;
+            if ix != 0x3ff921fb {
+                y[0] = z + PIO2_1T;
+                y[1] = (z - y[0]) + PIO2_1T;
+            } else {
+                z += PIO2_2;
+                y[0] = z + PIO2_2T;
+                y[1] = (z - y[0]) + PIO2_2T;
+            }
+            -1
+        };
+    }
+
+    if ix <= 0x413921fb {
+        let mut t = x.abs();
+        let n = (t * INV_PIO2 + 0.5) as i32;
+        let fn_ = n as f64;
+        let mut r = t - fn_ * PIO2_1;
+        let mut w = fn_ * PIO2_1T;
+
+        if n < 32 && ix != NPIO2_HW[(n - 1) as usize] {
+            y[0] = r - w;
+        } else {
+            let j = ix >> 20;
+            y[0] = r - w;
+            let i = j - ((high(y[0]) >> 20) & 0x7ff);
+
+            if i > 16 {
+                t = r;
+                w = fn_ * PIO2_2;
+                r = t - w;
+                w = fn_ * PIO2_2T - ((t - r) - w);
+                y[0] = r - w;
+                let i = j - ((high(y[0]) >> 20) & 0x7ff);
+
+                if i > 49 {
+                    t = r;
+                    w = fn_ * PIO2_3;
+                    r = t - w;
+                    w = fn_ * PIO2_3T - ((t - r) - w);
+                    y[0] = r - w;
+                }
+            }
+        }
+
+        y[1] = (r - y[0]) - w;
+
+        return if hx < 0 {
+            y[0] = -y[0];
+            y[1] = -y[1];
+            -n
+        } else {
+            n
+        };
+    }
+
+    if ix >= 0x7ff00000 {
+        y[0] = x - x;
+        y[1] = y[0];
+        return 0;
+    }
+
+    let e0 = (ix >> 20) - 1046;
+    let mut z = from_words(ix - (e0 << 20), low(x));
+    let mut tx = [0.0f64; 3];
+
+    for value in tx.iter_mut().take(2) {
+        *value = (z as i32) as f64;
+        z = (z - *value) * TWO_24;
+    }
+    tx[2] = z;
+
+    let mut nx = 3;
+    while tx[nx - 1] == 0.0 {
+        nx -= 1;
+    }
+
+    let n = kernel_rem_pio2(&tx[..nx], y, e0);
+
+    if hx < 0 {
+        y[0] = -y[0];
+        y[1] = -y[1];
+        -n
+    } else {
+        n
+    }
+}
+
+fn kernel_sin(x: f64, y: f64, iy: i32) -> f64 {
+    const S1: f64 = -1.66666666666666324348e-01;
+    const S2: f64 = 8.33333333332248946124e-03;
+    const S3: f64 = -1.98412698298579493134e-04;
+    const S4: f64 = 2.75573137070700676789e-06;
+    const S5: f64 = -2.50507602534068634195e-08;
+    const S6: f64 = 1.58969099521155010221e-10;
+
+    let ix = high(x) & 0x7fffffff;
+    if ix < 0x3e400000 && x as i32 == 0 {
+        return x;
+    }
+
+    let z = x * x;
+    let v = z * x;
+    let r = S2 + z * (S3 + z * (S4 + z * (S5 + z * S6)));
+
+    if iy == 0 {
+        x + v * (S1 + z * r)
+    } else {
+        x - ((z * (0.5 * y - v * r) - y) - v * S1)
+    }
+}
+
+fn kernel_cos(x: f64, y: f64) -> f64 {
+    const C1: f64 = 4.16666666666666019037e-02;
+    const C2: f64 = -1.38888888888741095749e-03;
+    const C3: f64 = 2.48015872894767294178e-05;
+    const C4: f64 = -2.75573143513906633035e-07;
+    const C5: f64 = 2.08757232129817482790e-09;
+    const C6: f64 = -1.13596475577881948265e-11;
+
+    let ix = high(x) & 0x7fffffff;
+    if ix < 0x3e400000 && x as i32 == 0 {
+        return 1.0;
+    }
+
+    let z = x * x;
+    let r = z * (C1 + z * (C2 + z * (C3 + z * (C4 + z * (C5 + z * C6)))));
+
+    if ix < 0x3FD33333 {
+        1.0 - (0.5 * z - (z * r - x * y))
+    } else {
+        let qx = if ix > 0x3fe90000 {
+            0.28125
+        } else {
+            from_words(ix - 0x00200000, 0)
+        };
+        let hz = 0.5 * z - qx;
+        let a = 1.0 - qx;
+
+        a - (hz - (z * r - x * y))
+    }
+}
+
+fn kernel_tan(mut x: f64, mut y: f64, iy: i32) -> f64 {
+    const T: [f64; 13] = [
+        3.33333333333334091986e-01,
+        1.33333333333201242699e-01,
+        5.39682539762260521377e-02,
+        2.18694882948595424599e-02,
+        8.86323982359930005737e-03,
+        3.59207910759131235356e-03,
+        1.45620945432529025516e-03,
+        5.88041240820264096874e-04,
+        2.46463134818469906812e-04,
+        7.81794442939557092300e-05,
+        7.14072491382608190305e-05,
+        -1.85586374855275456654e-05,
+        2.59073051863633712884e-05,
+    ];
+    const PIO4: f64 = 7.85398163397448278999e-01;
+    const PIO4_LO: f64 = 3.06161699786838301793e-17;
+
+    let hx = high(x);
+    let ix = hx & 0x7fffffff;
+
+    if ix < 0x3e300000 && x as i32 == 0 {
+        if ((ix as u32 | low(x)) | (iy + 1) as u32) == 0 {
+            return 1.0 / x.abs();
+        } else if iy == 1 {
+            return x;
+        } else {
+            let w = x + y;
+            let z = with_low(w, 0);
+            let v = y - (z - x);
+            let a = -1.0 / w;
+            let t = with_low(a, 0);
+            let s = 1.0 + t * z;
+
+            return t + a * (s + t * v);
+        }
+    }
+
+    if ix >= 0x3FE59428 {
+        if hx < 0 {
+            x = -x;
+            y = -y;
+        }
+        let z = PIO4 - x;
+        let w = PIO4_LO - y;
+        x = z + w;
+        y = 0.0;
+    }
+
+    let z = x * x;
+    let w = z * z;
+    let mut r = T[1] + w * (T[3] + w * (T[5] + w * (T[7] + w * (T[9] + w * T[11]))));
+    let v = z * (T[2] + w * (T[4] + w * (T[6] + w * (T[8] + w * (T[10] + w * T[12])))));
+    let s = z * x;
+    r = y + z * (s * (r + v) + y);
+    r += T[0] * s;
+    let w = x + r;
+
+    if ix >= 0x3FE59428 {
+        let v = iy as f64;
+        return (1 - ((hx >> 30) & 2)) as f64 * (v - 2.0 * (x - (w * w / (w + v) - r)));
+    }
+
+    if iy == 1 {
+        w
+    } else {
+        let z = with_low(w, 0);
+        let v = r - (z - x);
+        let a = -1.0 / w;
+        let t = with_low(a, 0);
+        let s = 1.0 + t * z;
+
+        t + a * (s + t * v)
+    }
+}
+
+pub fn sin(x: f64) -> f64 {
+    let ix = high(x) & 0x7fffffff;
+
+    if ix <= 0x3fe921fb {
+        return kernel_sin(x, 0.0, 0);
+    }
+    if ix >= 0x7ff00000 {
+        return x - x;
+    }
+
+    let mut y = [0.0; 2];
+    match rem_pio2(x, &mut y) & 3 {
+        0 => kernel_sin(y[0], y[1], 1),
+        1 => kernel_cos(y[0], y[1]),
+        2 => -kernel_sin(y[0], y[1], 1),
+        _ => -kernel_cos(y[0], y[1]),
+    }
+}
+
+pub fn cos(x: f64) -> f64 {
+    let ix = high(x) & 0x7fffffff;
+
+    if ix <= 0x3fe921fb {
+        return kernel_cos(x, 0.0);
+    }
+    if ix >= 0x7ff00000 {
+        return x - x;
+    }
+
+    let mut y = [0.0; 2];
+    match rem_pio2(x, &mut y) & 3 {
+        0 => kernel_cos(y[0], y[1]),
+        1 => -kernel_sin(y[0], y[1], 1),
+        2 => -kernel_cos(y[0], y[1]),
+        _ => kernel_sin(y[0], y[1], 1),
+    }
+}
+
+pub fn tan(x: f64) -> f64 {
+    let ix = high(x) & 0x7fffffff;
+
+    if ix <= 0x3fe921fb {
+        return kernel_tan(x, 0.0, 1);
+    }
+    if ix >= 0x7ff00000 {
+        return x - x;
+    }
+
+    let mut y = [0.0; 2];
+    let n = rem_pio2(x, &mut y);
+    kernel_tan(y[0], y[1], 1 - ((n & 1) << 1))
+}
+
+const PIO2_HI: f64 = 1.57079632679489655800e+00;
+const PIO2_LO: f64 = 6.12323399573676603587e-17;
+
+pub fn asin(x: f64) -> f64 {
+    const PIO4_HI: f64 = 7.85398163397448278999e-01;
+    const PS0: f64 = 1.66666666666666657415e-01;
+    const PS1: f64 = -3.25565818622400915405e-01;
+    const PS2: f64 = 2.01212532134862925881e-01;
+    const PS3: f64 = -4.00555345006794114027e-02;
+    const PS4: f64 = 7.91534994289814532176e-04;
+    const PS5: f64 = 3.47933107596021167570e-05;
+    const QS1: f64 = -2.40339491173441421878e+00;
+    const QS2: f64 = 2.02094576023350569471e+00;
+    const QS3: f64 = -6.88283971605453293030e-01;
+    const QS4: f64 = 7.70381505559019352791e-02;
+
+    let hx = high(x);
+    let ix = hx & 0x7fffffff;
+
+    if ix >= 0x3ff00000 {
+        if ((ix - 0x3ff00000) as u32 | low(x)) == 0 {
+            return x * PIO2_HI + x * PIO2_LO;
+        }
+        return (x - x) / (x - x);
+    } else if ix < 0x3fe00000 {
+        if ix < 0x3e400000 {
+            return x;
+        }
+
+        let t = x * x;
+        let p = t * (PS0 + t * (PS1 + t * (PS2 + t * (PS3 + t * (PS4 + t * PS5)))));
+        let q = 1.0 + t * (QS1 + t * (QS2 + t * (QS3 + t * QS4)));
+        let w = p / q;
+
+        return x + x * w;
+    }
+
+    let w = 1.0 - x.abs();
+    let mut t = w * 0.5;
+    let mut p = t * (PS0 + t * (PS1 + t * (PS2 + t * (PS3 + t * (PS4 + t * PS5)))));
+    let mut q = 1.0 + t * (QS1 + t * (QS2 + t * (QS3 + t * QS4)));
+    let s = t.sqrt();
+
+    if ix >= 0x3FEF3333 {
+        let w = p / q;
+        t = PIO2_HI - (2.0 * (s + s * w) - PIO2_LO);
+    } else {
+        let w = with_low(s, 0);
+        let c = (t - w * w) / (s + w);
+        let r = p / q;
+        p = 2.0 * s * r - (PIO2_LO - 2.0 * c);
+        q = PIO4_HI - 2.0 * w;
+        t = PIO4_HI - (p - q);
+    }
+
+    if hx > 0 {
+        t
+    } else {
+        -t
+    }
+}
+
+pub fn atan(mut x: f64) -> f64 {
+    const ATAN_HI: [f64; 4] = [
+        4.63647609000806093515e-01,
+        7.85398163397448278999e-01,
+        9.82793723247329054082e-01,
+        1.57079632679489655800e+00,
+    ];
+    const ATAN_LO: [f64; 4] = [
+        2.26987774529616870924e-17,
+        3.06161699786838301793e-17,
+        1.39033110312309984516e-17,
+        6.12323399573676603587e-17,
+    ];
+    const AT: [f64; 11] = [
+        3.33333333333329318027e-01,
+        -1.99999999998764832476e-01,
+        1.42857142725034663711e-01,
+        -1.11111104054623557880e-01,
+        9.09088713343650656196e-02,
+        -7.69187620504482999495e-02,
+        6.66107313738753120669e-02,
+        -5.83357013379057348645e-02,
+        4.97687799461593236017e-02,
+        -3.65315727442169155270e-02,
+        1.62858201153657823623e-02,
+    ];
+
+    let hx = high(x);
+    let ix = hx & 0x7fffffff;
+
+    if ix >= 0x44100000 {
+        if ix > 0x7ff00000 || (ix == 0x7ff00000 && low(x) != 0) {
+            return x + x;
+        }
+        return if hx > 0 {
+            ATAN_HI[3] + ATAN_LO[3]
+        } else {
+            -ATAN_HI[3] - ATAN_LO[3]
+        };
+    }
+
+    let id = if ix < 0x3fdc0000 {
+        if ix < 0x3e200000 {
+            return x;
+        }
+        None
+    } else {
+        x = x.abs();
+        if ix < 0x3ff30000 {
+            if ix < 0x3fe60000 {
+                x = (2.0 * x - 1.0) / (2.0 + x);
+                Some(0)
+            } else {
+                x = (x - 1.0) / (x + 1.0);
+                Some(1)
+            }
+        } else if ix < 0x40038000 {
+            x = (x - 1.5) / (1.0 + 1.5 * x);
+            Some(2)
+        } else {
+            x = -1.0 / x;
+            Some(3)
+        }
+    };
+
+    let z = x * x;
+    let w = z * z;
+    let s1 = z * (AT[0] + w * (AT[2] + w * (AT[4] + w * (AT[6] + w * (AT[8] + w * AT[10])))));
+    let s2 = w * (AT[1] + w * (AT[3] + w * (AT[5] + w * (AT[7] + w * AT[9]))));
+
+    match id {
+        None => x - x * (s1 + s2),
+        Some(id) => {
+            let z = ATAN_HI[id] - ((x * (s1 + s2) - ATAN_LO[id]) - x);
+            if hx < 0 {
+                -z
+            } else {
+                z
+            }
+        }
+    }
+}
+
+pub fn atan2(y: f64, x: f64) -> f64 {
+    const PI_O_4: f64 = 7.8539816339744827900E-01;
+    const PI_O_2: f64 = 1.5707963267948965580E+00;
+    const PI: f64 = 3.1415926535897931160E+00;
+    const PI_LO: f64 = 1.2246467991473531772E-16;
+
+    let hx = high(x);
+    let ix = hx & 0x7fffffff;
+    let lx = low(x);
+    let hy = high(y);
+    let iy = hy & 0x7fffffff;
+    let ly = low(y);
+
+    if (ix as u32 | ((lx | lx.wrapping_neg()) >> 31)) > 0x7ff00000
+        || (iy as u32 | ((ly | ly.wrapping_neg()) >> 31)) > 0x7ff00000
+    {
+        return x + y;
+    }
+    if (hx.wrapping_sub(0x3ff00000) as u32 | lx) == 0 {
+        return atan(y);
+    }
+
+    let m = ((hy >> 31) & 1) | ((hx >> 30) & 2);
+
+    if (iy as u32 | ly) == 0 {
+        return match m {
+            0 | 1 => y,
+            2 => PI + TINY,
+            _ => -PI - TINY,
+        };
+    }
+    if (ix as u32 | lx) == 0 {
+        return if hy < 0 { -PI_O_2 - TINY } else { PI_O_2 + TINY };
+    }
+
+    if ix == 0x7ff00000 {
+        return if iy == 0x7ff00000 {
+            match m {
+                0 => PI_O_4 + TINY,
+                1 => -PI_O_4 - TINY,
+                2 => 3.0 * PI_O_4 + TINY,
+                _ => -3.0 * PI_O_4 - TINY,
+            }
+        } else {
+            match m {
+                0 => 0.0,
+                1 => -0.0,
+                2 => PI + TINY,
+                _ => -PI - TINY,
+            }
+        };
+    }
+    if iy == 0x7ff00000 {
+        return if hy < 0 { -PI_O_2 - TINY } else { PI_O_2 + TINY };
+    }
+
+    let k = (iy - ix) >> 20;
+    let z = if k > 60 {
+        PI_O_2 + 0.5 * PI_LO
+    } else if hx < 0 && k < -60 {
+        0.0
+    } else {
+        atan((y / x).abs())
+    };
+
+    match m {
+        0 => z,
+        1 => -z,
+        2 => PI - (z - PI_LO),
+        _ => (z - PI_LO) - PI,
+    }
+}
+
+const LN2_HI: f64 = 6.93147180369123816490e-01;
+const LN2_LO: f64 = 1.90821492927058770002e-10;
+const INV_LN2: f64 = 1.44269504088896338700e+00;
+const P1: f64 = 1.66666666666666019037e-01;
+const P2: f64 = -2.77777777770155933842e-03;
+const P3: f64 = 6.61375632143793436117e-05;
+const P4: f64 = -1.65339022054652515390e-06;
+const P5: f64 = 4.13813679705723846039e-08;
+
+pub fn exp(mut x: f64) -> f64 {
+    const O_THRESHOLD: f64 = 7.09782712893383973096e+02;
+    const U_THRESHOLD: f64 = -7.45133219101941108420e+02;
+    const TWO_MINUS_1000: f64 = 9.33263618503218878990e-302;
+
+    let hx = high(x) as u32;
+    let xsb = ((hx >> 31) & 1) as i32;
+    let hx = hx & 0x7fffffff;
+
+    if hx >= 0x40862E42 {
+        if hx >= 0x7ff00000 {
+            if ((hx & 0xfffff) | low(x)) != 0 {
+                return x + x;
+            }
+            return if xsb == 0 { x } else { 0.0 };
+        }
+        if x > O_THRESHOLD {
+            return HUGE * HUGE;
+        }
+        if x < U_THRESHOLD {
+            return TWO_MINUS_1000 * TWO_MINUS_1000;
+        }
+    }
+
+    let mut hi = 0.0;
+    let mut lo = 0.0;
+    let k;
+
+    if hx > 0x3fd62e42 {
+        if hx < 0x3FF0A2B2 {
+            if xsb == 0 {
+                hi = x - LN2_HI;
+                lo = LN2_LO;
+            } else {
+                hi = x + LN2_HI;
+                lo = -LN2_LO;
+            }
+            k = 1 - xsb - xsb;
+        } else {
+            k = (INV_LN2 * x + if xsb == 0 { 0.5 } else { -0.5 }) as i32;
+            let t = k as f64;
+            hi = x - t * LN2_HI;
+            lo = t * LN2_LO;
+        }
+        x = hi - lo;
+    } else if hx < 0x3e300000 {
+        return 1.0 + x;
+    } else {
+        k = 0;
+    }
+
+    let t = x * x;
+    let c = x - t * (P1 + t * (P2 + t * (P3 + t * (P4 + t * P5))));
+
+    if k == 0 {
+        return 1.0 - ((x * c) / (c - 2.0) - x);
+    }
+
+    let y = 1.0 - ((lo - (x * c) / (2.0 - c)) - hi);
+
+    if k >= -1021 {
+        with_high(y, high(y).wrapping_add(k << 20))
+    } else {
+        with_high(y, high(y).wrapping_add((k + 1000) << 20)) * TWO_MINUS_1000
+    }
+}
+
+pub fn log(mut x: f64) -> f64 {
+    const LG1: f64 = 6.666666666666735130e-01;
+    const LG2: f64 = 3.999999999940941908e-01;
+    const LG3: f64 = 2.857142874366239149e-01;
+    const LG4: f64 = 2.222219843214978396e-01;
+    const LG5: f64 = 1.818357216161805012e-01;
+    const LG6: f64 = 1.531383769920937332e-01;
+    const LG7: f64 = 1.479819860511658591e-01;
+
+    let mut hx = high(x);
+    let lx = low(x);
+    let mut k = 0;
+
+    if hx < 0x00100000 {
+        if ((hx & 0x7fffffff) as u32 | lx) == 0 {
+            return f64::NEG_INFINITY;
+        }
+        if hx < 0 {
+            return f64::NAN;
+        }
+        k -= 54;
+        x *= TWO_54;
+        hx = high(x);
+    }
+    if hx >= 0x7ff00000 {
+        return x + x;
+    }
+
+    k += (hx >> 20) - 1023;
+    hx &= 0x000fffff;
+    let i = (hx + 0x95f64) & 0x100000;
+    x = with_high(x, hx | (i ^ 0x3ff00000));
+    k += i >> 20;
+    let f = x - 1.0;
+    let dk = k as f64;
+
+    if (0x000fffff & (2 + hx)) < 3 {
+        if f == 0.0 {
+            return if k == 0 { 0.0 } else { dk * LN2_HI + dk * LN2_LO };
+        }
+
+        let r = f * f * (0.5 - 0.33333333333333333 * f);
+        return if k == 0 {
+            f - r
+        } else {
+            dk * LN2_HI - ((r - dk * LN2_LO) - f)
+        };
+    }
+
+    let s = f / (2.0 + f);
+    let z = s * s;
+    let mut i = hx - 0x6147a;
+    let w = z * z;
+    let j = 0x6b851 - hx;
+    let t1 = w * (LG2 + w * (LG4 + w * LG6));
+    let t2 = z * (LG1 + w * (LG3 + w * (LG5 + w * LG7)));
+    i |= j;
+    let r = t2 + t1;
+
+    if i > 0 {
+        let hfsq = 0.5 * f * f;
+        if k == 0 {
+            f - (hfsq - s * (hfsq + r))
+        } else {
+            dk * LN2_HI - ((hfsq - (s * (hfsq + r) + dk * LN2_LO)) - f)
+        }
+    } else if k == 0 {
+        f - s * (f - r)
+    } else {
+        dk * LN2_HI - ((s * (f - r) - dk * LN2_LO) - f)
+    }
+}
+
+pub fn log10(mut x: f64) -> f64 {
+    const INV_LN10: f64 = 4.34294481903251816668e-01;
+    const LOG10_2_HI: f64 = 3.01029995663611771306e-01;
+    const LOG10_2_LO: f64 = 3.69423907715893078616e-13;
+
+    let mut hx = high(x);
+    let lx = low(x);
+    let mut k = 0;
+
+    if hx < 0x00100000 {
+        if ((hx & 0x7fffffff) as u32 | lx) == 0 {
+            return f64::NEG_INFINITY;
+        }
+        if hx < 0 {
+            return f64::NAN;
+        }
+        k -= 54;
+        x *= TWO_54;
+        hx = high(x);
+    }
+    if hx >= 0x7ff00000 {
+        return x + x;
+    }
+
+    k += (hx >> 20) - 1023;
+    let i = ((k as u32 & 0x80000000) >> 31) as i32;
+    hx = (hx & 0x000fffff) | ((0x3ff - i) << 20);
+    let y = (k + i) as f64;
+    x = with_high(x, hx);
+    let z = y * LOG10_2_LO + INV_LN10 * log(x);
+
+    z + y * LOG10_2_HI
+}
+
+pub fn pow(x: f64, y: f64) -> f64 {
+    const BP: [f64; 2] = [1.0, 1.5];
+    const DP_H: [f64; 2] = [0.0, 5.84962487220764160156e-01];
+    const DP_L: [f64; 2] = [0.0, 1.35003920212974897128e-08];
+    const TWO_53: f64 = 9007199254740992.0;
+    const L1: f64 = 5.99999999999994648725e-01;
+    const L2: f64 = 4.28571428578550184252e-01;
+    const L3: f64 = 3.33333329818377432918e-01;
+    const L4: f64 = 2.72728123808534006489e-01;
+    const L5: f64 = 2.30660745775561754067e-01;
+    const L6: f64 = 2.06975017800338417784e-01;
+    const LG2: f64 = 6.93147180559945286227e-01;
+    const LG2_H: f64 = 6.93147182464599609375e-01;
+    const LG2_L: f64 = -1.90465429995776804525e-09;
+    const OVT: f64 = 8.0085662595372944372e-17;
+    const CP: f64 = 9.61796693925975554329e-01;
+    const CP_H: f64 = 9.61796700954437255859e-01;
+    const CP_L: f64 = -7.02846165095275826516e-09;
+    const INV_LN2_H: f64 = 1.44269502162933349609e+00;
+    const INV_LN2_L: f64 = 1.92596299112661746887e-08;
+
+    let hx = high(x);
+    let lx = low(x);
+    let hy = high(y);
+    let ly = low(y);
+    let mut ix = hx & 0x7fffffff;
+    let iy = hy & 0x7fffffff;
+
+    if (iy as u32 | ly) == 0 {
+        return 1.0;
+    }
+
+    if ix > 0x7ff00000
+        || (ix == 0x7ff00000 && lx != 0)
+        || iy > 0x7ff00000
+        || (iy == 0x7ff00000 && ly != 0)
+    {
+        return x + y;
+    }
+
+    // 0: y is not an integer, 1: y is an odd integer, 2: y is an even integer.
+    let mut yisint = 0;
+    if hx < 0 {
+        if iy >= 0x43400000 {
+            yisint = 2;
+        } else if iy >= 0x3ff00000 {
+            let k = (iy >> 20) - 0x3ff;
+            if k > 20 {
+                let j = ly >> (52 - k);
+                if (j << (52 - k)) == ly {
+                    yisint = 2 - (j & 1) as i32;
+                }
+            } else if ly == 0 {
+                let j = iy >> (20 - k);
+                if (j << (20 - k)) == iy {
+                    yisint = 2 - (j & 1);
+                }
+            }
+        }
+    }
+
+    if ly == 0 {
+        if iy == 0x7ff00000 {
+            return if (ix.wrapping_sub(0x3ff00000) as u32 | lx) == 0 {
+                y - y
+            } else if ix >= 0x3ff00000 {
+                if hy >= 0 {
+                    y
+                } else {
+                    0.0
+                }
+            } else if hy < 0 {
+                -y
+            } else {
+                0.0
+            };
+        }
+        if iy == 0x3ff00000 {
+            return if hy < 0 { 1.0 / x } else { x };
+        }
+        if hy == 0x40000000 {
+            return x * x;
+        }
+        if hy == 0x3fe00000 && hx >= 0 {
+            return x.sqrt();
+        }
+    }
+
+    let mut ax = x.abs();
+
+    if lx == 0 && (ix == 0x7ff00000 || ix == 0 || ix == 0x3ff00000) {
+        let mut z = ax;
+        if hy < 0 {
+            z = 1.0 / z;
+        }
+        if hx < 0 {
+            if ((ix - 0x3ff00000) | yisint) == 0 {
+                z = (z - z) / (z - z);
+            } else if yisint == 1 {
+                z = -z;
+            }
+        }
+        return z;
+    }
+
+    let n = (hx >> 31) + 1;
+
+    if (n | yisint) == 0 {
+        return (x - x) / (x - x);
+    }
+
+    let s = if (n | (yisint - 1)) == 0 { -1.0 } else { 1.0 };
+
+    let t1;
+    let t2;
+
+    if iy > 0x41e00000 {
+        if iy > 0x43f00000 {
+            if ix <= 0x3fefffff {
+                return if hy < 0 { HUGE * HUGE } else { TINY * TINY };
+            }
+            if ix >= 0x3ff00000 {
+                return if hy > 0 { HUGE * HUGE } else { TINY * TINY };
+            }
+        }
+        if ix < 0x3fefffff {
+            return if hy < 0 { s * HUGE * HUGE } else { s * TINY * TINY };
+        }
+        if ix > 0x3ff00000 {
+            return if hy > 0 { s * HUGE * HUGE } else { s * TINY * TINY };
+        }
+
+        let t = ax - 1.0;
+        let w = (t * t) * (0.5 - t * (0.3333333333333333333333 - t * 0.25));
+        let u = INV_LN2_H * t;
+        let v = t * INV_LN2_L - w * INV_LN2;
+        t1 = with_low(u + v, 0);
+        t2 = v - (t1 - u);
+    } else {
+        let mut n = 0;
+
+        if ix < 0x00100000 {
+            ax *= TWO_53;
+            n -= 53;
+            ix = high(ax);
+        }
+        n += (ix >> 20) - 0x3ff;
+        let j = ix & 0x000fffff;
+        ix = j | 0x3ff00000;
+
+        let k = if j <= 0x3988E {
+            0
+        } else if j < 0xBB67A {
+            1
+        } else {
+            n += 1;
+            ix -= 0x00100000;
+            0
+        };
+        ax = with_high(ax, ix);
+
+        let u = ax - BP[k];
+        let v = 1.0 / (ax + BP[k]);
+        let ss = u * v;
+        let s_h = with_low(ss, 0);
+        let t_h = from_words(((ix >> 1) | 0x20000000) + 0x00080000 + ((k as i32) << 18), 0);
+        let t_l = ax - (t_h - BP[k]);
+        let s_l = v * ((u - s_h * t_h) - s_h * t_l);
+
+        let mut s2 = ss * ss;
+        let mut r = s2 * s2 * (L1 + s2 * (L2 + s2 * (L3 + s2 * (L4 + s2 * (L5 + s2 * L6)))));
+        r += s_l * (s_h + ss);
+        s2 = s_h * s_h;
+        let t_h = with_low(3.0 + s2 + r, 0);
+        let t_l = r - ((t_h - 3.0) - s2);
+
+        let u = s_h * t_h;
+        let v = s_l * t_h + t_l * ss;
+
+        let p_h = with_low(u + v, 0);
+        let p_l = v - (p_h - u);
+        let z_h = CP_H * p_h;
+        let z_l = CP_L * p_h + p_l * CP + DP_L[k];
+
+        let t = n as f64;
+        t1 = with_low(((z_h + z_l) + DP_H[k]) + t, 0);
+        t2 = z_l - (((t1 - t) - DP_H[k]) - z_h);
+    }
+
+    let y1 = with_low(y, 0);
+    let p_l = (y - y1) * t1 + y * t2;
+    let mut p_h = y1 * t1;
+    let mut z = p_l + p_h;
+    let j = high(z);
+    let i = low(z);
+
+    if j >= 0x40900000 {
+        if ((j - 0x40900000) as u32 | i) != 0 || p_l + OVT > z - p_h {
+            return s * HUGE * HUGE;
+        }
+    } else if (j & 0x7fffffff) >= 0x4090cc00
+        && ((j as u32).wrapping_sub(0xc090cc00) | i != 0 || p_l <= z - p_h)
+    {
+        return s * TINY * TINY;
+    }
+
+    let i = j & 0x7fffffff;
+    let mut k = (i >> 20) - 0x3ff;
+    let mut n = 0;
+
+    if i > 0x3fe00000 {
+        n = j + (0x00100000 >> (k + 1));
+        k = ((n & 0x7fffffff) >> 20) - 0x3ff;
+        let t = from_words(n & !(0x000fffff >> k), 0);
+        n = ((n & 0x000fffff) | 0x00100000) >> (20 - k);
+        if j < 0 {
+            n = -n;
+        }
+        p_h -= t;
+    }
+
+    let t = with_low(p_l + p_h, 0);
+    let u = t * LG2_H;
+    let v = (p_l - (t - p_h)) * LG2 + t * LG2_L;
+    z = u + v;
+    let w = v - (z - u);
+    let t = z * z;
+    let t1 = z - t * (P1 + t * (P2 + t * (P3 + t * (P4 + t * P5))));
+    let r = (z * t1) / (t1 - 2.0) - (w + z * w);
+    z = 1.0 - (r - z);
+
+    let j = high(z).wrapping_add(n << 20);
+    if (j >> 20) <= 0 {
+        z = scalbn(z, n);
+    } else {
+        z = with_high(z, j);
+    }
+
+    s * z
+}
+
+pub fn cbrt(x: f64) -> f64 {
+    const B1: u32 = 715094163;
+    const B2: u32 = 696219795;
+    const C: f64 = 5.42857142857142815906e-01;
+    const D: f64 = -7.05306122448979611050e-01;
+    const E: f64 = 1.41428571428571436819e+00;
+    const F: f64 = 1.60714285714285720630e+00;
+    const G: f64 = 3.57142857142857150787e-01;
+
+    let hx = high(x);
+    let sign = hx & 0x80000000u32 as i32;
+    let hx = hx ^ sign;
+
+    if hx >= 0x7ff00000 {
+        return x + x;
+    }
+    if (hx as u32 | low(x)) == 0 {
+        return x;
+    }
+
+    let x = with_high(x, hx);
+
+    let mut t = if hx < 0x00100000 {
+        let t = from_words(0x43500000, 0) * x;
+        from_words((high(t) as u32 / 3 + B2) as i32, 0)
+    } else {
+        from_words((hx as u32 / 3 + B1) as i32, 0)
+    };
+
+    let r = t * t / x;
+    let s = C + r * t;
+    t *= G + F / (s + E + D / s);
+
+    t = from_words(high(t) + 1, 0);
+
+    let s = t * t;
+    let mut r = x / s;
+    let w = t + t;
+    r = (r - t) / (w + r);
+    t += t * r;
+
+    with_high(t, high(t) | sign)
+}
+
+pub fn hypot(x: f64, y: f64) -> f64 {
+    const TWO_600: f64 = 4.149515568880993e+180;
+    const TWO_MINUS_600: f64 = 2.409919865102884e-181;
+
+    let mut a = x.abs();
+    let mut b = y.abs();
+
+    if !a.is_finite() || !b.is_finite() {
+        return if a == f64::INFINITY || b == f64::INFINITY {
+            f64::INFINITY
+        } else {
+            a + b
+        };
+    }
+
+    if b > a {
+        std::mem::swap(&mut a, &mut b);
+    }
+
+    let mut ha = high(a);
+    let mut hb = high(b);
+
+    if (ha - hb) > 0x3c00000 {
+        return a + b;
+    }
+
+    let mut k = 0;
+    if ha > 0x5f300000 {
+        ha -= 0x25800000;
+        hb -= 0x25800000;
+        a *= TWO_MINUS_600;
+        b *= TWO_MINUS_600;
+        k += 600;
+    }
+
+    if hb < 0x20b00000 {
+        if hb <= 0x000fffff {
+            if b == 0.0 {
+                return a;
+            }
+            let t1 = from_words(0x7fd00000, 0);
+            b *= t1;
+            a *= t1;
+            k -= 1022;
+        } else {
+            ha += 0x25800000;
+            hb += 0x25800000;
+            a *= TWO_600;
+            b *= TWO_600;
+            k -= 600;
+        }
+    }
+
+    let mut w = a - b;
+    if w > b {
+        let t1 = from_words(ha, 0);
+        let t2 = a - t1;
+        w = (t1 * t1 - (b * (-b) - t2 * (a + t1))).sqrt();
+    } else {
+        a += a;
+        let y1 = from_words(hb, 0);
+        let y2 = b - y1;
+        let t1 = from_words(ha + 0x00100000, 0);
+        let t2 = a - t1;
+        w = (t1 * y1 - (w * (-w) - (t1 * y2 + t2 * b))).sqrt();
+    }
+
+    if k != 0 {
+        from_words(0x3ff00000 + (k << 20), 0) * w
+    } else {
+        w
+    }
+}
+
+pub fn ieee_remainder(mut x: f64, mut p: f64) -> f64 {
+    let hx = high(x);
+    let lx = low(x);
+    let hp = high(p) & 0x7fffffff;
+    let lp = low(p);
+    let sx = hx & 0x80000000u32 as i32;
+    let hx = hx & 0x7fffffff;
+
+    if (hp as u32 | lp) == 0 {
+        return (x * p) / (x * p);
+    }
+    if hx >= 0x7ff00000 || (hp >= 0x7ff00000 && ((hp - 0x7ff00000) as u32 | lp) != 0) {
+        return (x * p) / (x * p);
+    }
+
+    if hp <= 0x7fdfffff {
+        // fmod is exact, so the remainder operator gives the same bits as fdlibm's fmod.
+        x %= p + p;
+    }
+    if ((hx - hp) as u32 | lx.wrapping_sub(lp)) == 0 {
+        return 0.0 * x;
+    }
+
+    x = x.abs();
+    p = p.abs();
+
+    if hp < 0x00200000 {
+        if x + x > p {
+            x -= p;
+            if x + x >= p {
+                x -= p;
+            }
+        }
+    } else {
+        let p_half = 0.5 * p;
+        if x > p_half {
+            x -= p;
+            if x >= p_half {
+                x -= p;
+            }
+        }
+    }
+
+    with_high(x, high(x) ^ sx)
+}
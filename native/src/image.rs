@@ -0,0 +1,358 @@
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, SeekFrom},
+};
+
+use wasmjvm_class::Class;
+use wasmjvm_common::WasmJVMError;
+
+use crate::Resource;
+
+const JIMAGE_MAGIC: u32 = 0xCAFEDADA;
+const JIMAGE_HEADER_SIZE: usize = 7 * 4;
+const JIMAGE_HASH_MULTIPLIER: u32 = 0x01000193;
+
+const ATTRIBUTE_END: u8 = 0;
+const ATTRIBUTE_MODULE: u8 = 1;
+const ATTRIBUTE_PARENT: u8 = 2;
+const ATTRIBUTE_BASE: u8 = 3;
+const ATTRIBUTE_EXTENSION: u8 = 4;
+const ATTRIBUTE_OFFSET: u8 = 5;
+const ATTRIBUTE_COMPRESSED: u8 = 6;
+const ATTRIBUTE_UNCOMPRESSED: u8 = 7;
+
+const JMOD_HEADER: [u8; 4] = [b'J', b'M', 1, 0];
+
+fn image_error(message: String) -> WasmJVMError {
+    WasmJVMError::ClassFormatError(format!("Invalid jimage: {}", message))
+}
+
+/// The attributes of one entry in the jimage location table.
+#[derive(Default)]
+struct Location {
+    attributes: [u64; 8],
+}
+
+impl Location {
+    fn get(self: &Self, kind: u8) -> u64 {
+        self.attributes[kind as usize]
+    }
+}
+
+/// A `lib/modules` file from a JDK runtime image. Only the index is kept in memory; class
+/// bytes are read from `reader` on demand.
+pub struct JImage<F: Read + Seek> {
+    reader: F,
+    little_endian: bool,
+    table_length: usize,
+    redirect: Vec<u8>,
+    offsets: Vec<u8>,
+    locations: Vec<u8>,
+    strings: Vec<u8>,
+    content_offset: u64,
+    length: u64,
+    /// Maps a package like `java/util` to the module that owns it.
+    packages: HashMap<String, String>,
+}
+
+impl<F> JImage<F>
+where
+    F: Read + Seek,
+{
+    pub fn new(mut reader: F) -> Result<Self, WasmJVMError> {
+        let mut header = [0u8; JIMAGE_HEADER_SIZE];
+        reader
+            .read_exact(&mut header)
+            .map_err(|error| image_error(format!("{}", error)))?;
+
+        let little_endian = match u32::from_le_bytes([header[0], header[1], header[2], header[3]]) {
+            JIMAGE_MAGIC => true,
+            magic if magic.swap_bytes() == JIMAGE_MAGIC => false,
+            magic => return Err(image_error(format!("bad magic {:#x}", magic))),
+        };
+        let field = |index: usize| {
+            let bytes = [header[index * 4], header[index * 4 + 1], header[index * 4 + 2], header[index * 4 + 3]];
+
+            if little_endian {
+                u32::from_le_bytes(bytes) as usize
+            } else {
+                u32::from_be_bytes(bytes) as usize
+            }
+        };
+
+        let version = field(1);
+        if version >> 16 != 1 {
+            return Err(image_error(format!("unsupported version {}.{}", version >> 16, version & 0xffff)));
+        }
+
+        let table_length = field(4);
+        let locations_size = field(5);
+        let strings_size = field(6);
+
+        let length = reader
+            .seek(SeekFrom::End(0))
+            .and_then(|length| reader.seek(SeekFrom::Start(JIMAGE_HEADER_SIZE as u64)).map(|_| length))
+            .map_err(|error| image_error(format!("{}", error)))?;
+        let index_size = (table_length as u64 * 8) + locations_size as u64 + strings_size as u64;
+
+        if JIMAGE_HEADER_SIZE as u64 + index_size > length {
+            return Err(image_error(format!("index of {} bytes doesn't fit in a {} byte file", index_size, length)));
+        }
+
+        let mut read = |size: usize| -> Result<Vec<u8>, WasmJVMError> {
+            let mut buffer = vec![0u8; size];
+            reader
+                .read_exact(&mut buffer)
+                .map_err(|error| image_error(format!("{}", error)))?;
+
+            Ok(buffer)
+        };
+
+        let redirect = read(table_length * 4)?;
+        let offsets = read(table_length * 4)?;
+        let locations = read(locations_size)?;
+        let strings = read(strings_size)?;
+        let content_offset = (JIMAGE_HEADER_SIZE + table_length * 8 + locations_size + strings_size) as u64;
+
+        let mut image = Self {
+            reader,
+            little_endian,
+            table_length,
+            redirect,
+            offsets,
+            locations,
+            strings,
+            content_offset,
+            length,
+            packages: HashMap::new(),
+        };
+        image.index_packages()?;
+
+        Ok(image)
+    }
+
+    fn table_entry(self: &Self, table: &[u8], index: usize) -> u32 {
+        let bytes = [table[index * 4], table[index * 4 + 1], table[index * 4 + 2], table[index * 4 + 3]];
+
+        if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        }
+    }
+
+    fn string(self: &Self, offset: u64) -> Result<&str, WasmJVMError> {
+        let start = offset as usize;
+        let end = self.strings[start.min(self.strings.len())..]
+            .iter()
+            .position(|byte| *byte == 0)
+            .map(|length| start + length)
+            .ok_or_else(|| image_error(format!("unterminated string at {}", offset)))?;
+
+        std::str::from_utf8(&self.strings[start..end]).map_err(|error| image_error(format!("{}", error)))
+    }
+
+    fn location(self: &Self, index: usize) -> Result<Location, WasmJVMError> {
+        let mut position = self.table_entry(&self.offsets, index) as usize;
+        let mut location = Location::default();
+
+        loop {
+            let byte = *self
+                .locations
+                .get(position)
+                .ok_or_else(|| image_error(format!("location {} out of range", index)))?;
+            let kind = byte >> 3;
+
+            if kind == ATTRIBUTE_END {
+                break;
+            }
+
+            let length = (byte & 0x7) as usize + 1;
+            let bytes = self
+                .locations
+                .get(position + 1..position + 1 + length)
+                .ok_or_else(|| image_error(format!("location {} out of range", index)))?;
+
+            if let Some(attribute) = location.attributes.get_mut(kind as usize) {
+                *attribute = bytes.iter().fold(0u64, |value, byte| (value << 8) | *byte as u64);
+            }
+
+            position += 1 + length;
+        }
+
+        Ok(location)
+    }
+
+    /// The name a location was stored under, like `/java.base/java/lang/Object.class`.
+    fn location_name(self: &Self, location: &Location) -> Result<String, WasmJVMError> {
+        let mut name = String::new();
+
+        let module = self.string(location.get(ATTRIBUTE_MODULE))?;
+        if !module.is_empty() {
+            name += "/";
+            name += module;
+            name += "/";
+        }
+
+        let parent = self.string(location.get(ATTRIBUTE_PARENT))?;
+        if !parent.is_empty() {
+            name += parent;
+            name += "/";
+        }
+
+        name += self.string(location.get(ATTRIBUTE_BASE))?;
+
+        let extension = self.string(location.get(ATTRIBUTE_EXTENSION))?;
+        if !extension.is_empty() {
+            name += ".";
+            name += extension;
+        }
+
+        Ok(name)
+    }
+
+    fn index_packages(self: &mut Self) -> Result<(), WasmJVMError> {
+        for index in 0..self.table_length {
+            let location = self.location(index)?;
+
+            if self.string(location.get(ATTRIBUTE_EXTENSION))? == "class" {
+                let module = self.string(location.get(ATTRIBUTE_MODULE))?.to_string();
+                let parent = self.string(location.get(ATTRIBUTE_PARENT))?.to_string();
+
+                self.packages.entry(parent).or_insert(module);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `ImageStringsReader.hashCode`, a FNV-style hash over the modified UTF-8 bytes.
+    fn hash(name: &str, seed: u32) -> usize {
+        let mut hash = seed;
+
+        for unit in name.encode_utf16() {
+            let bytes: Vec<u8> = match unit {
+                0x0001..=0x007f => vec![unit as u8],
+                0x0000 | 0x0080..=0x07ff => vec![0xc0 | (unit >> 6) as u8, 0x80 | (unit & 0x3f) as u8],
+                _ => vec![
+                    0xe0 | (unit >> 12) as u8,
+                    0x80 | ((unit >> 6) & 0x3f) as u8,
+                    0x80 | (unit & 0x3f) as u8,
+                ],
+            };
+
+            for byte in bytes {
+                hash = hash.wrapping_mul(JIMAGE_HASH_MULTIPLIER) ^ byte as u32;
+            }
+        }
+
+        (hash & 0x7fffffff) as usize
+    }
+
+    fn find(self: &Self, name: &str) -> Result<Option<Location>, WasmJVMError> {
+        if self.table_length == 0 {
+            return Ok(None);
+        }
+
+        let redirect = self.table_entry(&self.redirect, Self::hash(name, JIMAGE_HASH_MULTIPLIER) % self.table_length) as i32;
+
+        let index = if redirect < 0 {
+            (-redirect - 1) as usize
+        } else if redirect > 0 {
+            Self::hash(name, redirect as u32) % self.table_length
+        } else {
+            return Ok(None);
+        };
+
+        let location = self.location(index)?;
+
+        if self.location_name(&location)? == name {
+            Ok(Some(location))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reads a resource by its full image name, like `/java.base/java/lang/Object.class`.
+    pub fn resource(self: &mut Self, name: &str) -> Result<Option<Vec<u8>>, WasmJVMError> {
+        let location = match self.find(name)? {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+
+        if location.get(ATTRIBUTE_COMPRESSED) != 0 {
+            return Err(image_error(format!("{} is compressed, which is not supported", name)));
+        }
+
+        let size = location.get(ATTRIBUTE_UNCOMPRESSED);
+        let start = self.content_offset.saturating_add(location.get(ATTRIBUTE_OFFSET));
+
+        if start.saturating_add(size) > self.length {
+            return Err(image_error(format!("{} runs past the end of the image", name)));
+        }
+
+        let mut bytes = vec![0u8; size as usize];
+        self.reader
+            .seek(SeekFrom::Start(start))
+            .and_then(|_| self.reader.read_exact(&mut bytes))
+            .map_err(|error| image_error(format!("{}", error)))?;
+
+        Ok(Some(bytes))
+    }
+}
+
+impl<F> Resource for JImage<F>
+where
+    F: Read + Seek,
+{
+    fn load_class(self: &mut Self, name: &str) -> Result<Class, WasmJVMError> {
+        let package = name.rsplit_once('/').map_or("", |(package, _)| package);
+
+        if let Some(module) = self.packages.get(package).cloned() {
+            if let Some(bytes) = self.resource(&format!("/{}/{}.class", module, name))? {
+                return Class::from_file(bytes.as_slice());
+            }
+        }
+
+        Err(WasmJVMError::ClassNotFoundException(format!("{}", name)))
+    }
+}
+
+/// A `.jmod` file: a zip behind a four byte header, with the classes under `classes/`.
+pub struct JMod<F: Read + Seek> {
+    zip_file: zip::ZipArchive<F>,
+}
+
+impl<F> JMod<F>
+where
+    F: Read + Seek,
+{
+    pub fn new(mut reader: F) -> Result<Self, WasmJVMError> {
+        let mut header = [0u8; 4];
+        reader
+            .read_exact(&mut header)
+            .map_err(|error| WasmJVMError::ClassFormatError(format!("Invalid jmod: {}", error)))?;
+
+        if header != JMOD_HEADER {
+            return Err(WasmJVMError::ClassFormatError(format!("Invalid jmod: bad header {:?}", header)));
+        }
+
+        match zip::ZipArchive::new(reader) {
+            Ok(zip_file) => Ok(Self { zip_file }),
+            Err(error) => Err(WasmJVMError::ClassFormatError(format!("Invalid jmod: {}", error))),
+        }
+    }
+}
+
+impl<F> Resource for JMod<F>
+where
+    F: Read + Seek,
+{
+    fn load_class(self: &mut Self, name: &str) -> Result<Class, WasmJVMError> {
+        if let Ok(file) = self.zip_file.by_name(format!("classes/{}.class", name).as_str()) {
+            Class::from_file(file)
+        } else {
+            Err(WasmJVMError::ClassNotFoundException(format!("{}", name)))
+        }
+    }
+}
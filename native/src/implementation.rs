@@ -1,10 +1,14 @@
 use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
-use crate::{NativeEnv, NativeInterface, Object, Primitive, RustObject};
+use wasmjvm_common::WasmJVMError;
+use crate::{
+    native, Array, JObject, NativeEnv, NativeInterface, Primitive, RustObject, JAVA_OBJECT,
+    JAVA_STACK_TRACE_ELEMENT, JAVA_STRING,
+};
 
 #[macro_export]
 macro_rules! async_box {
     ($async_func: ident, $func: ident) => {
-        fn $async_func(env: &mut NativeEnv) -> std::pin::Pin<std::boxed::Box<dyn std::future::Future<Output = Primitive> + '_>> {
+        fn $async_func(env: &mut NativeEnv) -> std::pin::Pin<std::boxed::Box<dyn std::future::Future<Output = Result<Primitive, WasmJVMError>> + '_>> {
             Box::pin($func(env))
         }
     }
@@ -27,29 +31,210 @@ macro_rules! register_method {
 }
 
 pub fn register(interface: &mut NativeInterface) {
+    crate::reflection::register(interface);
+    crate::reference::register(interface);
+    crate::math::register(interface);
+    crate::number::register(interface);
+    crate::system::register(interface);
+    crate::jdk::register(interface);
+
     register_method!(
         interface,
         async_string_get_internal,
         "java/lang/String",
         "getInternal",
         vec![],
-        Type::Array(SingleType::Byte, 1)
+        Type::Array(SingleType::Char, 1)
     );
     register_method!(
         interface,
         async_string_set_internal,
         "java/lang/String",
         "setInternal",
+        vec![Type::Array(SingleType::Char, 1)],
+        Type::Single(SingleType::Void)
+    );
+    register_method!(
+        interface,
+        async_string_get_bytes,
+        "java/lang/String",
+        "getBytes",
+        vec![],
+        Type::Array(SingleType::Byte, 1)
+    );
+    register_method!(
+        interface,
+        async_string_set_bytes,
+        "java/lang/String",
+        "setBytes",
         vec![Type::Array(SingleType::Byte, 1)],
         Type::Single(SingleType::Void)
     );
     register_method!(
         interface,
-        async_object_get_class,
+        async_string_intern,
+        "java/lang/String",
+        "intern",
+        vec![],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+    register_method!(
+        interface,
+        async_string_length,
+        "java/lang/String",
+        "length",
+        vec![],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_string_char_at,
+        "java/lang/String",
+        "charAtInternal",
+        vec![Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Char)
+    );
+    register_method!(
+        interface,
+        async_string_equals,
+        "java/lang/String",
+        "equals",
+        vec![Type::Single(SingleType::Object(JAVA_OBJECT.to_string()))],
+        Type::Single(SingleType::Boolean)
+    );
+    register_method!(
+        interface,
+        async_string_hash_code,
+        "java/lang/String",
+        "hashCode",
+        vec![],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_string_compare_to,
+        "java/lang/String",
+        "compareTo",
+        vec![Type::Single(SingleType::Object(JAVA_STRING.to_string()))],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_string_index_of_char,
+        "java/lang/String",
+        "indexOf",
+        vec![Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_string_index_of_char,
+        "java/lang/String",
+        "indexOf",
+        vec![Type::Single(SingleType::Int), Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_string_index_of_string,
+        "java/lang/String",
+        "indexOf",
+        vec![Type::Single(SingleType::Object(JAVA_STRING.to_string()))],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_string_index_of_string,
+        "java/lang/String",
+        "indexOf",
+        vec![Type::Single(SingleType::Object(JAVA_STRING.to_string())), Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_string_substring,
+        "java/lang/String",
+        "substringInternal",
+        vec![Type::Single(SingleType::Int), Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+    register_method!(
+        interface,
+        async_string_concat,
+        "java/lang/String",
+        "concat",
+        vec![Type::Single(SingleType::Object(JAVA_STRING.to_string()))],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+    register_method!(
+        interface,
+        async_string_starts_with,
+        "java/lang/String",
+        "startsWith",
+        vec![Type::Single(SingleType::Object(JAVA_STRING.to_string()))],
+        Type::Single(SingleType::Boolean)
+    );
+    register_method!(
+        interface,
+        async_string_starts_with,
+        "java/lang/String",
+        "startsWith",
+        vec![Type::Single(SingleType::Object(JAVA_STRING.to_string())), Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Boolean)
+    );
+    register_method!(
+        interface,
+        async_string_to_upper_case,
+        "java/lang/String",
+        "toUpperCase",
+        vec![],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+    register_method!(
+        interface,
+        async_string_to_lower_case,
+        "java/lang/String",
+        "toLowerCase",
+        vec![],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+    register_method!(
+        interface,
+        async_object_hash_code,
         "java/lang/Object",
-        "getClass",
+        "hashCode",
         vec![],
-        Type::Single(SingleType::Object("java/lang/Class".to_string()))
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_object_clone,
+        "java/lang/Object",
+        "clone",
+        vec![],
+        Type::Single(SingleType::Object(JAVA_OBJECT.to_string()))
+    );
+    register_method!(
+        interface,
+        async_system_identity_hash_code,
+        "java/lang/System",
+        "identityHashCode",
+        vec![Type::Single(SingleType::Object(JAVA_OBJECT.to_string()))],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_system_arraycopy,
+        "java/lang/System",
+        "arraycopy",
+        vec![
+            Type::Single(SingleType::Object(JAVA_OBJECT.to_string())),
+            Type::Single(SingleType::Int),
+            Type::Single(SingleType::Object(JAVA_OBJECT.to_string())),
+            Type::Single(SingleType::Int),
+            Type::Single(SingleType::Int),
+        ],
+        Type::Single(SingleType::Void)
     );
     register_method!(
         interface,
@@ -59,96 +244,487 @@ pub fn register(interface: &mut NativeInterface) {
         vec![],
         Type::Single(SingleType::Object("java/lang/String".to_string()))
     );
+    register_method!(
+        interface,
+        async_throwable_fill_in_stack_trace,
+        "java/lang/Throwable",
+        "fillInStackTrace",
+        vec![],
+        Type::Single(SingleType::Object("java/lang/Throwable".to_string()))
+    );
+    register_method!(
+        interface,
+        async_throwable_get_stack_trace,
+        "java/lang/Throwable",
+        "getStackTrace",
+        vec![],
+        Type::Array(SingleType::Object("java/lang/StackTraceElement".to_string()), 1)
+    );
 }
 
 async_box!(async_string_get_internal, string_get_internal);
-async fn string_get_internal(env: &mut NativeEnv) -> Primitive {
+async fn string_get_internal(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
     let variables = env.variables().clone();
 
-    let value = if let [this, ..] = &variables[..] {
-        let this = env.reference(&this).unwrap();
+    if let [this, ..] = &variables[..] {
+        let chars = env.global().java_chars(this)?.clone();
 
-        if this.fields.contains_key(&"<raw>".to_string()) {
-            return this.fields.get(&"<raw>".to_string()).unwrap().clone();
-        } else if let RustObject::String(value) = this.inner() {
-            value
+        Ok(Primitive::Reference(
+            env.global_mut()
+                .new_array_from(&Type::Single(SingleType::Char), Array::Char(chars))?,
+        ))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_string_set_internal, string_set_internal);
+async fn string_set_internal(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = &env.variables().clone();
+
+    if let [this, raw, ..] = &variables[..] {
+        let chars = if let RustObject::Array(Array::Char(raw)) = env.reference(raw)?.inner() {
+            raw.clone()
         } else {
-            todo!()
+            return Err(env.invalid_arguments())
+        };
+
+        let this = env.reference_mut(this)?;
+        *this.inner_mut() = RustObject::String(chars);
+    };
+
+    Ok(Primitive::Void)
+}
+
+async_box!(async_string_get_bytes, string_get_bytes);
+async fn string_get_bytes(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, ..] = &variables[..] {
+        let string = env.string(this)?.ok_or_else(|| env.invalid_arguments())?;
+        let bytes = string.as_bytes().iter().map(|byte| *byte as i8).collect();
+
+        Ok(Primitive::Reference(
+            env.global_mut()
+                .new_array_from(&Type::Single(SingleType::Byte), Array::Byte(bytes))?,
+        ))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_string_set_bytes, string_set_bytes);
+async fn string_set_bytes(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = &env.variables().clone();
+
+    if let [this, raw, ..] = &variables[..] {
+        let bytes: Vec<u8> = if let RustObject::Array(Array::Byte(raw)) = env.reference(raw)?.inner() {
+            raw.iter().map(|byte| *byte as u8).collect()
+        } else {
+            return Err(env.invalid_arguments())
+        };
+
+        let this = env.reference_mut(this)?;
+        *this.inner_mut() = RustObject::String(String::from_utf8_lossy(&bytes).encode_utf16().collect());
+    };
+
+    Ok(Primitive::Void)
+}
+
+async_box!(async_string_intern, string_intern);
+async fn string_intern(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, ..] = &variables[..] {
+        let chars = env.global().java_chars(this)?.clone();
+
+        Ok(Primitive::Reference(env.global_mut().intern_chars(chars)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_string_length, string_length);
+async fn string_length(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [this, ..] = &env.variables()[..] {
+        Ok(Primitive::Int(env.global().java_chars(this)?.len() as i32))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_string_char_at, string_char_at);
+async fn string_char_at(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [this, Primitive::Int(index), ..] = &env.variables()[..] {
+        let chars = env.global().java_chars(this)?;
+        let char = chars.get(*index as usize).copied().ok_or_else(|| {
+            WasmJVMError::IndexOutOfBoundException(format!(
+                "Index {} out of bounds for length {}",
+                index,
+                chars.len()
+            ))
+        })?;
+
+        Ok(Primitive::Char(char))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_string_equals, string_equals);
+async fn string_equals(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [this, other, ..] = &env.variables()[..] {
+        if other.is_null() {
+            return Ok(Primitive::Boolean(false));
+        }
+
+        let global = env.global();
+        let this = global.java_chars(this)?;
+
+        match global.java_chars(other) {
+            Ok(other) => Ok(Primitive::Boolean(this == other)),
+            Err(_) => Ok(Primitive::Boolean(false)),
         }
     } else {
-        todo!()
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_string_hash_code, string_hash_code);
+async fn string_hash_code(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [this, ..] = &env.variables()[..] {
+        let hash = env
+            .global()
+            .java_chars(this)?
+            .iter()
+            .fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(*c as i32));
+
+        Ok(Primitive::Int(hash))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_string_compare_to, string_compare_to);
+async fn string_compare_to(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [this, other, ..] = &env.variables()[..] {
+        let global = env.global();
+        let this = global.java_chars(this)?;
+        let other = global.java_chars(other)?;
+
+        let difference = this
+            .iter()
+            .zip(other.iter())
+            .find(|(left, right)| left != right)
+            .map(|(left, right)| *left as i32 - *right as i32)
+            .unwrap_or(this.len() as i32 - other.len() as i32);
+
+        Ok(Primitive::Int(difference))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+fn index_of(chars: &[u16], target: &[u16], from: i32) -> i32 {
+    let from = from.clamp(0, chars.len() as i32) as usize;
+
+    if target.len() > chars.len() {
+        return -1;
+    }
+
+    (from..=chars.len() - target.len())
+        .find(|i| &chars[*i..*i + target.len()] == target)
+        .map_or(-1, |i| i as i32)
+}
+
+async_box!(async_string_index_of_char, string_index_of_char);
+async fn string_index_of_char(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let (this, code_point, from) = match &env.variables()[..] {
+        [this, Primitive::Int(code_point)] => (this, *code_point, 0),
+        [this, Primitive::Int(code_point), Primitive::Int(from), ..] => (this, *code_point, *from),
+        _ => return Err(env.invalid_arguments()),
     };
 
-    let raw_array: Vec<Primitive> = Vec::from_iter(value.as_bytes())
-        .iter()
-        .map(|c| Primitive::Byte(**c))
-        .collect();
+    let target: Vec<u16> = match char::from_u32(code_point as u32) {
+        Some(c) => c.encode_utf16(&mut [0; 2]).to_vec(),
+        None if (0..=0xffff).contains(&code_point) => vec![code_point as u16],
+        None => return Ok(Primitive::Int(-1)),
+    };
 
-    let array = Object::new_array(raw_array).unwrap();
+    Ok(Primitive::Int(index_of(env.global().java_chars(this)?, &target, from)))
+}
 
-    let index = Primitive::Reference(env.alloc(array).unwrap());
+async_box!(async_string_index_of_string, string_index_of_string);
+async fn string_index_of_string(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let (this, target, from) = match &env.variables()[..] {
+        [this, target] => (this, target, 0),
+        [this, target, Primitive::Int(from), ..] => (this, target, *from),
+        _ => return Err(env.invalid_arguments()),
+    };
 
-    if let [this, ..] = &variables[..] {
-        let this = env.reference_mut(&this).unwrap();
-        this.fields.insert("<raw>".to_string(), index.clone());
+    let global = env.global();
+
+    Ok(Primitive::Int(index_of(
+        global.java_chars(this)?,
+        global.java_chars(target)?,
+        from,
+    )))
+}
+
+async_box!(async_string_substring, string_substring);
+async fn string_substring(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, Primitive::Int(begin), Primitive::Int(end), ..] = &variables[..] {
+        let chars = env.global().java_chars(this)?;
+
+        if *begin == 0 && *end as usize == chars.len() {
+            return Ok(this.clone());
+        }
+
+        let chars = chars
+            .get(*begin as usize..*end as usize)
+            .ok_or_else(|| {
+                WasmJVMError::IndexOutOfBoundException(format!(
+                    "begin {}, end {}, length {}",
+                    begin,
+                    end,
+                    chars.len()
+                ))
+            })?
+            .to_vec();
+
+        Ok(Primitive::Reference(env.global_mut().new_java_chars(chars)?))
+    } else {
+        Err(env.invalid_arguments())
     }
+}
+
+async_box!(async_string_concat, string_concat);
+async fn string_concat(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, other, ..] = &variables[..] {
+        let global = env.global();
+        let other_chars = global.java_chars(other)?;
 
-    index
+        if other_chars.is_empty() {
+            return Ok(this.clone());
+        }
+
+        let mut chars = global.java_chars(this)?.clone();
+        chars.extend_from_slice(other_chars);
+
+        Ok(Primitive::Reference(env.global_mut().new_java_chars(chars)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
 }
 
-async_box!(async_string_set_internal, string_set_internal);
-async fn string_set_internal(env: &mut NativeEnv) -> Primitive {
-    let variables = &env.variables().clone();
+async_box!(async_string_starts_with, string_starts_with);
+async fn string_starts_with(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let (this, prefix, offset) = match &env.variables()[..] {
+        [this, prefix] => (this, prefix, 0),
+        [this, prefix, Primitive::Int(offset), ..] => (this, prefix, *offset),
+        _ => return Err(env.invalid_arguments()),
+    };
 
-    if let [this, raw, ..] = &variables[..] {
-        let raw = env.reference(raw).unwrap();
-
-        let mut raw_array: Vec<u8> = Vec::new();
-        if let RustObject::Array(raw) = raw.inner() {
-            for value in raw.iter() {
-                if let Primitive::Byte(r#char) = value {
-                    raw_array.push(*r#char);
-                } else {
-                    break;
-                }
+    let global = env.global();
+    let chars = global.java_chars(this)?;
+    let prefix = global.java_chars(prefix)?;
+
+    if offset < 0 || offset as usize + prefix.len() > chars.len() {
+        return Ok(Primitive::Boolean(false));
+    }
+
+    Ok(Primitive::Boolean(chars[offset as usize..].starts_with(prefix)))
+}
+
+/// Maps the case of the valid runs in `chars`, copying unpaired surrogates through unchanged.
+fn map_case(chars: &[u16], upper: bool) -> Vec<u16> {
+    let mut mapped = Vec::with_capacity(chars.len());
+    let mut run = String::new();
+    let mut flush = |run: &mut String, mapped: &mut Vec<u16>| {
+        let text = if upper { run.to_uppercase() } else { run.to_lowercase() };
+        mapped.extend(text.encode_utf16());
+        run.clear();
+    };
+
+    for decoded in char::decode_utf16(chars.iter().copied()) {
+        match decoded {
+            Ok(c) => run.push(c),
+            Err(error) => {
+                flush(&mut run, &mut mapped);
+                mapped.push(error.unpaired_surrogate());
             }
+        }
+    }
+    flush(&mut run, &mut mapped);
+
+    mapped
+}
+
+fn string_map_case(env: &mut NativeEnv, upper: bool) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, ..] = &variables[..] {
+        let chars = env.global().java_chars(this)?;
+        let mapped = map_case(chars, upper);
+
+        if &mapped == chars {
+            Ok(this.clone())
         } else {
-            todo!()
+            Ok(Primitive::Reference(env.global_mut().new_java_chars(mapped)?))
         }
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
 
-        let this = env.reference_mut(this).unwrap();
-        *this.inner_mut() = RustObject::String(String::from_utf8(raw_array).unwrap());
-    };
+async_box!(async_string_to_upper_case, string_to_upper_case);
+async fn string_to_upper_case(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    string_map_case(env, true)
+}
 
-    Primitive::Void
+async_box!(async_string_to_lower_case, string_to_lower_case);
+async fn string_to_lower_case(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    string_map_case(env, false)
 }
 
-async_box!(async_object_get_class, object_get_class);
-async fn object_get_class(env: &mut NativeEnv) -> Primitive {
-    let variables = &env.variables().clone();
+#[native("java/lang/Object", "getClass", descriptor = "()Ljava/lang/Class;")]
+fn object_get_class(env: &mut NativeEnv, this: JObject) -> Result<JObject, WasmJVMError> {
+    let class = env
+        .reference(&this.0)?
+        .class()
+        .ok_or_else(|| WasmJVMError::VerifyError(format!("Object has no class {:?}", this.0)))?;
+
+    Ok(JObject(Primitive::Reference(class)))
+}
+
+async_box!(async_object_hash_code, object_hash_code);
+async fn object_hash_code(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [this] = &env.variables()[..] {
+        Ok(Primitive::Int(env.global().identity_hash_code(this)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_object_clone, object_clone);
+async fn object_clone(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
 
     if let [this] = &variables[..] {
-        let object = env.reference(this).unwrap();
-        Primitive::Reference(object.class().unwrap())
+        Ok(Primitive::Reference(env.global_mut().clone_object(this)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_system_identity_hash_code, system_identity_hash_code);
+async fn system_identity_hash_code(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [object] = &env.variables()[..] {
+        Ok(Primitive::Int(env.global().identity_hash_code(object)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_system_arraycopy, system_arraycopy);
+async fn system_arraycopy(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [source, Primitive::Int(source_position), destination, Primitive::Int(destination_position), Primitive::Int(length)] =
+        &variables[..]
+    {
+        env.global_mut().array_copy(
+            source,
+            *source_position,
+            destination,
+            *destination_position,
+            *length,
+        )?;
+
+        Ok(Primitive::Void)
     } else {
-        todo!()
+        Err(env.invalid_arguments())
     }
 }
 
 async_box!(async_class_get_name, class_get_name);
-async fn class_get_name(env: &mut NativeEnv) -> Primitive {
+async fn class_get_name(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
     let variables = &env.variables().clone();
 
     if let [this] = &variables[..] {
         if let Primitive::Reference(this) = this {
-            let class = env.global().class(*this).unwrap();
-            let class_name = class.metadata().this_class().to_string();
+            let class = env.global().class(*this)?;
+            let class_name = class.metadata().this_class().replace('/', ".");
 
-            Primitive::Reference(env.new_string(class_name).unwrap())
+            Ok(Primitive::Reference(env.new_string(class_name)?))
         } else {
-            todo!()
+            return Err(env.invalid_arguments())
         }
     } else {
-        todo!()
+        Err(env.invalid_arguments())
     }
 }
+
+async_box!(async_throwable_fill_in_stack_trace, throwable_fill_in_stack_trace);
+async fn throwable_fill_in_stack_trace(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = &env.variables().clone();
+
+    if let [this] = &variables[..] {
+        let entries = env.global().current_thread()?.backtrace(this)?;
+
+        let this_object = env.reference_mut(this)?;
+        *this_object.inner_mut() = RustObject::Backtrace(entries);
+
+        Ok(this.clone())
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_throwable_get_stack_trace, throwable_get_stack_trace);
+async fn throwable_get_stack_trace(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = &env.variables().clone();
+
+    let entries = if let [this] = &variables[..] {
+        if let RustObject::Backtrace(entries) = env.reference(this)?.inner() {
+            entries.clone()
+        } else {
+            Vec::new()
+        }
+    } else {
+        return Err(env.invalid_arguments())
+    };
+
+    let mut elements = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let declaring_class = env.new_string(entry.class.replace('/', "."))?;
+        let method_name = env.new_string(entry.method.clone())?;
+        let file_name = match &entry.file {
+            Some(file) => Primitive::Reference(env.new_string(file.clone())?),
+            None => Primitive::Null,
+        };
+
+        let element = env
+            .global_mut()
+            .new_instance(JAVA_STACK_TRACE_ELEMENT)?;
+        let element_object = Primitive::Reference(element);
+
+        env.set_field(&element_object, "declaringClass", Primitive::Reference(declaring_class))?;
+        env.set_field(&element_object, "methodName", Primitive::Reference(method_name))?;
+        env.set_field(&element_object, "fileName", file_name)?;
+        env.set_field(&element_object, "lineNumber", Primitive::Int(entry.line))?;
+
+        elements.push(Some(element));
+    }
+
+    let component = Type::from_class_name(JAVA_STACK_TRACE_ELEMENT)?;
+
+    Ok(Primitive::Reference(
+        env.global_mut()
+            .new_array_from(&component, Array::Reference(elements))?,
+    ))
+}
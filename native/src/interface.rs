@@ -1,31 +1,86 @@
-use std::{collections::HashMap, fmt::Debug, sync::Arc, future::Future, pin::Pin};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
 
-use crate::{Global, Object, Primitive};
+use crate::{
+    CallOutcome, Global, Object, Primitive, RegisterFn, RustObject, Thread, ThreadResult, JAVA_THREAD,
+};
 use wasmjvm_class::MethodRef;
 use wasmjvm_common::WasmJVMError;
 
-pub type NativeFn = Box<dyn for<'a> Fn(&'a mut NativeEnv) -> Pin<Box<dyn Future<Output = Primitive> + 'a>>>;
+pub type NativeFn = Box<dyn for<'a> Fn(&'a mut NativeEnv) -> Pin<Box<dyn Future<Output = Result<Primitive, WasmJVMError>> + 'a>>>;
+
+/// The library every front end links the VM's own natives under.
+pub const JAVA_LIBRARY: &str = "java";
 
 #[derive(Clone)]
 pub struct NativeMethod {
     raw: Arc<NativeFn>,
+    library: String,
+    priority: i32,
 }
 
 impl NativeMethod {
-    fn new(r#fn: NativeFn) -> Self {
+    fn new(r#fn: NativeFn, library: String, priority: i32) -> Self {
         Self {
             raw: Arc::new(r#fn),
+            library,
+            priority,
         }
     }
 
-    pub async fn invoke(self: &Self, env: &mut NativeEnv) -> Primitive {
+    pub fn library(self: &Self) -> &str {
+        &self.library
+    }
+
+    pub async fn invoke(self: &Self, env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
         (self.raw)(env).await
     }
 }
 
+/// A named set of natives. When two linked libraries implement the same method the one with the
+/// higher priority wins, and on a tie the one linked last does.
+pub struct NativeLibrary {
+    name: String,
+    priority: i32,
+    register: RegisterFn,
+    on_boot: bool,
+}
+
+impl NativeLibrary {
+    pub const DEFAULT_PRIORITY: i32 = 0;
+
+    /// A library that is linked when the VM boots.
+    pub fn new(name: &str, priority: i32, register: RegisterFn) -> Self {
+        Self {
+            name: name.to_string(),
+            priority,
+            register,
+            on_boot: true,
+        }
+    }
+
+    /// Leaves the library unlinked until the program calls `System.loadLibrary` with its name.
+    pub fn on_demand(self: Self) -> Self {
+        Self { on_boot: false, ..self }
+    }
+
+    pub fn name(self: &Self) -> &str {
+        &self.name
+    }
+}
+
 #[derive(Default)]
 pub struct NativeInterface {
     methods: HashMap<MethodRef, NativeMethod>,
+    libraries: Vec<NativeLibrary>,
+    linked: HashSet<String>,
+    /// The library whose natives `register` is adding, and its priority.
+    linking: Option<(String, i32)>,
 }
 
 impl Debug for NativeInterface {
@@ -36,25 +91,91 @@ impl Debug for NativeInterface {
 
 impl NativeInterface {
     pub fn new() -> Self {
-        Self {
-            methods: HashMap::new(),
-        }
+        Self::default()
     }
 
+    /// Registers a native for the library being linked. Natives registered outside of a library
+    /// belong to `JAVA_LIBRARY` at the default priority.
     pub fn register(
         self: &mut Self,
         method_ref: MethodRef,
         r#fn: NativeFn,
     ) -> Result<(), WasmJVMError> {
-        if self.methods.contains_key(&method_ref) {
-            return Err(WasmJVMError::TODO(24));
+        let (library, priority) = self
+            .linking
+            .clone()
+            .unwrap_or_else(|| (JAVA_LIBRARY.to_string(), NativeLibrary::DEFAULT_PRIORITY));
+
+        if let Some(existing) = self.methods.get(&method_ref) {
+            if existing.priority > priority {
+                return Ok(());
+            }
+        }
+
+        self.methods.insert(method_ref, NativeMethod::new(r#fn, library, priority));
+
+        Ok(())
+    }
+
+    pub fn add_library(self: &mut Self, library: NativeLibrary) -> Result<(), WasmJVMError> {
+        if self.libraries.iter().any(|existing| existing.name == library.name) {
+            return Err(WasmJVMError::LinkageError(format!("Native library {} is already registered", library.name)));
         }
 
-        self.methods.insert(method_ref, NativeMethod::new(r#fn));
+        self.libraries.push(library);
 
         Ok(())
     }
 
+    /// Links the libraries that load on boot, in the order they were added.
+    pub fn link_boot_libraries(self: &mut Self) -> Result<(), WasmJVMError> {
+        let names: Vec<String> = self
+            .libraries
+            .iter()
+            .filter(|library| library.on_boot)
+            .map(|library| library.name.clone())
+            .collect();
+
+        for name in names {
+            self.link(&name)?;
+        }
+
+        Ok(())
+    }
+
+    /// Links the library called `name` along with its `#[native]` functions. Returns false when
+    /// no such library was added.
+    pub fn link(self: &mut Self, name: &str) -> Result<bool, WasmJVMError> {
+        if self.linked.contains(name) {
+            return Ok(true);
+        }
+
+        let index = match self.libraries.iter().position(|library| library.name == name) {
+            Some(index) => index,
+            None => return Ok(false),
+        };
+
+        let library = self.libraries.remove(index);
+        self.linking = Some((library.name.clone(), library.priority));
+
+        (library.register)(self);
+        let declared = self.register_declared(&library.name);
+
+        self.linking = None;
+        self.linked.insert(library.name.clone());
+        self.libraries.insert(index, library);
+
+        declared.map(|_| true)
+    }
+
+    pub fn is_linked(self: &Self, name: &str) -> bool {
+        self.linked.contains(name)
+    }
+
+    pub fn contains(self: &Self, method_ref: &MethodRef) -> bool {
+        self.methods.contains_key(method_ref)
+    }
+
     pub fn method(self: &Self, method_ref: &MethodRef) -> Result<NativeMethod, WasmJVMError> {
         if let Some(method) = self.methods.get(method_ref) {
             Ok(method.clone())
@@ -64,21 +185,156 @@ impl NativeInterface {
     }
 }
 
+/// What the calling thread does after a native returns, instead of pushing its result.
+#[derive(Debug)]
+pub enum NativeContinuation {
+    /// Replace the native frame with a call, optionally boxing the call's result.
+    Invoke {
+        method_ref: MethodRef,
+        this: Option<Primitive>,
+        arguments: Vec<Primitive>,
+        boxed: bool,
+    },
+    /// Run the frames, then call the native again.
+    Retry(Vec<MethodRef>),
+    /// Throw the throwable from the native frame, like `athrow`.
+    Throw(Primitive),
+}
+
 #[derive(Debug)]
 pub struct NativeEnv {
     global: Global,
     variables: Vec<Primitive>,
+    continuation: Option<NativeContinuation>,
 }
 
 impl NativeEnv {
     pub fn new(global: Global, variables: Vec<Primitive>) -> Self {
-        Self { global, variables }
+        Self {
+            global,
+            variables,
+            continuation: None,
+        }
+    }
+
+    pub fn continuation(self: &mut Self) -> Option<NativeContinuation> {
+        self.continuation.take()
+    }
+
+    pub fn invoke(
+        self: &mut Self,
+        method_ref: MethodRef,
+        this: Option<Primitive>,
+        arguments: Vec<Primitive>,
+        boxed: bool,
+    ) {
+        self.continuation = Some(NativeContinuation::Invoke {
+            method_ref,
+            this,
+            arguments,
+            boxed,
+        });
+    }
+
+    /// Calls `method_ref` and runs it to the end before returning, unlike `invoke`, so the native
+    /// can use the result. With `this` the call is virtual. It runs on a thread of its own next to
+    /// the calling one, and only the loader's initialization threads get to run meanwhile.
+    pub async fn call(
+        self: &mut Self,
+        method_ref: MethodRef,
+        this: Option<Primitive>,
+        arguments: Vec<Primitive>,
+    ) -> Result<CallOutcome, WasmJVMError> {
+        let parent = self.global.current_thread_index()?;
+        self.global.check_call(&method_ref, this.as_ref(), &arguments)?;
+        let mut thread = Thread::new_call(self.global.thread(parent)?);
+
+        match this {
+            Some(this) => thread.invoke_virtual(method_ref, this, arguments)?,
+            None => thread.invoke_static(method_ref, arguments)?,
+        }
+
+        let thread = self
+            .global
+            .new_rust_instance(JAVA_THREAD, RustObject::Thread(thread))?;
+
+        let outcome = self.drive(parent, thread).await;
+        self.global.remove_thread(thread)?;
+        self.global.set_current_thread(parent)?;
+
+        outcome
+    }
+
+    /// Ticks the call's thread, and the loader's threads while they hold the others back.
+    async fn drive(self: &mut Self, parent: usize, thread: usize) -> Result<CallOutcome, WasmJVMError> {
+        let (clinit_thread, init_thread) = self.global.loader()?.threads();
+
+        loop {
+            for helper in [clinit_thread, init_thread] {
+                if helper != parent && self.global.thread_priority()? > 0 {
+                    self.global.thread_tick(helper).await?;
+                }
+            }
+
+            let result = self.global.thread_tick(thread).await?;
+
+            if let Some(status) = self.global.exit_status()? {
+                return Ok(CallOutcome::Exit(status));
+            }
+
+            match result {
+                ThreadResult::Continue => {}
+                ThreadResult::Stop => return Ok(CallOutcome::Return(Primitive::Void)),
+                ThreadResult::Result(value) => return Ok(CallOutcome::Return(value)),
+                ThreadResult::Uncaught(throwable) => return Ok(CallOutcome::Throw(throwable)),
+            }
+        }
+    }
+
+    /// Returns false when the class still has to be initialized, in which case
+    /// the native must return and will be called again afterwards.
+    pub fn initialize(self: &mut Self, class_index: usize) -> Result<bool, WasmJVMError> {
+        let thread_id = self.global.current_thread()?.id();
+
+        match self.global.initialize_class(class_index, thread_id)? {
+            Some(frames) => {
+                self.continuation = Some(NativeContinuation::Retry(frames));
+                Ok(false)
+            }
+            None => Ok(true),
+        }
+    }
+
+    /// Throws `throwable` once the native returns; whatever the native returns is ignored.
+    pub fn throw(self: &mut Self, throwable: Primitive) -> Result<Primitive, WasmJVMError> {
+        self.continuation = Some(NativeContinuation::Throw(throwable));
+
+        Ok(Primitive::Void)
+    }
+
+    /// Creates a `class` throwable with `message` and a backtrace of the calling thread, then
+    /// throws it like `throw`. Meant to be returned from the native: `return env.throw_new(..)`.
+    pub fn throw_new(self: &mut Self, class: &str, message: &str) -> Result<Primitive, WasmJVMError> {
+        let backtrace = self.global.current_thread()?.stack_trace_entries()?;
+        let throwable = self
+            .global
+            .new_throwable(class, Some(message.to_string()), backtrace)?;
+
+        self.throw(Primitive::Reference(throwable))
+    }
+
+    pub fn invalid_arguments(self: &Self) -> WasmJVMError {
+        WasmJVMError::InternalError(format!("Invalid native arguments {:?}", self.variables))
     }
 
     pub fn global(self: &Self) -> &Global {
         &self.global
     }
 
+    pub fn global_mut(self: &mut Self) -> &mut Global {
+        &mut self.global
+    }
+
     pub fn variables(self: &Self) -> &Vec<Primitive> {
         &self.variables
     }
@@ -87,10 +343,27 @@ impl NativeEnv {
         &mut self.variables
     }
 
+    pub fn field(self: &Self, reference: &Primitive, name: &str) -> Result<Primitive, WasmJVMError> {
+        self.global.field_named(reference, name)
+    }
+
+    pub fn set_field(
+        self: &mut Self,
+        reference: &Primitive,
+        name: &str,
+        value: Primitive,
+    ) -> Result<(), WasmJVMError> {
+        self.global.field_named_set(reference, name, value)
+    }
+
     pub fn new_string(self: &mut Self, string: String) -> Result<usize, WasmJVMError> {
         self.global.new_java_string(string)
     }
 
+    pub fn string(self: &Self, reference: &Primitive) -> Result<Option<String>, WasmJVMError> {
+        self.global.java_string(reference)
+    }
+
     pub fn alloc(self: &mut Self, object: Object) -> Result<usize, WasmJVMError> {
         self.global.new_object(object)
     }
@@ -0,0 +1,415 @@
+//! Natives that the JDK's own `java.base` classes call when they run next to the boot jar.
+
+use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
+use wasmjvm_common::WasmJVMError;
+
+use crate::{
+    async_box, register_method, Array, NativeEnv, NativeInterface, Primitive, RustObject, JAVA_CLASS,
+    JAVA_OBJECT, JAVA_STRING,
+};
+
+const JDK_UNSAFE: &str = "jdk/internal/misc/Unsafe";
+const JDK_VM: &str = "jdk/internal/misc/VM";
+const JDK_CDS: &str = "jdk/internal/misc/CDS";
+const JDK_REFLECTION: &str = "jdk/internal/reflect/Reflection";
+const JAVA_CLASS_LOADER: &str = "java/lang/ClassLoader";
+
+/// `Unsafe` offsets are field slots for objects and element indices for arrays, so arrays
+/// start at zero and their elements are one apart.
+const ARRAY_BASE_OFFSET: i32 = 0;
+const ARRAY_INDEX_SCALE: i32 = 1;
+
+/// The value types `Unsafe` has a `get`, `put` and volatile pair for.
+const UNSAFE_KINDS: [&str; 9] = ["Int", "Long", "Reference", "Boolean", "Byte", "Short", "Char", "Float", "Double"];
+
+fn object() -> Type {
+    Type::Single(SingleType::Object(JAVA_OBJECT.to_string()))
+}
+
+fn class() -> Type {
+    Type::Single(SingleType::Object(JAVA_CLASS.to_string()))
+}
+
+fn class_loader() -> Type {
+    Type::Single(SingleType::Object(JAVA_CLASS_LOADER.to_string()))
+}
+
+fn string() -> Type {
+    Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+}
+
+fn single(r#type: SingleType) -> Type {
+    Type::Single(r#type)
+}
+
+fn unsafe_kind(kind: &str) -> Type {
+    match kind {
+        "Int" => single(SingleType::Int),
+        "Long" => single(SingleType::Long),
+        "Boolean" => single(SingleType::Boolean),
+        "Byte" => single(SingleType::Byte),
+        "Short" => single(SingleType::Short),
+        "Char" => single(SingleType::Char),
+        "Float" => single(SingleType::Float),
+        "Double" => single(SingleType::Double),
+        _ => object(),
+    }
+}
+
+pub fn register(interface: &mut NativeInterface) {
+    let void = || single(SingleType::Void);
+    let boolean = || single(SingleType::Boolean);
+    let int = || single(SingleType::Int);
+    let long = || single(SingleType::Long);
+
+    register_method!(interface, async_no_op, JDK_CDS, "logLambdaFormInvoker", vec![string()], void());
+    register_method!(interface, async_no_op, JDK_CDS, "initializeFromArchive", vec![class()], void());
+    register_method!(
+        interface,
+        async_no_op,
+        JDK_CDS,
+        "defineArchivedModules",
+        vec![class_loader(), class_loader()],
+        void()
+    );
+    register_method!(interface, async_no_op, JDK_CDS, "dumpClassList", vec![string()], void());
+    register_method!(interface, async_no_op, JDK_CDS, "dumpDynamicArchive", vec![string()], void());
+    register_method!(interface, async_false, JDK_CDS, "isDumpingClassList0", vec![], boolean());
+    register_method!(interface, async_false, JDK_CDS, "isDumpingArchive0", vec![], boolean());
+    register_method!(interface, async_false, JDK_CDS, "isSharingEnabled0", vec![], boolean());
+    register_method!(interface, async_zero, JDK_CDS, "getRandomSeedForDumping", vec![], long());
+
+    register_method!(interface, async_no_op, JDK_VM, "initialize", vec![], void());
+    register_method!(interface, async_null, JDK_VM, "latestUserDefinedLoader0", vec![], class_loader());
+    register_method!(interface, async_zero, JDK_VM, "getuid", vec![], long());
+    register_method!(interface, async_zero, JDK_VM, "geteuid", vec![], long());
+    register_method!(interface, async_zero, JDK_VM, "getgid", vec![], long());
+    register_method!(interface, async_zero, JDK_VM, "getegid", vec![], long());
+    register_method!(
+        interface,
+        async_vm_get_runtime_arguments,
+        JDK_VM,
+        "getRuntimeArguments",
+        vec![],
+        Type::Array(SingleType::Object(JAVA_STRING.to_string()), 1)
+    );
+
+    register_method!(
+        interface,
+        async_reflection_get_caller_class,
+        JDK_REFLECTION,
+        "getCallerClass",
+        vec![],
+        class()
+    );
+    register_method!(
+        interface,
+        async_reflection_get_class_access_flags,
+        JDK_REFLECTION,
+        "getClassAccessFlags",
+        vec![class()],
+        int()
+    );
+    register_method!(
+        interface,
+        async_reflection_are_nest_mates,
+        JDK_REFLECTION,
+        "areNestMates",
+        vec![class(), class()],
+        boolean()
+    );
+
+    register_method!(interface, async_no_op, JDK_UNSAFE, "registerNatives", vec![], void());
+    register_method!(interface, async_no_op, JDK_UNSAFE, "loadFence", vec![], void());
+    register_method!(interface, async_no_op, JDK_UNSAFE, "storeFence", vec![], void());
+    register_method!(interface, async_no_op, JDK_UNSAFE, "fullFence", vec![], void());
+    register_method!(
+        interface,
+        async_unsafe_array_base_offset,
+        JDK_UNSAFE,
+        "arrayBaseOffset0",
+        vec![class()],
+        int()
+    );
+    register_method!(
+        interface,
+        async_unsafe_array_index_scale,
+        JDK_UNSAFE,
+        "arrayIndexScale0",
+        vec![class()],
+        int()
+    );
+    register_method!(
+        interface,
+        async_unsafe_object_field_offset,
+        JDK_UNSAFE,
+        "objectFieldOffset1",
+        vec![class(), string()],
+        long()
+    );
+    register_method!(
+        interface,
+        async_unsafe_should_be_initialized,
+        JDK_UNSAFE,
+        "shouldBeInitialized0",
+        vec![class()],
+        boolean()
+    );
+    register_method!(
+        interface,
+        async_unsafe_ensure_class_initialized,
+        JDK_UNSAFE,
+        "ensureClassInitialized0",
+        vec![class()],
+        void()
+    );
+
+    for kind in UNSAFE_KINDS {
+        for suffix in ["", "Volatile"] {
+            let get = format!("get{}{}", kind, suffix);
+            let put = format!("put{}{}", kind, suffix);
+
+            register_method!(interface, async_unsafe_get, JDK_UNSAFE, get, vec![object(), long()], unsafe_kind(kind));
+            register_method!(
+                interface,
+                async_unsafe_put,
+                JDK_UNSAFE,
+                put,
+                vec![object(), long(), unsafe_kind(kind)],
+                void()
+            );
+        }
+    }
+
+    for kind in ["Int", "Long", "Reference"] {
+        let set = format!("compareAndSet{}", kind);
+        let exchange = format!("compareAndExchange{}", kind);
+
+        register_method!(
+            interface,
+            async_unsafe_compare_and_set,
+            JDK_UNSAFE,
+            set,
+            vec![object(), long(), unsafe_kind(kind), unsafe_kind(kind)],
+            boolean()
+        );
+        register_method!(
+            interface,
+            async_unsafe_compare_and_exchange,
+            JDK_UNSAFE,
+            exchange,
+            vec![object(), long(), unsafe_kind(kind), unsafe_kind(kind)],
+            unsafe_kind(kind)
+        );
+    }
+}
+
+async_box!(async_no_op, no_op);
+async fn no_op(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Void)
+}
+
+async_box!(async_false, always_false);
+async fn always_false(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Boolean(false))
+}
+
+async_box!(async_zero, zero);
+async fn zero(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Long(0))
+}
+
+async_box!(async_null, null);
+async fn null(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Null)
+}
+
+async_box!(async_vm_get_runtime_arguments, vm_get_runtime_arguments);
+async fn vm_get_runtime_arguments(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let component = Type::Single(SingleType::Object(JAVA_STRING.to_string()));
+
+    Ok(Primitive::Reference(env.global_mut().new_array_from(&component, Array::Reference(Vec::new()))?))
+}
+
+async_box!(async_reflection_get_caller_class, reflection_get_caller_class);
+/// The class of the method that called the `@CallerSensitive` method which asked.
+async fn reflection_get_caller_class(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let caller = {
+        let frames = env.global().current_thread()?.frames();
+
+        frames
+            .len()
+            .checked_sub(3)
+            .map(|index| frames[index].method_ref().class.clone())
+    };
+
+    match caller {
+        Some(caller) => Ok(Primitive::Reference(env.global().class_index(&caller)?)),
+        None => Ok(Primitive::Null),
+    }
+}
+
+async_box!(async_reflection_get_class_access_flags, reflection_get_class_access_flags);
+async fn reflection_get_class_access_flags(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index)] = env.variables()[..] {
+        let metadata = env.global().class(class_index)?.metadata();
+
+        Ok(Primitive::Int(metadata.access_flags().bits() as i32))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_reflection_are_nest_mates, reflection_are_nest_mates);
+/// Nest attributes aren't tracked, so only a class and itself or its own inner classes count.
+async fn reflection_are_nest_mates(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(first), Primitive::Reference(second)] = env.variables()[..] {
+        let host = |name: &str| name.split('$').next().unwrap_or(name).to_string();
+        let first = host(env.global().class(first)?.metadata().this_class());
+        let second = host(env.global().class(second)?.metadata().this_class());
+
+        Ok(Primitive::Boolean(first == second))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_unsafe_array_base_offset, unsafe_array_base_offset);
+async fn unsafe_array_base_offset(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Int(ARRAY_BASE_OFFSET))
+}
+
+async_box!(async_unsafe_array_index_scale, unsafe_array_index_scale);
+async fn unsafe_array_index_scale(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Int(ARRAY_INDEX_SCALE))
+}
+
+async_box!(async_unsafe_object_field_offset, unsafe_object_field_offset);
+async fn unsafe_object_field_offset(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [_, Primitive::Reference(class_index), name] = &env.variables()[..] {
+        let name = env
+            .global()
+            .java_string(name)?
+            .ok_or_else(|| WasmJVMError::NullPointerException(format!("name")))?;
+        let class = env.global().class(*class_index)?;
+        let class_name = class.metadata().this_class();
+
+        let slot = class
+            .layout()
+            .iter()
+            .position(|slot| &slot.class == class_name && slot.name == name)
+            .ok_or_else(|| WasmJVMError::InternalError(format!("No field {}.{}", class_name, name)))?;
+
+        Ok(Primitive::Long(slot as i64))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_unsafe_should_be_initialized, unsafe_should_be_initialized);
+async fn unsafe_should_be_initialized(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [_, Primitive::Reference(class_index)] = env.variables()[..] {
+        let state = env.global().class(class_index)?.state();
+
+        Ok(Primitive::Boolean(state != crate::ClassState::Initialized))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_unsafe_ensure_class_initialized, unsafe_ensure_class_initialized);
+async fn unsafe_ensure_class_initialized(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [_, Primitive::Reference(class_index)] = env.variables()[..] {
+        env.initialize(class_index)?;
+
+        Ok(Primitive::Void)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+fn unsafe_read(env: &NativeEnv, target: &Primitive, offset: i64) -> Result<Primitive, WasmJVMError> {
+    match env.global().reference_p(target)?.inner() {
+        RustObject::Array(array) => array.get(offset as i32),
+        _ => env.global().reference_p(target)?.field(offset as usize),
+    }
+}
+
+fn unsafe_write(env: &mut NativeEnv, target: &Primitive, offset: i64, value: Primitive) -> Result<(), WasmJVMError> {
+    let object = env.global_mut().reference_p_mut(target)?;
+
+    match object.inner_mut() {
+        RustObject::Array(array) => array.set(offset as i32, value),
+        _ => object.set_field(offset as usize, value),
+    }
+}
+
+/// Whether a slot holds `expected`, comparing references by identity and floating point
+/// values by their bits like the JDK does.
+fn unsafe_equals(current: &Primitive, expected: &Primitive) -> bool {
+    match (current, expected) {
+        (Primitive::Null, Primitive::Null) => true,
+        (Primitive::Reference(current), Primitive::Reference(expected)) => current == expected,
+        (Primitive::Float(current), Primitive::Float(expected)) => current.to_bits() == expected.to_bits(),
+        (Primitive::Double(current), Primitive::Double(expected)) => current.to_bits() == expected.to_bits(),
+        (Primitive::Null, _) | (_, Primitive::Null) => false,
+        (current, expected) => match (current.as_long(), expected.as_long()) {
+            (Ok(current), Ok(expected)) => current == expected,
+            _ => false,
+        },
+    }
+}
+
+async_box!(async_unsafe_get, unsafe_get);
+async fn unsafe_get(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [_, target, Primitive::Long(offset)] = &env.variables()[..] {
+        unsafe_read(env, target, *offset)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_unsafe_put, unsafe_put);
+async fn unsafe_put(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [_, target, Primitive::Long(offset), value] = &env.variables()[..] {
+        let (target, offset, value) = (target.clone(), *offset, value.clone());
+        unsafe_write(env, &target, offset, value)?;
+
+        Ok(Primitive::Void)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_unsafe_compare_and_set, unsafe_compare_and_set);
+async fn unsafe_compare_and_set(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [_, target, Primitive::Long(offset), expected, value] = &env.variables()[..] {
+        let (target, offset, expected, value) = (target.clone(), *offset, expected.clone(), value.clone());
+
+        if unsafe_equals(&unsafe_read(env, &target, offset)?, &expected) {
+            unsafe_write(env, &target, offset, value)?;
+
+            Ok(Primitive::Boolean(true))
+        } else {
+            Ok(Primitive::Boolean(false))
+        }
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_unsafe_compare_and_exchange, unsafe_compare_and_exchange);
+async fn unsafe_compare_and_exchange(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [_, target, Primitive::Long(offset), expected, value] = &env.variables()[..] {
+        let (target, offset, expected, value) = (target.clone(), *offset, expected.clone(), value.clone());
+        let current = unsafe_read(env, &target, offset)?;
+
+        if unsafe_equals(&current, &expected) {
+            unsafe_write(env, &target, offset, value)?;
+        }
+
+        Ok(current)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
@@ -1,17 +1,36 @@
 pub mod base;
 pub mod class;
+mod fdlibm;
+pub mod image;
 pub mod implementation;
 pub mod interface;
+mod jdk;
 pub mod loader;
+mod math;
+mod number;
 pub mod object;
 pub mod opcode;
+mod reference;
+mod reflection;
+mod system;
 pub mod thread;
+mod typed;
 
 pub use base::*;
 pub use class::*;
+pub use image::*;
 pub use implementation::*;
 pub use interface::*;
 pub use loader::*;
 pub use object::*;
 pub use opcode::*;
+pub use system::DEFAULT_PROPERTIES;
 pub use thread::*;
+pub use typed::*;
+
+// `#[native]` expands to paths under `::wasmjvm_native`, which have to resolve in this crate too.
+extern crate self as wasmjvm_native;
+
+pub use inventory;
+pub use wasmjvm_class;
+pub use wasmjvm_macros::native;
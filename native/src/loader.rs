@@ -1,12 +1,13 @@
-use wasmjvm_class::{Class, WithFields};
+use wasmjvm_class::{Class, WithInterfaces};
 use wasmjvm_common::WasmJVMError;
 
 use crate::{
-    ClassInstance, Global, Object, RustObject, Thread, JAVA_CLASS, JAVA_LOADER, JAVA_OBJECT,
+    ClassInstance, ClassState, Global, Object, RustObject, Thread, JAVA_CLASS, JAVA_LOADER, JAVA_OBJECT,
     JAVA_THREAD,
 };
 
-trait Resource {
+/// Somewhere the loader can find class files, like a jar or a JDK runtime image.
+pub trait Resource {
     fn load_class(self: &mut Self, name: &str) -> Result<Class, WasmJVMError>;
 }
 
@@ -15,11 +16,12 @@ pub struct Jar<F: std::io::Read + std::io::Seek> {
 }
 
 impl<F> Jar<F> where F: std::io::Read + std::io::Seek {
-    pub fn new(reader: F) -> Self {
-        Self {
-            zip_file: zip::ZipArchive::new(reader).unwrap()
+    pub fn new(reader: F) -> Result<Self, WasmJVMError> {
+        match zip::ZipArchive::new(reader) {
+            Ok(zip_file) => Ok(Self { zip_file }),
+            Err(error) => Err(WasmJVMError::ClassFormatError(format!("Invalid jar: {}", error))),
         }
-    } 
+    }
 }
 
 impl<F> Resource for Jar<F> where F: std::io::Read + std::io::Seek {
@@ -59,15 +61,6 @@ impl Loader {
         }
     }
 
-    pub fn clinit(self: &mut Self, class: usize) -> Result<(), WasmJVMError> {
-        self.global.thread_lock(2)?;
-
-        let thread = self.global.thread_mut(self.clinit_thread)?;
-        thread.new_clinit_frame(class)?;
-
-        Ok(())
-    }
-
     pub fn default_init(self: &mut Self, class: usize, this: usize) -> Result<(), WasmJVMError> {
         self.global.thread_lock(1)?;
 
@@ -81,16 +74,40 @@ impl Loader {
         let class_index = self.global.class_index(JAVA_CLASS)?;
 
         let inner = RustObject::Class(ClassInstance::new(metadata));
-        let class = Object::new(class_index, self.global.resolve_fields(class_index)?, inner)?;
+        let class = Object::new(class_index, self.global.instance_fields(class_index)?, inner)?;
 
         let object_index = self.global.new_object(class)?;
 
-        self.clinit(object_index)?;
         self.default_init(class_index, object_index)?;
 
+        self.load_super_classes(object_index)?;
+        self.global.link_class(object_index)?;
+
         Ok(object_index)
     }
 
+    fn load_super_classes(self: &mut Self, class: usize) -> Result<(), WasmJVMError> {
+        let metadata = self.global.class(class)?.metadata();
+
+        let mut names = Vec::new();
+        if let Some(super_class) = metadata.super_class() {
+            names.push(super_class.clone());
+        }
+        if let Some(interfaces) = metadata.interfaces() {
+            for interface in interfaces {
+                names.push(interface.name().to_string());
+            }
+        }
+
+        for name in names {
+            if self.global.class_index(&name).is_err() {
+                self.load_class_name(&name)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn load_main_class(self: &mut Self) -> Result<usize, WasmJVMError> {
         // TODO: Look at Jar for actual main class.
         let main_class = "Main";
@@ -119,7 +136,12 @@ impl Loader {
         self: &mut Self,
         jar: Jar<F>,
     ) -> Result<(), WasmJVMError> {
-        self.resources.push(Box::new(jar));
+        self.load_resource(jar)
+    }
+
+    /// Resources are searched in the order they were loaded, so earlier ones shadow later ones.
+    pub fn load_resource<R: 'static + Resource>(self: &mut Self, resource: R) -> Result<(), WasmJVMError> {
+        self.resources.push(Box::new(resource));
 
         Ok(())
     }
@@ -132,23 +154,21 @@ impl Loader {
         }
     }
 
+    /// Loads the boot classes. The main class is loaded when the VM runs, so a VM that is only
+    /// called into doesn't need one.
     pub fn boot(self: &mut Self) -> Result<(), WasmJVMError> {
-        self.load_boot_classes()?;
-        self.load_main_class()?;
-
-        Ok(())
+        self.load_boot_classes()
     }
 
     fn load_boot_classes(self: &mut Self) -> Result<(), WasmJVMError> {
         let object_class = self.extract_boot_class(JAVA_OBJECT)?;
-        let object_fields = object_class.field_names();
 
         let mut clinits = Vec::new();
         let mut inits = Vec::new();
 
         let object = Object::new(
             self.global.index()?,
-            object_fields.clone(),
+            Vec::new(),
             RustObject::Class(ClassInstance::new(object_class)),
         )?;
         let object_index = self.global.new_object(object)?;
@@ -156,12 +176,9 @@ impl Loader {
         inits.push((object_index, object_index));
 
         let class_class = self.extract_boot_class(JAVA_CLASS)?;
-        let mut class_fields = class_class.field_names();
-        class_fields.append(&mut object_fields.clone());
-
         let class = Object::new(
             object_index.clone(),
-            object_fields,
+            Vec::new(),
             RustObject::Class(ClassInstance::new(class_class)),
         )?;
         let class_index = self.global.new_object(class)?;
@@ -171,7 +188,7 @@ impl Loader {
         let loader_class = self.extract_boot_class(JAVA_LOADER)?;
         let loader = Object::new(
             class_index.clone(),
-            class_fields.clone(),
+            Vec::new(),
             RustObject::Class(ClassInstance::new(loader_class)),
         )?;
         let loader_index = self.global.new_object(loader)?;
@@ -181,7 +198,7 @@ impl Loader {
         let thread_class = self.extract_boot_class(JAVA_THREAD)?;
         let thread_class = Object::new(
             class_index.clone(),
-            class_fields.clone(),
+            Vec::new(),
             RustObject::Class(ClassInstance::new(thread_class)),
         )?;
         let thread_class_index = self.global.new_object(thread_class)?;
@@ -191,9 +208,21 @@ impl Loader {
         inits.push((thread_class_index, inits.len()));
         inits.push((thread_class_index, inits.len() + 1));
 
-        let thread_fields = self.global.resolve_fields(thread_class_index)?;
+        let boot_classes = [object_index, class_index, loader_index, thread_class_index];
+        for index in boot_classes {
+            self.global.link_class(index)?;
+            self.global.class_mut(index)?.set_state(ClassState::Initialized);
+        }
+        for index in boot_classes {
+            if let Some(class) = self.global.reference(index)?.class() {
+                let fields = self.global.instance_fields(class)?;
+                *self.global.reference_mut(index)?.fields_mut() = fields;
+            }
+        }
 
-        let mut clinit_thread = Thread::new(self.global.clone(), 2);
+        let thread_fields = self.global.instance_fields(thread_class_index)?;
+
+        let mut clinit_thread = Thread::new(self.global.clone(), "clinit", 2);
         for class in clinits.iter().rev() {
             clinit_thread.new_clinit_frame(*class)?;
         }
@@ -204,7 +233,7 @@ impl Loader {
         )?;
         self.clinit_thread = self.global.new_object(clinit_thread)?;
 
-        let mut init_thread = Thread::new(self.global.clone(), 1);
+        let mut init_thread = Thread::new(self.global.clone(), "init", 1);
         for (class, this) in inits.iter().rev() {
             init_thread.new_default_init_frame(*class, *this)?;
         }
@@ -215,6 +244,8 @@ impl Loader {
         )?;
         self.init_thread = self.global.new_object(init_thread)?;
 
+        self.global.thread_lock(2)?;
+
         Ok(())
     }
 }
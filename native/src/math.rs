@@ -0,0 +1,123 @@
+use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
+use wasmjvm_common::WasmJVMError;
+
+use crate::{async_box, fdlibm, native, register_method, NativeEnv, NativeInterface, Primitive};
+
+/// Math shares the StrictMath natives, so both give the same bits on every platform.
+const MATH_CLASSES: [&str; 2] = ["java/lang/Math", "java/lang/StrictMath"];
+
+macro_rules! double_native {
+    ($async_func: ident, $func: ident, $implementation: expr, $($argument: ident),+) => {
+        async_box!($async_func, $func);
+        async fn $func(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+            if let [$(Primitive::Double($argument)),+] = &env.variables()[..] {
+                Ok(Primitive::Double($implementation($(*$argument),+)))
+            } else {
+                Err(env.invalid_arguments())
+            }
+        }
+    };
+}
+
+fn doubles(count: usize) -> Vec<Type> {
+    vec![Type::Single(SingleType::Double); count]
+}
+
+pub fn register(interface: &mut NativeInterface) {
+    for class in MATH_CLASSES {
+        let double = || Type::Single(SingleType::Double);
+
+        register_method!(interface, async_math_sin, class, "sin", doubles(1), double());
+        register_method!(interface, async_math_cos, class, "cos", doubles(1), double());
+        register_method!(interface, async_math_tan, class, "tan", doubles(1), double());
+        register_method!(interface, async_math_asin, class, "asin", doubles(1), double());
+        register_method!(interface, async_math_atan, class, "atan", doubles(1), double());
+        register_method!(interface, async_math_atan2, class, "atan2", doubles(2), double());
+        register_method!(interface, async_math_exp, class, "exp", doubles(1), double());
+        register_method!(interface, async_math_log, class, "log", doubles(1), double());
+        register_method!(interface, async_math_log10, class, "log10", doubles(1), double());
+        register_method!(interface, async_math_pow, class, "pow", doubles(2), double());
+        register_method!(interface, async_math_sqrt, class, "sqrt", doubles(1), double());
+        register_method!(interface, async_math_cbrt, class, "cbrt", doubles(1), double());
+        register_method!(interface, async_math_floor, class, "floor", doubles(1), double());
+        register_method!(interface, async_math_ceil, class, "ceil", doubles(1), double());
+        register_method!(interface, async_math_rint, class, "rint", doubles(1), double());
+        register_method!(interface, async_math_hypot, class, "hypot", doubles(2), double());
+        register_method!(
+            interface,
+            async_math_ieee_remainder,
+            class,
+            "IEEEremainder",
+            doubles(2),
+            double()
+        );
+    }
+}
+
+double_native!(async_math_sin, math_sin, fdlibm::sin, x);
+double_native!(async_math_cos, math_cos, fdlibm::cos, x);
+double_native!(async_math_tan, math_tan, fdlibm::tan, x);
+double_native!(async_math_asin, math_asin, fdlibm::asin, x);
+double_native!(async_math_atan, math_atan, fdlibm::atan, x);
+double_native!(async_math_atan2, math_atan2, fdlibm::atan2, y, x);
+double_native!(async_math_exp, math_exp, fdlibm::exp, x);
+double_native!(async_math_log, math_log, fdlibm::log, x);
+double_native!(async_math_log10, math_log10, fdlibm::log10, x);
+double_native!(async_math_pow, math_pow, fdlibm::pow, x, y);
+double_native!(async_math_sqrt, math_sqrt, f64::sqrt, x);
+double_native!(async_math_cbrt, math_cbrt, fdlibm::cbrt, x);
+double_native!(async_math_floor, math_floor, f64::floor, x);
+double_native!(async_math_ceil, math_ceil, f64::ceil, x);
+double_native!(async_math_rint, math_rint, f64::round_ties_even, x);
+double_native!(async_math_hypot, math_hypot, fdlibm::hypot, x, y);
+double_native!(async_math_ieee_remainder, math_ieee_remainder, fdlibm::ieee_remainder, x, y);
+
+#[native("java/lang/Float", "floatToRawIntBits")]
+fn float_float_to_raw_int_bits(value: f32) -> i32 {
+    value.to_bits() as i32
+}
+
+#[native("java/lang/Float", "intBitsToFloat")]
+fn float_int_bits_to_float(bits: i32) -> f32 {
+    f32::from_bits(bits as u32)
+}
+
+#[native("java/lang/Double", "doubleToRawLongBits")]
+fn double_double_to_raw_long_bits(value: f64) -> i64 {
+    value.to_bits() as i64
+}
+
+#[native("java/lang/Double", "longBitsToDouble")]
+fn double_long_bits_to_double(bits: i64) -> f64 {
+    f64::from_bits(bits as u64)
+}
+
+#[native("java/lang/Integer", "numberOfLeadingZeros")]
+fn integer_number_of_leading_zeros(value: i32) -> i32 {
+    value.leading_zeros() as i32
+}
+
+#[native("java/lang/Integer", "numberOfTrailingZeros")]
+fn integer_number_of_trailing_zeros(value: i32) -> i32 {
+    value.trailing_zeros() as i32
+}
+
+#[native("java/lang/Integer", "bitCount")]
+fn integer_bit_count(value: i32) -> i32 {
+    value.count_ones() as i32
+}
+
+#[native("java/lang/Long", "numberOfLeadingZeros")]
+fn long_number_of_leading_zeros(value: i64) -> i32 {
+    value.leading_zeros() as i32
+}
+
+#[native("java/lang/Long", "numberOfTrailingZeros")]
+fn long_number_of_trailing_zeros(value: i64) -> i32 {
+    value.trailing_zeros() as i32
+}
+
+#[native("java/lang/Long", "bitCount")]
+fn long_bit_count(value: i64) -> i32 {
+    value.count_ones() as i32
+}
@@ -0,0 +1,636 @@
+use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
+use wasmjvm_common::WasmJVMError;
+
+use crate::{async_box, native, register_method, JString, NativeEnv, NativeInterface, Primitive, JAVA_STRING};
+
+const MIN_RADIX: i32 = 2;
+const MAX_RADIX: i32 = 36;
+
+pub fn register(interface: &mut NativeInterface) {
+    register_method!(
+        interface,
+        async_integer_parse_int,
+        "java/lang/Integer",
+        "parseInt",
+        vec![
+            Type::Single(SingleType::Object(JAVA_STRING.to_string())),
+            Type::Single(SingleType::Int)
+        ],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_integer_to_string,
+        "java/lang/Integer",
+        "toString",
+        vec![Type::Single(SingleType::Int), Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+    register_method!(
+        interface,
+        async_long_parse_long,
+        "java/lang/Long",
+        "parseLong",
+        vec![
+            Type::Single(SingleType::Object(JAVA_STRING.to_string())),
+            Type::Single(SingleType::Int)
+        ],
+        Type::Single(SingleType::Long)
+    );
+    register_method!(
+        interface,
+        async_long_to_string,
+        "java/lang/Long",
+        "toString",
+        vec![Type::Single(SingleType::Long), Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+    register_method!(
+        interface,
+        async_float_parse_float,
+        "java/lang/Float",
+        "parseFloat",
+        vec![Type::Single(SingleType::Object(JAVA_STRING.to_string()))],
+        Type::Single(SingleType::Float)
+    );
+    register_method!(
+        interface,
+        async_float_to_string,
+        "java/lang/Float",
+        "toString",
+        vec![Type::Single(SingleType::Float)],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+    register_method!(
+        interface,
+        async_double_parse_double,
+        "java/lang/Double",
+        "parseDouble",
+        vec![Type::Single(SingleType::Object(JAVA_STRING.to_string()))],
+        Type::Single(SingleType::Double)
+    );
+    register_method!(
+        interface,
+        async_double_to_string,
+        "java/lang/Double",
+        "toString",
+        vec![Type::Single(SingleType::Double)],
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    );
+}
+
+/// `Character.digit` for the ASCII and fullwidth Latin digits and letters.
+fn digit(c: char, radix: u32) -> Option<u32> {
+    let c = match c as u32 {
+        0xff10..=0xff19 => char::from_u32(c as u32 - 0xff10 + '0' as u32)?,
+        0xff21..=0xff3a => char::from_u32(c as u32 - 0xff21 + 'A' as u32)?,
+        0xff41..=0xff5a => char::from_u32(c as u32 - 0xff41 + 'a' as u32)?,
+        _ => c,
+    };
+
+    c.to_digit(radix)
+}
+
+fn input_error(string: &str, radix: i32) -> WasmJVMError {
+    if radix == 10 {
+        WasmJVMError::NumberFormatException(format!("For input string: \"{}\"", string))
+    } else {
+        WasmJVMError::NumberFormatException(format!(
+            "For input string: \"{}\" under radix {}",
+            string, radix
+        ))
+    }
+}
+
+/// `Long.parseLong`, checked against `min` and `max` so `Integer.parseInt` can share it.
+fn parse_integer(string: Option<String>, radix: i32, min: i64, max: i64) -> Result<i64, WasmJVMError> {
+    let string = string.ok_or_else(|| {
+        WasmJVMError::NumberFormatException("Cannot parse null string".to_string())
+    })?;
+
+    if radix < MIN_RADIX {
+        return Err(WasmJVMError::NumberFormatException(format!(
+            "radix {} less than Character.MIN_RADIX",
+            radix
+        )));
+    }
+    if radix > MAX_RADIX {
+        return Err(WasmJVMError::NumberFormatException(format!(
+            "radix {} greater than Character.MAX_RADIX",
+            radix
+        )));
+    }
+
+    let (negative, digits) = match string.chars().next() {
+        Some('-') => (true, &string[1..]),
+        Some('+') => (false, &string[1..]),
+        _ => (false, &string[..]),
+    };
+
+    if digits.is_empty() {
+        return Err(input_error(&string, radix));
+    }
+
+    let mut value: i128 = 0;
+    for c in digits.chars() {
+        let digit = digit(c, radix as u32).ok_or_else(|| input_error(&string, radix))?;
+        value = value * radix as i128 + digit as i128;
+
+        if value > max as i128 + 1 {
+            return Err(input_error(&string, radix));
+        }
+    }
+
+    let value = if negative { -value } else { value };
+
+    if value < min as i128 || value > max as i128 {
+        return Err(input_error(&string, radix));
+    }
+
+    Ok(value as i64)
+}
+
+/// `Long.toString(long, int)`, radixes out of range fall back to ten.
+fn integer_to_string(value: i64, radix: i32) -> String {
+    let radix = if (MIN_RADIX..=MAX_RADIX).contains(&radix) { radix as u64 } else { 10 };
+    let mut magnitude = value.unsigned_abs();
+    let mut digits = Vec::new();
+
+    loop {
+        digits.push(std::char::from_digit((magnitude % radix) as u32, radix as u32).unwrap_or('?'));
+        magnitude /= radix;
+
+        if magnitude == 0 {
+            break;
+        }
+    }
+
+    if value < 0 {
+        digits.push('-');
+    }
+
+    digits.iter().rev().collect()
+}
+
+/// Splits Rust's `{:e}` output into its significant digits and decimal exponent.
+fn scientific_parts(string: &str) -> (String, i32) {
+    let (mantissa, exponent) = string.split_once('e').unwrap_or((string, "0"));
+
+    (mantissa.chars().filter(|c| *c != '.').collect(), exponent.parse().unwrap_or(0))
+}
+
+/// When the value lies exactly between two candidates of the same length, Java takes the one
+/// with the even last digit, as long as it still rounds back to the value.
+fn prefer_even(
+    value: f64,
+    digits: String,
+    exponent: i32,
+    round_trips: &impl Fn(&str) -> bool,
+) -> (String, i32) {
+    let (exact, exact_exponent) = scientific_parts(&format!("{:.800e}", value.abs()));
+    let exact = exact.trim_end_matches('0');
+
+    if exact_exponent != exponent || exact.len() != digits.len() + 1 || !exact.ends_with('5') {
+        return (digits, exponent);
+    }
+
+    let low: u64 = match exact[..digits.len()].parse() {
+        Ok(low) => low,
+        Err(_) => return (digits, exponent),
+    };
+    let even = if low % 2 == 0 { low } else { low + 1 };
+    let scale = exponent - (digits.len() as i32 - 1);
+
+    if round_trips(&format!("{}e{}", even, scale)) {
+        let even = even.to_string();
+        let exponent = scale + even.len() as i32 - 1;
+
+        (even, exponent)
+    } else {
+        (digits, exponent)
+    }
+}
+
+/// The significant digits `Double.toString` picks for a finite, non-zero value and the decimal
+/// exponent of the first one.
+fn floating_digits(value: f64, shortest: String, round_trips: impl Fn(&str) -> bool) -> (String, i32) {
+    // Rust finds the shortest length, but not always the closest decimal of that length.
+    let (mut digits, mut exponent) = scientific_parts(shortest.trim_start_matches('-'));
+    let closest = format!("{:.*e}", digits.len().max(2) - 1, value.abs());
+
+    if digits.len() == 1 || round_trips(&closest) {
+        (digits, exponent) = scientific_parts(&closest);
+    }
+
+    let (digits, exponent) = prefer_even(value, digits, exponent, &round_trips);
+    let digits = match digits.trim_end_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+
+    (digits.to_string(), exponent)
+}
+
+/// Formats the shortest decimal that rounds back to the value the way `Double.toString` does:
+/// plain notation in [10^-3, 10^7) and computerized scientific notation elsewhere. Among the
+/// shortest candidates the closest wins, and at least two digits are considered.
+fn floating_to_string(value: f64, shortest: String, round_trips: impl Fn(&str) -> bool) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+
+    let negative = value.is_sign_negative();
+
+    if value.is_infinite() {
+        return if negative { "-Infinity" } else { "Infinity" }.to_string();
+    }
+    if value == 0.0 {
+        return if negative { "-0.0" } else { "0.0" }.to_string();
+    }
+
+    let (digits, exponent) = floating_digits(value, shortest, round_trips);
+    let digits = digits.as_str();
+
+    let mut result = String::new();
+    if negative {
+        result.push('-');
+    }
+
+    if (-3..7).contains(&exponent) {
+        if exponent < 0 {
+            result.push_str("0.");
+            result.push_str(&"0".repeat((-exponent - 1) as usize));
+            result.push_str(digits);
+        } else {
+            let point = exponent as usize + 1;
+
+            if digits.len() > point {
+                result.push_str(&digits[..point]);
+                result.push('.');
+                result.push_str(&digits[point..]);
+            } else {
+                result.push_str(digits);
+                result.push_str(&"0".repeat(point - digits.len()));
+                result.push_str(".0");
+            }
+        }
+    } else {
+        result.push_str(&digits[..1]);
+        result.push('.');
+        result.push_str(if digits.len() > 1 { &digits[1..] } else { "0" });
+        result.push('E');
+        result.push_str(&exponent.to_string());
+    }
+
+    result
+}
+
+fn double_to_string(value: f64) -> String {
+    floating_to_string(value, format!("{:e}", value), |candidate| {
+        candidate.parse::<f64>().map(|parsed| parsed == value.abs()).unwrap_or(false)
+    })
+}
+
+/// Keeps the first `count` of `digits`, rounding half up like the JDK's `Formatter` does on the
+/// `Double.toString` digits, and returns the exponent of the first digit afterwards.
+fn round_digits(digits: &mut Vec<u8>, exponent: i32, count: i32) -> i32 {
+    if count < 0 || count as usize >= digits.len() {
+        return exponent;
+    }
+
+    let up = digits[count as usize] >= b'5';
+    digits.truncate(count as usize);
+
+    if !up {
+        if digits.is_empty() {
+            digits.push(b'0');
+        }
+
+        return exponent;
+    }
+
+    for digit in digits.iter_mut().rev() {
+        if *digit < b'9' {
+            *digit += 1;
+            return exponent;
+        }
+
+        *digit = b'0';
+    }
+
+    digits.insert(0, b'1');
+    exponent + 1
+}
+
+/// The magnitude of `value` the way `%.{precision}f` or `%.{precision}e` prints it.
+fn format_decimal(value: f64, precision: i32, scientific: bool) -> String {
+    let (digits, exponent) = if value == 0.0 {
+        ("0".to_string(), 0)
+    } else {
+        floating_digits(value, format!("{:e}", value), |candidate| {
+            candidate.parse::<f64>().map(|parsed| parsed == value.abs()).unwrap_or(false)
+        })
+    };
+
+    let mut digits = digits.into_bytes();
+    let count = if scientific { precision + 1 } else { exponent + 1 + precision };
+    let exponent = round_digits(&mut digits, exponent, count);
+    let digit = |index: i32| if index < 0 { '0' } else { *digits.get(index as usize).unwrap_or(&b'0') as char };
+
+    let mut result = String::new();
+    if scientific {
+        result.push(digit(0));
+        if precision > 0 {
+            result.push('.');
+            result.extend((1..=precision).map(digit));
+        }
+
+        result.push_str(&format!("e{}{:02}", if exponent < 0 { '-' } else { '+' }, exponent.abs()));
+    } else {
+        if exponent < 0 {
+            result.push('0');
+        } else {
+            result.extend((0..=exponent).map(digit));
+        }
+
+        if precision > 0 {
+            result.push('.');
+            result.extend((exponent + 1..=exponent + precision).map(digit));
+        }
+    }
+
+    result
+}
+
+#[native("java/lang/String", "formatDecimal")]
+fn string_format_decimal(value: f64, precision: i32, scientific: bool) -> JString {
+    JString(format_decimal(value, precision, scientific))
+}
+
+fn float_to_string(value: f32) -> String {
+    floating_to_string(value as f64, format!("{:e}", value), |candidate| {
+        candidate.parse::<f32>().map(|parsed| parsed == value.abs()).unwrap_or(false)
+    })
+}
+
+/// Rounds `mantissa * 2^exponent` to a binary floating point number with `precision` bits and
+/// a smallest subnormal of `2^min_exponent`, ties to even.
+fn round_binary(mantissa: u128, sticky: bool, exponent: i64, precision: i64, min_exponent: i64) -> f64 {
+    if mantissa == 0 {
+        return 0.0;
+    }
+
+    let length = 128 - mantissa.leading_zeros() as i64;
+    let top = exponent + length - 1;
+
+    if top > 1023 {
+        return f64::INFINITY;
+    }
+
+    let low = (top - precision + 1).max(min_exponent);
+    let shift = low - exponent;
+
+    let rounded = if shift <= 0 {
+        mantissa << -shift
+    } else if shift > 127 {
+        0
+    } else {
+        let quotient = mantissa >> shift;
+        let remainder = mantissa & ((1u128 << shift) - 1);
+        let half = 1u128 << (shift - 1);
+
+        if remainder > half || (remainder == half && (sticky || quotient & 1 == 1)) {
+            quotient + 1
+        } else {
+            quotient
+        }
+    };
+
+    // Both factors are exact and so is the product, subnormal scales go through a normal one.
+    let power = |exponent: i64| f64::from_bits(((exponent + 1023) as u64) << 52);
+
+    if low >= -1022 {
+        rounded as f64 * power(low)
+    } else {
+        rounded as f64 * power(low + 64) * power(-64)
+    }
+}
+
+/// Parses a Java hexadecimal floating point literal body such as `1.8p1`.
+fn parse_hex(body: &str, precision: i64, min_exponent: i64) -> Option<f64> {
+    let (significand, exponent) = body.split_once(|c| c == 'p' || c == 'P')?;
+    let (integer, fraction) = significand.split_once('.').unwrap_or((significand, ""));
+
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let exponent_digits = exponent.strip_prefix(|c| c == '+' || c == '-').unwrap_or(exponent);
+    if exponent_digits.is_empty() || !exponent_digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let mut binary_exponent: i64 = exponent_digits
+        .chars()
+        .fold(0i64, |value, c| (value * 10 + c as i64 - '0' as i64).min(100_000));
+    if exponent.starts_with('-') {
+        binary_exponent = -binary_exponent;
+    }
+
+    let mut mantissa: u128 = 0;
+    let mut sticky = false;
+    let mut significant = 0;
+
+    for (index, c) in integer.chars().chain(fraction.chars()).enumerate() {
+        let digit = c.to_digit(16)? as u128;
+        let is_fraction = index >= integer.len();
+
+        if mantissa == 0 && digit == 0 {
+            if is_fraction {
+                binary_exponent -= 4;
+            }
+            continue;
+        }
+
+        if significant < 28 {
+            mantissa = mantissa * 16 + digit;
+            significant += 1;
+
+            if is_fraction {
+                binary_exponent -= 4;
+            }
+        } else {
+            sticky |= digit != 0;
+
+            if !is_fraction {
+                binary_exponent += 4;
+            }
+        }
+    }
+
+    Some(round_binary(mantissa, sticky, binary_exponent, precision, min_exponent))
+}
+
+enum Floating {
+    Decimal(String),
+    Binary(f64),
+}
+
+/// Checks the `Double.parseDouble` grammar and returns something Rust can parse exactly.
+fn parse_floating(
+    string: Option<String>,
+    precision: i64,
+    min_exponent: i64,
+) -> Result<Floating, WasmJVMError> {
+    let string = string.ok_or_else(|| WasmJVMError::NullPointerException(
+            "Cannot invoke \"String.trim()\" because \"in\" is null".to_string(),
+        ))?;
+    let trimmed = string.trim_matches(|c| c <= ' ');
+
+    if trimmed.is_empty() {
+        return Err(WasmJVMError::NumberFormatException("empty String".to_string()));
+    }
+
+    let error = || input_error(trimmed, 10);
+
+    let (sign, unsigned) = match trimmed.chars().next() {
+        Some(sign @ ('-' | '+')) => (sign, &trimmed[1..]),
+        _ => ('+', trimmed),
+    };
+
+    match unsigned {
+        "NaN" => return Ok(Floating::Binary(f64::NAN)),
+        "Infinity" if sign == '-' => return Ok(Floating::Binary(f64::NEG_INFINITY)),
+        "Infinity" => return Ok(Floating::Binary(f64::INFINITY)),
+        _ => {}
+    }
+
+    let unsigned = unsigned
+        .strip_suffix(|c| matches!(c, 'f' | 'F' | 'd' | 'D'))
+        .unwrap_or(unsigned);
+
+    if let Some(body) = unsigned.strip_prefix("0x").or_else(|| unsigned.strip_prefix("0X")) {
+        let value = parse_hex(body, precision, min_exponent).ok_or_else(error)?;
+        return Ok(Floating::Binary(if sign == '-' { -value } else { value }));
+    }
+
+    let (significand, exponent) = match unsigned.split_once(|c| c == 'e' || c == 'E') {
+        Some((significand, exponent)) => (significand, Some(exponent)),
+        None => (unsigned, None),
+    };
+    let (integer, fraction) = significand.split_once('.').unwrap_or((significand, ""));
+
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    let valid_exponent = match exponent {
+        Some(exponent) => {
+            let digits = exponent.strip_prefix(|c| c == '+' || c == '-').unwrap_or(exponent);
+            !digits.is_empty() && is_digits(digits)
+        }
+        None => true,
+    };
+
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) || !valid_exponent {
+        return Err(error());
+    }
+
+    Ok(Floating::Decimal(format!("{}{}", sign, unsigned)))
+}
+
+async_box!(async_integer_parse_int, integer_parse_int);
+async fn integer_parse_int(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [string, Primitive::Int(radix)] = &env.variables()[..] {
+        let string = env.global().java_string(string)?;
+        let value = parse_integer(string, *radix, i32::MIN as i64, i32::MAX as i64)?;
+
+        Ok(Primitive::Int(value as i32))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_integer_to_string, integer_to_string_native);
+async fn integer_to_string_native(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Int(value), Primitive::Int(radix)] = env.variables()[..] {
+        let string = integer_to_string(value as i64, radix);
+
+        Ok(Primitive::Reference(env.global_mut().new_java_string(string)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_long_parse_long, long_parse_long);
+async fn long_parse_long(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [string, Primitive::Int(radix)] = &env.variables()[..] {
+        let string = env.global().java_string(string)?;
+
+        Ok(Primitive::Long(parse_integer(string, *radix, i64::MIN, i64::MAX)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_long_to_string, long_to_string);
+async fn long_to_string(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Long(value), Primitive::Int(radix)] = env.variables()[..] {
+        let string = integer_to_string(value, radix);
+
+        Ok(Primitive::Reference(env.global_mut().new_java_string(string)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_float_parse_float, float_parse_float);
+async fn float_parse_float(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [string] = &env.variables()[..] {
+        let string = env.global().java_string(string)?;
+
+        let value = match parse_floating(string, 24, -149)? {
+            Floating::Binary(value) => value as f32,
+            Floating::Decimal(decimal) => decimal
+                .parse::<f32>()
+                .map_err(|_| input_error(&decimal, 10))?,
+        };
+
+        Ok(Primitive::Float(value))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_float_to_string, float_to_string_native);
+async fn float_to_string_native(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Float(value)] = env.variables()[..] {
+        Ok(Primitive::Reference(env.global_mut().new_java_string(float_to_string(value))?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_double_parse_double, double_parse_double);
+async fn double_parse_double(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [string] = &env.variables()[..] {
+        let string = env.global().java_string(string)?;
+
+        let value = match parse_floating(string, 53, -1074)? {
+            Floating::Binary(value) => value,
+            Floating::Decimal(decimal) => decimal
+                .parse::<f64>()
+                .map_err(|_| input_error(&decimal, 10))?,
+        };
+
+        Ok(Primitive::Double(value))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_double_to_string, double_to_string_native);
+async fn double_to_string_native(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Double(value)] = env.variables()[..] {
+        Ok(Primitive::Reference(env.global_mut().new_java_string(double_to_string(value))?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
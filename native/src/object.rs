@@ -1,18 +1,17 @@
-use std::collections::HashMap;
-
 use wasmjvm_class::{Constant, SingleType, Type};
 use wasmjvm_common::WasmJVMError;
 
-use crate::{ClassInstance, Loader, NativeInterface, Thread, Global};
+use crate::{ClassInstance, Global, Loader, NativeInterface, StackTraceEntry, Thread};
 
 #[derive(Debug)]
 pub enum RustObject {
     Class(ClassInstance),
-    String(String),
-    Array(Vec<Primitive>),
+    String(Vec<u16>),
+    Array(Array),
     Thread(Thread),
     Loader(Loader),
     Native(NativeInterface),
+    Backtrace(Vec<StackTraceEntry>),
     Null,
 }
 
@@ -20,52 +19,70 @@ pub enum RustObject {
 pub struct Object {
     class: Option<usize>,
     inner: RustObject,
-    pub fields: HashMap<String, Primitive>,
+    fields: Vec<Primitive>,
 }
 
 impl Object {
     pub fn new(
         class_index: usize,
-        fields: Vec<String>,
+        fields: Vec<Primitive>,
         inner: RustObject,
     ) -> Result<Self, WasmJVMError> {
-        let mut fields_map = HashMap::new();
-
-        for field in fields {
-            fields_map.insert(field, Primitive::Null);
-        }
-
         Ok(Self {
             class: Some(class_index),
             inner,
-            fields: fields_map,
+            fields,
         })
     }
 
-    pub fn new_array(raw: Vec<Primitive>) -> Result<Self, WasmJVMError> {
+    pub fn new_array(class_index: usize, array: Array) -> Result<Self, WasmJVMError> {
         Ok(Self {
-            class: None,
-            inner: RustObject::Array(raw),
-            fields: HashMap::new(),
+            class: Some(class_index),
+            inner: RustObject::Array(array),
+            fields: Vec::new(),
         })
     }
 
-    pub fn new_empty_array(size: usize) -> Result<Self, WasmJVMError> {
-        // TODO: Use type default value.
-        Self::new_array(vec![Primitive::Null; size])
+    pub fn fields(self: &Self) -> &Vec<Primitive> {
+        &self.fields
+    }
+
+    pub fn fields_mut(self: &mut Self) -> &mut Vec<Primitive> {
+        &mut self.fields
+    }
+
+    pub fn field(self: &Self, index: usize) -> Result<Primitive, WasmJVMError> {
+        if let Some(field) = self.fields.get(index) {
+            Ok(field.clone())
+        } else {
+            Err(WasmJVMError::NoSuchFieldError(format!("Field slot {}", index)))
+        }
     }
 
-    pub fn new_deep_array(global: &mut Global, counts: &Vec<usize>, index: usize) -> Result<Primitive, WasmJVMError> {
-        let array_index: usize;
+    pub fn set_field(self: &mut Self, index: usize, value: Primitive) -> Result<(), WasmJVMError> {
+        if let Some(field) = self.fields.get_mut(index) {
+            *field = value;
+
+            Ok(())
+        } else {
+            Err(WasmJVMError::NoSuchFieldError(format!("Field slot {}", index)))
+        }
+    }
 
-        if index + 1 == counts.len() {
-            array_index = global.new_object(Object::new_empty_array(counts[index])?)?;
+    pub fn new_deep_array(global: &mut Global, r#type: &Type, counts: &Vec<i32>, index: usize) -> Result<Primitive, WasmJVMError> {
+        let component = if let Some(component) = r#type.component() {
+            component
         } else {
-            let mut array = Vec::new();
-            for _ in 0..counts[index] {
-                array.push(Object::new_deep_array(global, counts, index + 1)?);
+            return Err(WasmJVMError::ClassFormatError(format!("{:?} is not an array", r#type)));
+        };
+
+        let array_index = global.new_array(&component, counts[index])?;
+
+        if index + 1 < counts.len() {
+            for i in 0..counts[index] {
+                let value = Object::new_deep_array(global, &component, counts, index + 1)?;
+                global.array_set(Primitive::Reference(array_index), Primitive::Int(i), value)?;
             }
-            array_index = global.new_object(Object::new_array(array)?)?;
         }
 
         Ok(Primitive::Reference(array_index))
@@ -84,14 +101,195 @@ impl Object {
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum Array {
+    Boolean(Vec<bool>),
+    Byte(Vec<i8>),
+    Char(Vec<u16>),
+    Short(Vec<i16>),
+    Int(Vec<i32>),
+    Long(Vec<i64>),
+    Float(Vec<f32>),
+    Double(Vec<f64>),
+    Reference(Vec<Option<usize>>),
+}
+
+impl Array {
+    /// A zeroed array, or an `OutOfMemoryError` when `size` elements can't be allocated.
+    pub fn new(component: &Type, size: usize) -> Result<Self, WasmJVMError> {
+        Ok(match component {
+            Type::Single(SingleType::Boolean) => Array::Boolean(Self::filled(false, size)?),
+            Type::Single(SingleType::Byte) => Array::Byte(Self::filled(0, size)?),
+            Type::Single(SingleType::Char) => Array::Char(Self::filled(0, size)?),
+            Type::Single(SingleType::Short) => Array::Short(Self::filled(0, size)?),
+            Type::Single(SingleType::Int) => Array::Int(Self::filled(0, size)?),
+            Type::Single(SingleType::Long) => Array::Long(Self::filled(0, size)?),
+            Type::Single(SingleType::Float) => Array::Float(Self::filled(0.0, size)?),
+            Type::Single(SingleType::Double) => Array::Double(Self::filled(0.0, size)?),
+            _ => Array::Reference(Self::filled(None, size)?),
+        })
+    }
+
+    fn filled<T: Clone>(value: T, size: usize) -> Result<Vec<T>, WasmJVMError> {
+        let mut elements = Vec::new();
+        elements
+            .try_reserve_exact(size)
+            .map_err(|_| WasmJVMError::OutOfMemoryError(format!("Java heap space")))?;
+        elements.resize(size, value);
+
+        Ok(elements)
+    }
+
+    pub fn atype_component(atype: u8) -> Result<Type, WasmJVMError> {
+        let component = match atype {
+            4 => SingleType::Boolean,
+            5 => SingleType::Char,
+            6 => SingleType::Float,
+            7 => SingleType::Double,
+            8 => SingleType::Byte,
+            9 => SingleType::Short,
+            10 => SingleType::Int,
+            11 => SingleType::Long,
+            _ => return Err(WasmJVMError::ClassFormatError(format!("Invalid array type {}", atype))),
+        };
+
+        Ok(Type::Single(component))
+    }
+
+    pub fn len(self: &Self) -> usize {
+        match self {
+            Array::Boolean(array) => array.len(),
+            Array::Byte(array) => array.len(),
+            Array::Char(array) => array.len(),
+            Array::Short(array) => array.len(),
+            Array::Int(array) => array.len(),
+            Array::Long(array) => array.len(),
+            Array::Float(array) => array.len(),
+            Array::Double(array) => array.len(),
+            Array::Reference(array) => array.len(),
+        }
+    }
+
+    /// A copy of `length` elements starting at `start`, which the caller has bounds checked.
+    pub fn range(self: &Self, start: usize, length: usize) -> Array {
+        let end = start + length;
+
+        match self {
+            Array::Boolean(array) => Array::Boolean(array[start..end].to_vec()),
+            Array::Byte(array) => Array::Byte(array[start..end].to_vec()),
+            Array::Char(array) => Array::Char(array[start..end].to_vec()),
+            Array::Short(array) => Array::Short(array[start..end].to_vec()),
+            Array::Int(array) => Array::Int(array[start..end].to_vec()),
+            Array::Long(array) => Array::Long(array[start..end].to_vec()),
+            Array::Float(array) => Array::Float(array[start..end].to_vec()),
+            Array::Double(array) => Array::Double(array[start..end].to_vec()),
+            Array::Reference(array) => Array::Reference(array[start..end].to_vec()),
+        }
+    }
+
+    /// Overwrites the elements from `start` with `source`, which must hold the same element type.
+    pub fn write(self: &mut Self, start: usize, source: &Array) -> Result<(), WasmJVMError> {
+        let end = start + source.len();
+
+        if end > self.len() {
+            return Err(WasmJVMError::IndexOutOfBoundException(format!(
+                "Index {} out of bounds for length {}",
+                end - 1,
+                self.len()
+            )));
+        }
+
+        match (self, source) {
+            (Array::Boolean(array), Array::Boolean(source)) => array[start..end].copy_from_slice(source),
+            (Array::Byte(array), Array::Byte(source)) => array[start..end].copy_from_slice(source),
+            (Array::Char(array), Array::Char(source)) => array[start..end].copy_from_slice(source),
+            (Array::Short(array), Array::Short(source)) => array[start..end].copy_from_slice(source),
+            (Array::Int(array), Array::Int(source)) => array[start..end].copy_from_slice(source),
+            (Array::Long(array), Array::Long(source)) => array[start..end].copy_from_slice(source),
+            (Array::Float(array), Array::Float(source)) => array[start..end].copy_from_slice(source),
+            (Array::Double(array), Array::Double(source)) => array[start..end].copy_from_slice(source),
+            (Array::Reference(array), Array::Reference(source)) => {
+                array[start..end].copy_from_slice(source)
+            }
+            _ => {
+                return Err(WasmJVMError::ArrayStoreException(format!(
+                    "arraycopy: type mismatch"
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_index(self: &Self, index: i32) -> Result<usize, WasmJVMError> {
+        if index < 0 || index as usize >= self.len() {
+            Err(WasmJVMError::IndexOutOfBoundException(format!(
+                "Index {} out of bounds for length {}",
+                index,
+                self.len()
+            )))
+        } else {
+            Ok(index as usize)
+        }
+    }
+
+    pub fn get(self: &Self, index: i32) -> Result<Primitive, WasmJVMError> {
+        let index = self.check_index(index)?;
+
+        Ok(match self {
+            Array::Boolean(array) => Primitive::Int(array[index] as i32),
+            Array::Byte(array) => Primitive::Int(array[index] as i32),
+            Array::Char(array) => Primitive::Int(array[index] as i32),
+            Array::Short(array) => Primitive::Int(array[index] as i32),
+            Array::Int(array) => Primitive::Int(array[index]),
+            Array::Long(array) => Primitive::Long(array[index]),
+            Array::Float(array) => Primitive::Float(array[index]),
+            Array::Double(array) => Primitive::Double(array[index]),
+            Array::Reference(array) => match array[index] {
+                Some(reference) => Primitive::Reference(reference),
+                None => Primitive::Null,
+            },
+        })
+    }
+
+    pub fn set(self: &mut Self, index: i32, value: Primitive) -> Result<(), WasmJVMError> {
+        let index = self.check_index(index)?;
+
+        match self {
+            Array::Boolean(array) => array[index] = value.as_int()? & 1 != 0,
+            Array::Byte(array) => array[index] = value.as_int()? as i8,
+            Array::Char(array) => array[index] = value.as_int()? as u16,
+            Array::Short(array) => array[index] = value.as_int()? as i16,
+            Array::Int(array) => array[index] = value.as_int()?,
+            Array::Long(array) => array[index] = value.as_long()?,
+            Array::Float(array) => array[index] = value.as_float()?,
+            Array::Double(array) => array[index] = value.as_double()?,
+            Array::Reference(array) => {
+                array[index] = match value {
+                    Primitive::Null => None,
+                    Primitive::Reference(reference) => Some(reference),
+                    _ => {
+                        return Err(WasmJVMError::ArrayStoreException(format!(
+                            "{:?} is not a reference",
+                            value
+                        )))
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Primitive {
     Void,
     Null,
     Boolean(bool),
-    Byte(u8),
-    Char(u8),
-    Short(u16),
+    Byte(i8),
+    Char(u16),
+    Short(i16),
     Int(i32),
     Long(i64),
     Float(f32),
@@ -104,30 +302,46 @@ macro_rules! primitive_into {
         pub fn $name(self: &Self) -> Result<Self, WasmJVMError> {
             match self {
                 Primitive::Boolean(value) => Ok(Primitive::$primitive(*value as u8 as $type)),
-                Primitive::Byte(value) | Primitive::Char(value) => {
-                    Ok(Primitive::$primitive(*value as $type))
-                }
+                Primitive::Byte(value) => Ok(Primitive::$primitive(*value as $type)),
+                Primitive::Char(value) => Ok(Primitive::$primitive(*value as $type)),
                 Primitive::Short(value) => Ok(Primitive::$primitive(*value as $type)),
                 Primitive::Int(value) => Ok(Primitive::$primitive(*value as $type)),
                 Primitive::Long(value) => Ok(Primitive::$primitive(*value as $type)),
                 Primitive::Float(value) => Ok(Primitive::$primitive(*value as $type)),
                 Primitive::Double(value) => Ok(Primitive::$primitive(*value as $type)),
                 Primitive::Null => Ok(Primitive::$primitive(0 as $type)),
-                _ => panic!("Failed to cast {:?} to {}.", self, stringify!($type)),
+                _ => Err(WasmJVMError::VerifyError(format!("Cannot convert {:?} to {}", self, stringify!($type)))),
             }
         }
     };
 }
 
 macro_rules! primitive_op {
-    ($name:ident, $op:tt) => {
+    ($name:ident, $wrapping:ident, $op:tt) => {
         pub fn $name(self: &Self, other: &Self) -> Result<Self, WasmJVMError> {
             match (self, other) {
-                (Primitive::Int(left), Primitive::Int(right)) => Ok(Primitive::Int(left $op right)),
-                (Primitive::Long(left), Primitive::Long(right)) => Ok(Primitive::Long(left $op right)),
+                (Primitive::Int(left), Primitive::Int(right)) => Ok(Primitive::Int(left.$wrapping(*right))),
+                (Primitive::Long(left), Primitive::Long(right)) => Ok(Primitive::Long(left.$wrapping(*right))),
                 (Primitive::Float(left), Primitive::Float(right)) => Ok(Primitive::Float(left $op right)),
                 (Primitive::Double(left), Primitive::Double(right)) => Ok(Primitive::Double(left $op right)),
-                _ => unreachable!()
+                _ => Err(WasmJVMError::VerifyError(format!("Cannot {} {:?} and {:?}", stringify!($name), self, other)))
+            }
+        }
+    }
+}
+
+macro_rules! primitive_div_op {
+    ($name:ident, $wrapping:ident, $op:tt) => {
+        pub fn $name(self: &Self, other: &Self) -> Result<Self, WasmJVMError> {
+            match (self, other) {
+                (Primitive::Int(_), Primitive::Int(0)) | (Primitive::Long(_), Primitive::Long(0)) => {
+                    Err(WasmJVMError::ArithmeticException(format!("/ by zero")))
+                }
+                (Primitive::Int(left), Primitive::Int(right)) => Ok(Primitive::Int(left.$wrapping(*right))),
+                (Primitive::Long(left), Primitive::Long(right)) => Ok(Primitive::Long(left.$wrapping(*right))),
+                (Primitive::Float(left), Primitive::Float(right)) => Ok(Primitive::Float(left $op right)),
+                (Primitive::Double(left), Primitive::Double(right)) => Ok(Primitive::Double(left $op right)),
+                _ => Err(WasmJVMError::VerifyError(format!("Cannot {} {:?} and {:?}", stringify!($name), self, other)))
             }
         }
     }
@@ -139,41 +353,54 @@ macro_rules! primitive_bit_op {
             match (self, other) {
                 (Primitive::Int(left), Primitive::Int(right)) => Ok(Primitive::Int(left $op right)),
                 (Primitive::Long(left), Primitive::Long(right)) => Ok(Primitive::Long(left $op right)),
-                _ => unreachable!()
+                _ => Err(WasmJVMError::VerifyError(format!("Cannot {} {:?} and {:?}", stringify!($name), self, other)))
             }
         }
     }
 }
 
 impl Primitive {
+    pub fn zero(r#type: &Type) -> Self {
+        match r#type {
+            Type::Single(SingleType::Boolean)
+            | Type::Single(SingleType::Byte)
+            | Type::Single(SingleType::Char)
+            | Type::Single(SingleType::Short)
+            | Type::Single(SingleType::Int) => Primitive::Int(0),
+            Type::Single(SingleType::Long) => Primitive::Long(0),
+            Type::Single(SingleType::Float) => Primitive::Float(0.0),
+            Type::Single(SingleType::Double) => Primitive::Double(0.0),
+            _ => Primitive::Null,
+        }
+    }
+
     primitive_into!(into_float, Float, f32);
     primitive_into!(into_double, Double, f64);
     primitive_into!(into_int, Int, i32);
     primitive_into!(into_long, Long, i64);
-    primitive_into!(into_byte, Byte, u8);
-    primitive_into!(into_char, Char, u8);
-    primitive_into!(into_short, Short, u16);
+    primitive_into!(into_byte, Byte, i8);
+    primitive_into!(into_char, Char, u16);
+    primitive_into!(into_short, Short, i16);
 
     pub fn into_bool(self: &Self) -> Result<Self, WasmJVMError> {
         match self {
             Primitive::Boolean(value) => Ok(Primitive::Boolean(*value)),
-            Primitive::Byte(value) | Primitive::Char(value) => {
-                Ok(Primitive::Boolean(*value != 0))
-            }
+            Primitive::Byte(value) => Ok(Primitive::Boolean(*value != 0)),
+            Primitive::Char(value) => Ok(Primitive::Boolean(*value != 0)),
             Primitive::Short(value) => Ok(Primitive::Boolean(*value != 0)),
             Primitive::Int(value) => Ok(Primitive::Boolean(*value != 0)),
             Primitive::Long(value) => Ok(Primitive::Boolean(*value != 0)),
             Primitive::Float(value) => Ok(Primitive::Boolean(*value != 0.0)),
             Primitive::Double(value) => Ok(Primitive::Boolean(*value != 0.0)),
             Primitive::Null => Ok(Primitive::Boolean(false)),
-            _ => panic!("Failed to cast {:?} to {}.", self, stringify!($type)),
+            _ => Err(WasmJVMError::VerifyError(format!("Cannot convert {:?} to boolean", self))),
         }
     }
 
     pub fn into_void(self: &Self) -> Result<Self, WasmJVMError> {
         match self {
             Self::Void => Ok(Self::Void),
-            _ => unreachable!(),
+            _ => Err(WasmJVMError::VerifyError(format!("Cannot convert {:?} to void", self))),
         }
     }
 
@@ -195,21 +422,81 @@ impl Primitive {
         }
     }
 
-    primitive_op!(add, +);
-    primitive_op!(sub, -);
-    primitive_op!(mul, *);
-    primitive_op!(div, /);
-    primitive_op!(rem, %);
+    primitive_op!(add, wrapping_add, +);
+    primitive_op!(sub, wrapping_sub, -);
+    primitive_op!(mul, wrapping_mul, *);
+    primitive_div_op!(div, wrapping_div, /);
+    primitive_div_op!(rem, wrapping_rem, %);
     primitive_bit_op!(and, &);
     primitive_bit_op!(or, |);
     primitive_bit_op!(xor, ^);
-    primitive_bit_op!(shl, <<);
-    primitive_bit_op!(shr, >>);
+
+    pub fn shl(self: &Self, other: &Self) -> Result<Self, WasmJVMError> {
+        let distance = other.as_int()? as u32;
+
+        match self {
+            Primitive::Int(value) => Ok(Primitive::Int(value.wrapping_shl(distance & 0x1f))),
+            Primitive::Long(value) => Ok(Primitive::Long(value.wrapping_shl(distance & 0x3f))),
+            _ => Err(WasmJVMError::VerifyError(format!("Cannot shl {:?}", self))),
+        }
+    }
+
+    pub fn shr(self: &Self, other: &Self) -> Result<Self, WasmJVMError> {
+        let distance = other.as_int()? as u32;
+
+        match self {
+            Primitive::Int(value) => Ok(Primitive::Int(value >> (distance & 0x1f))),
+            Primitive::Long(value) => Ok(Primitive::Long(value >> (distance & 0x3f))),
+            _ => Err(WasmJVMError::VerifyError(format!("Cannot shr {:?}", self))),
+        }
+    }
+
     pub fn ushr(self: &Self, other: &Self) -> Result<Self, WasmJVMError> {
-        match (self, other) {
-            (Primitive::Int(left), Primitive::Int(right)) => Ok(Primitive::Int(((*left as u32) >> (*right as u32)) as i32)),
-            (Primitive::Long(left), Primitive::Long(right)) => Ok(Primitive::Long(((*left as u64) >> (*right as u64)) as i64)),
-            _ => unreachable!()
+        let distance = other.as_int()? as u32;
+
+        match self {
+            Primitive::Int(value) => Ok(Primitive::Int(((*value as u32) >> (distance & 0x1f)) as i32)),
+            Primitive::Long(value) => Ok(Primitive::Long(((*value as u64) >> (distance & 0x3f)) as i64)),
+            _ => Err(WasmJVMError::VerifyError(format!("Cannot ushr {:?}", self))),
+        }
+    }
+
+    pub fn as_int(self: &Self) -> Result<i32, WasmJVMError> {
+        if let Primitive::Int(value) = self.into_int()? {
+            Ok(value)
+        } else {
+            Err(WasmJVMError::VerifyError(format!("Cannot convert {:?} to int", self)))
+        }
+    }
+
+    pub fn as_long(self: &Self) -> Result<i64, WasmJVMError> {
+        if let Primitive::Long(value) = self.into_long()? {
+            Ok(value)
+        } else {
+            Err(WasmJVMError::VerifyError(format!("Cannot convert {:?} to long", self)))
+        }
+    }
+
+    pub fn as_float(self: &Self) -> Result<f32, WasmJVMError> {
+        if let Primitive::Float(value) = self.into_float()? {
+            Ok(value)
+        } else {
+            Err(WasmJVMError::VerifyError(format!("Cannot convert {:?} to float", self)))
+        }
+    }
+
+    pub fn as_double(self: &Self) -> Result<f64, WasmJVMError> {
+        if let Primitive::Double(value) = self.into_double()? {
+            Ok(value)
+        } else {
+            Err(WasmJVMError::VerifyError(format!("Cannot convert {:?} to double", self)))
+        }
+    }
+
+    pub fn size(self: &Self) -> usize {
+        match self {
+            Primitive::Long(..) | Primitive::Double(..) => 2,
+            _ => 1,
         }
     }
 
@@ -237,16 +524,24 @@ impl Primitive {
 
     pub fn neg(self: &Self) -> Result<Self, WasmJVMError> {
         match self {
-            Primitive::Int(value) => Ok(Primitive::Int(-value)),
-            Primitive::Long(value) => Ok(Primitive::Long(-value)),
+            Primitive::Int(value) => Ok(Primitive::Int(value.wrapping_neg())),
+            Primitive::Long(value) => Ok(Primitive::Long(value.wrapping_neg())),
             Primitive::Float(value) => Ok(Primitive::Float(-value)),
             Primitive::Double(value) => Ok(Primitive::Double(-value)),
-            _ => todo!(),
+            _ => Err(WasmJVMError::VerifyError(format!("Cannot negate {:?}", self))),
+        }
+    }
+
+    pub fn is_nan(self: &Self) -> bool {
+        match self {
+            Primitive::Float(value) => value.is_nan(),
+            Primitive::Double(value) => value.is_nan(),
+            _ => false,
         }
     }
 
     pub fn cmpg(self: &Self, other: &Self) -> Result<Self, WasmJVMError> {
-        if self.is_null() || other.is_null() {
+        if self.is_nan() || other.is_nan() {
             Ok(Primitive::Int(1))
         } else {
             self.cmp(other)
@@ -254,7 +549,7 @@ impl Primitive {
     }
 
     pub fn cmpl(self: &Self, other: &Self) -> Result<Self, WasmJVMError> {
-        if self.is_null() || other.is_null() {
+        if self.is_nan() || other.is_nan() {
             Ok(Primitive::Int(-1))
         } else {
             self.cmp(other)
@@ -271,7 +566,12 @@ impl Primitive {
             (Primitive::Reference(_left), Primitive::Null) => (false, false),
             (Primitive::Null, Primitive::Reference(_right)) => (false, false),
             (Primitive::Null, Primitive::Null) => (false, true),
-            _ => unreachable!(),
+            _ => {
+                return Err(WasmJVMError::VerifyError(format!(
+                    "Cannot compare {:?} and {:?}",
+                    self, other
+                )))
+            }
         };
 
         if gt {
@@ -284,14 +584,16 @@ impl Primitive {
     }
 }
 
-impl From<Constant> for Primitive {
-    fn from(constant: Constant) -> Self {
+impl TryFrom<Constant> for Primitive {
+    type Error = WasmJVMError;
+
+    fn try_from(constant: Constant) -> Result<Self, Self::Error> {
         match constant {
-            Constant::Integer(value) => Primitive::Int(value),
-            Constant::Float(value) => Primitive::Float(value),
-            Constant::Long(value) => Primitive::Long(value),
-            Constant::Double(value) => Primitive::Double(value),
-            _ => unreachable!(),
+            Constant::Integer(value) => Ok(Primitive::Int(value)),
+            Constant::Float(value) => Ok(Primitive::Float(value)),
+            Constant::Long(value) => Ok(Primitive::Long(value)),
+            Constant::Double(value) => Ok(Primitive::Double(value)),
+            constant => Err(WasmJVMError::VerifyError(format!("Cannot load constant {:?}", constant))),
         }
     }
 }
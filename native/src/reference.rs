@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
+use wasmjvm_common::WasmJVMError;
+
+use crate::{
+    async_box, register_method, Array, Global, NativeEnv, NativeInterface, Primitive,
+    RustObject, Thread, JAVA_REFERENCE, JAVA_SOFT_REFERENCE, JAVA_THREAD,
+};
+
+/// Allocations between two collections that nobody asked for.
+const COLLECTION_INTERVAL: usize = 512;
+
+/// How a class takes part in reference processing, cached per class during a collection.
+#[derive(Clone, Copy)]
+struct ReferenceKind {
+    referent_slot: usize,
+    soft: bool,
+}
+
+pub fn register(interface: &mut NativeInterface) {
+    register_method!(
+        interface,
+        async_system_gc,
+        "java/lang/System",
+        "gc",
+        vec![],
+        Type::Single(SingleType::Void)
+    );
+    register_method!(
+        interface,
+        async_reference_get_pending_reference,
+        "java/lang/ref/Reference",
+        "getPendingReference",
+        vec![],
+        Type::Single(SingleType::Object(JAVA_REFERENCE.to_string()))
+    );
+}
+
+impl Global {
+    /// Runs at a safe point between ticks, when every live reference is in a frame or a root.
+    pub fn collect_if_due(self: &mut Self) -> Result<(), WasmJVMError> {
+        if self.take_collection(COLLECTION_INTERVAL)? {
+            self.collect()?;
+        }
+
+        if self.has_pending_references()? {
+            self.wake_reference_handler()?;
+        }
+
+        Ok(())
+    }
+
+    /// Clears the referents that are no longer strongly reachable and queues their references
+    /// for the reference handler. Soft referents are only cleared once the heap runs low.
+    pub fn collect(self: &mut Self) -> Result<(), WasmJVMError> {
+        let heap = self.heap();
+        let clear_soft = heap.size() * 4 >= heap.capacity() * 3;
+
+        let mut marked = vec![false; heap.size()];
+        let mut kinds: HashMap<usize, Option<ReferenceKind>> = HashMap::new();
+        let mut discovered = Vec::new();
+        let mut stack = self.roots()?;
+
+        while let Some(index) = stack.pop() {
+            match marked.get_mut(index) {
+                Some(true) | None => continue,
+                Some(mark) => *mark = true,
+            }
+
+            let object = self.reference(index)?;
+
+            let kind = match object.class() {
+                Some(class_index) => {
+                    stack.push(class_index);
+
+                    match kinds.get(&class_index) {
+                        Some(kind) => *kind,
+                        None => {
+                            let kind = self.reference_kind(class_index)?;
+                            kinds.insert(class_index, kind);
+                            kind
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            let weak_slot = match kind {
+                Some(kind) if clear_soft || !kind.soft => {
+                    discovered.push((index, kind.referent_slot));
+                    Some(kind.referent_slot)
+                }
+                _ => None,
+            };
+
+            for (slot, field) in object.fields().iter().enumerate() {
+                if let (Primitive::Reference(field), false) = (field, weak_slot == Some(slot)) {
+                    stack.push(*field);
+                }
+            }
+
+            match object.inner() {
+                RustObject::Array(Array::Reference(elements)) => {
+                    stack.extend(elements.iter().flatten());
+                }
+                RustObject::Class(class) => {
+                    for value in class.statics.values() {
+                        if let Primitive::Reference(value) = value {
+                            stack.push(*value);
+                        }
+                    }
+                }
+                RustObject::Thread(thread) => {
+                    for frame in thread.frames() {
+                        for value in frame.local_variables().iter().chain(frame.operand_stack()) {
+                            if let Primitive::Reference(value) = value {
+                                stack.push(*value);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut pending = Vec::new();
+        for (index, referent_slot) in discovered {
+            let object = self.reference(index)?;
+
+            let is_reachable = match object.field(referent_slot)? {
+                Primitive::Reference(referent) => marked.get(referent).copied().unwrap_or(true),
+                _ => true,
+            };
+
+            if !is_reachable {
+                let reference = Primitive::Reference(index);
+
+                self.reference_p_mut(&reference)?
+                    .set_field(referent_slot, Primitive::Null)?;
+
+                if !self.field_named(&reference, "queue")?.is_null() {
+                    pending.push(index);
+                }
+            }
+        }
+
+        self.push_pending_references(pending)
+    }
+
+    fn reference_kind(self: &Self, class_index: usize) -> Result<Option<ReferenceKind>, WasmJVMError> {
+        let class = self.class(class_index)?;
+        let name = class.metadata().this_class();
+
+        if Type::from_class_name(name)?.component().is_some() || !self.is_assignable(name, JAVA_REFERENCE)? {
+            return Ok(None);
+        }
+
+        let referent_slot = class
+            .layout()
+            .iter()
+            .position(|slot| slot.class == JAVA_REFERENCE && slot.name == "referent")
+            .ok_or_else(|| WasmJVMError::NoSuchFieldError(format!("{}.referent", JAVA_REFERENCE)))?;
+
+        Ok(Some(ReferenceKind {
+            referent_slot,
+            soft: self.is_assignable(name, JAVA_SOFT_REFERENCE)?,
+        }))
+    }
+
+    /// Starts the reference handler on the pending references unless it is already busy.
+    fn wake_reference_handler(self: &mut Self) -> Result<(), WasmJVMError> {
+        let thread_index = match self.reference_handler()? {
+            Some(thread_index) => thread_index,
+            None => {
+                let thread = Thread::new(self.clone(), "Reference Handler", 0);
+                let thread_index = self.new_rust_instance(JAVA_THREAD, RustObject::Thread(thread))?;
+                self.set_reference_handler(thread_index)?;
+
+                thread_index
+            }
+        };
+
+        let thread = self.thread_mut(thread_index)?;
+
+        if thread.frames().is_empty() {
+            thread.invoke_static(
+                MethodRef::new(
+                    JAVA_REFERENCE.to_string(),
+                    "processPendingReferences".to_string(),
+                    Descriptor::void(),
+                ),
+                Vec::new(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+async_box!(async_system_gc, system_gc);
+async fn system_gc(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    env.global_mut().request_collection()?;
+
+    Ok(Primitive::Void)
+}
+
+async_box!(async_reference_get_pending_reference, reference_get_pending_reference);
+async fn reference_get_pending_reference(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    match env.global_mut().pop_pending_reference()? {
+        Some(index) => Ok(Primitive::Reference(index)),
+        None => Ok(Primitive::Null),
+    }
+}
@@ -0,0 +1,973 @@
+use wasmjvm_class::{
+    AccessFlagType, AccessFlags, Descriptor, FieldRef, MethodRef, SingleType, Type,
+    WithAccessFlags, WithDescriptor, WithFields, WithInterfaces, WithMethods,
+};
+use wasmjvm_common::WasmJVMError;
+
+use crate::{
+    async_box, register_method, Array, Global, NativeEnv, NativeInterface, Primitive,
+    JAVA_CLASS, JAVA_CONSTRUCTOR, JAVA_FIELD, JAVA_METHOD, JAVA_OBJECT, JAVA_STRING,
+};
+
+const CLASS_MODIFIERS: u16 = 0x0e1f;
+const METHOD_MODIFIERS: u16 = 0x0d3f;
+const FIELD_MODIFIERS: u16 = 0x00df;
+const CONSTRUCTOR_MODIFIERS: u16 = 0x0007;
+
+const MODIFIER_NAMES: [(i32, &str); 12] = [
+    (0x0001, "public"),
+    (0x0004, "protected"),
+    (0x0002, "private"),
+    (0x0400, "abstract"),
+    (0x0008, "static"),
+    (0x0010, "final"),
+    (0x0080, "transient"),
+    (0x0040, "volatile"),
+    (0x0020, "synchronized"),
+    (0x0100, "native"),
+    (0x0800, "strictfp"),
+    (0x0200, "interface"),
+];
+
+fn object_type(name: &str) -> Type {
+    Type::Single(SingleType::Object(name.to_string()))
+}
+
+pub fn register(interface: &mut NativeInterface) {
+    register_method!(
+        interface,
+        async_class_for_name,
+        "java/lang/Class",
+        "forName0",
+        vec![object_type(JAVA_STRING)],
+        object_type(JAVA_CLASS)
+    );
+    register_method!(
+        interface,
+        async_class_get_primitive_class,
+        "java/lang/Class",
+        "getPrimitiveClass",
+        vec![object_type(JAVA_STRING)],
+        object_type(JAVA_CLASS)
+    );
+    register_method!(
+        interface,
+        async_class_is_primitive,
+        "java/lang/Class",
+        "isPrimitive",
+        vec![],
+        Type::Single(SingleType::Boolean)
+    );
+    register_method!(
+        interface,
+        async_class_is_array,
+        "java/lang/Class",
+        "isArray",
+        vec![],
+        Type::Single(SingleType::Boolean)
+    );
+    register_method!(
+        interface,
+        async_class_get_component_type,
+        "java/lang/Class",
+        "getComponentType",
+        vec![],
+        object_type(JAVA_CLASS)
+    );
+    register_method!(
+        interface,
+        async_class_get_simple_name,
+        "java/lang/Class",
+        "getSimpleName",
+        vec![],
+        object_type(JAVA_STRING)
+    );
+    register_method!(
+        interface,
+        async_class_initialize,
+        "java/lang/Class",
+        "initialize",
+        vec![],
+        Type::Single(SingleType::Void)
+    );
+    register_method!(
+        interface,
+        async_class_get_superclass,
+        "java/lang/Class",
+        "getSuperclass",
+        vec![],
+        object_type(JAVA_CLASS)
+    );
+    register_method!(
+        interface,
+        async_class_get_interfaces,
+        "java/lang/Class",
+        "getInterfaces",
+        vec![],
+        object_type(JAVA_CLASS).array()
+    );
+    register_method!(
+        interface,
+        async_class_is_instance,
+        "java/lang/Class",
+        "isInstance",
+        vec![object_type(JAVA_OBJECT)],
+        Type::Single(SingleType::Boolean)
+    );
+    register_method!(
+        interface,
+        async_class_is_assignable_from,
+        "java/lang/Class",
+        "isAssignableFrom",
+        vec![object_type(JAVA_CLASS)],
+        Type::Single(SingleType::Boolean)
+    );
+    register_method!(
+        interface,
+        async_class_get_modifiers,
+        "java/lang/Class",
+        "getModifiers",
+        vec![],
+        Type::Single(SingleType::Int)
+    );
+    register_method!(
+        interface,
+        async_class_get_declared_methods,
+        "java/lang/Class",
+        "getDeclaredMethods",
+        vec![],
+        object_type(JAVA_METHOD).array()
+    );
+    register_method!(
+        interface,
+        async_class_get_declared_fields,
+        "java/lang/Class",
+        "getDeclaredFields",
+        vec![],
+        object_type(JAVA_FIELD).array()
+    );
+    register_method!(
+        interface,
+        async_class_get_declared_constructors,
+        "java/lang/Class",
+        "getDeclaredConstructors",
+        vec![],
+        object_type(JAVA_CONSTRUCTOR).array()
+    );
+    register_method!(
+        interface,
+        async_modifier_to_string,
+        "java/lang/reflect/Modifier",
+        "toString",
+        vec![Type::Single(SingleType::Int)],
+        object_type(JAVA_STRING)
+    );
+    register_method!(
+        interface,
+        async_accessible_check_access,
+        "java/lang/reflect/AccessibleObject",
+        "checkAccess",
+        vec![object_type(JAVA_CLASS), Type::Single(SingleType::Int)],
+        object_type(JAVA_STRING)
+    );
+
+    for class in [JAVA_METHOD, JAVA_CONSTRUCTOR] {
+        register_method!(
+            interface,
+            async_executable_get_parameter_count,
+            class,
+            "getParameterCount",
+            vec![],
+            Type::Single(SingleType::Int)
+        );
+        register_method!(
+            interface,
+            async_executable_check_arguments,
+            class,
+            "checkArguments",
+            vec![object_type(JAVA_OBJECT).array()],
+            object_type(JAVA_STRING)
+        );
+    }
+
+    for class in [JAVA_METHOD, JAVA_CONSTRUCTOR] {
+        register_method!(
+            interface,
+            async_executable_get_parameter_types,
+            class,
+            "getParameterTypes",
+            vec![],
+            object_type(JAVA_CLASS).array()
+        );
+    }
+
+    register_method!(
+        interface,
+        async_member_get_type,
+        "java/lang/reflect/Method",
+        "getReturnType",
+        vec![],
+        object_type(JAVA_CLASS)
+    );
+    register_method!(
+        interface,
+        async_member_get_type,
+        "java/lang/reflect/Field",
+        "getType",
+        vec![],
+        object_type(JAVA_CLASS)
+    );
+
+    for class in [JAVA_METHOD, JAVA_CONSTRUCTOR, JAVA_FIELD] {
+        register_method!(
+            interface,
+            async_member_to_string,
+            class,
+            "toString",
+            vec![],
+            object_type(JAVA_STRING)
+        );
+    }
+
+    register_method!(
+        interface,
+        async_method_invoke,
+        "java/lang/reflect/Method",
+        "invoke0",
+        vec![object_type(JAVA_OBJECT), object_type(JAVA_OBJECT).array()],
+        object_type(JAVA_OBJECT)
+    );
+    register_method!(
+        interface,
+        async_constructor_allocate,
+        "java/lang/reflect/Constructor",
+        "allocate",
+        vec![],
+        object_type(JAVA_OBJECT)
+    );
+    register_method!(
+        interface,
+        async_constructor_construct,
+        "java/lang/reflect/Constructor",
+        "construct",
+        vec![object_type(JAVA_OBJECT), object_type(JAVA_OBJECT).array()],
+        Type::Single(SingleType::Void)
+    );
+    register_method!(
+        interface,
+        async_field_get,
+        "java/lang/reflect/Field",
+        "get0",
+        vec![object_type(JAVA_OBJECT)],
+        object_type(JAVA_OBJECT)
+    );
+    register_method!(
+        interface,
+        async_field_set,
+        "java/lang/reflect/Field",
+        "set0",
+        vec![object_type(JAVA_OBJECT), object_type(JAVA_OBJECT)],
+        Type::Single(SingleType::Boolean)
+    );
+}
+
+/// A reflected method, constructor or field as stored in its Java mirror.
+struct Member {
+    class_index: usize,
+    name: String,
+    descriptor: Descriptor,
+    modifiers: i32,
+}
+
+impl Member {
+    fn from_mirror(global: &Global, this: &Primitive) -> Result<Self, WasmJVMError> {
+        let class_index = match global.field_named(this, "clazz")? {
+            Primitive::Reference(class_index) => class_index,
+            _ => return Err(WasmJVMError::NullPointerException(format!("Member has no class"))),
+        };
+
+        let name = if global.object_class_name(this)? == JAVA_CONSTRUCTOR {
+            "<init>".to_string()
+        } else {
+            global.java_string(&global.field_named(this, "name")?)?.unwrap_or_default()
+        };
+
+        let descriptor = global
+            .java_string(&global.field_named(this, "descriptor")?)?
+            .unwrap_or_default();
+
+        Ok(Self {
+            class_index,
+            name,
+            descriptor: Descriptor::from_str(&descriptor)?,
+            modifiers: global.field_named(this, "modifiers")?.as_int()?,
+        })
+    }
+
+    fn class_name(self: &Self, global: &Global) -> Result<String, WasmJVMError> {
+        Ok(global.class(self.class_index)?.metadata().this_class().to_string())
+    }
+
+    fn is_static(self: &Self) -> bool {
+        self.modifiers & AccessFlagType::Static as i32 != 0
+    }
+
+    fn method_ref(self: &Self, global: &Global) -> Result<MethodRef, WasmJVMError> {
+        Ok(MethodRef::new(self.class_name(global)?, self.name.clone(), self.descriptor.clone()))
+    }
+
+    fn field_ref(self: &Self, global: &Global) -> Result<FieldRef, WasmJVMError> {
+        Ok(FieldRef {
+            class: self.class_name(global)?,
+            name: self.name.clone(),
+            descriptor: self.descriptor.clone(),
+        })
+    }
+}
+
+fn new_member(
+    global: &mut Global,
+    mirror: &str,
+    class_index: usize,
+    name: &str,
+    descriptor: String,
+    modifiers: u16,
+) -> Result<Option<usize>, WasmJVMError> {
+    let member = Primitive::Reference(global.new_instance(mirror)?);
+
+    global.field_named_set(&member, "clazz", Primitive::Reference(class_index))?;
+    if mirror != JAVA_CONSTRUCTOR {
+        let name = Primitive::Reference(global.new_java_string(name.to_string())?);
+        global.field_named_set(&member, "name", name)?;
+    }
+
+    let descriptor = Primitive::Reference(global.new_java_string(descriptor)?);
+    global.field_named_set(&member, "descriptor", descriptor)?;
+    global.field_named_set(&member, "modifiers", Primitive::Int(modifiers as i32))?;
+
+    if let Primitive::Reference(index) = member {
+        Ok(Some(index))
+    } else {
+        unreachable!()
+    }
+}
+
+fn new_reference_array(
+    global: &mut Global,
+    component: &str,
+    references: Vec<Option<usize>>,
+) -> Result<Primitive, WasmJVMError> {
+    let index = global.new_array_from(&object_type(component), Array::Reference(references))?;
+
+    Ok(Primitive::Reference(index))
+}
+
+fn array_arguments(
+    global: &Global,
+    descriptor: &Descriptor,
+    arguments: &Primitive,
+) -> Result<Vec<Primitive>, WasmJVMError> {
+    let values: Vec<Primitive> = if arguments.is_null() {
+        Vec::new()
+    } else if let Array::Reference(references) = global.array(arguments)? {
+        references
+            .iter()
+            .map(|reference| reference.map_or(Primitive::Null, Primitive::Reference))
+            .collect()
+    } else {
+        return Err(WasmJVMError::IllegalArgumentException(format!("argument type mismatch")));
+    };
+
+    if values.len() != descriptor.parameters().len() {
+        return Err(WasmJVMError::IllegalArgumentException(format!(
+            "wrong number of arguments: {} expected: {}",
+            values.len(),
+            descriptor.parameters().len()
+        )));
+    }
+
+    descriptor
+        .parameters()
+        .zip(values.iter())
+        .map(|(r#type, value)| global.unbox_primitive(r#type, value))
+        .collect()
+}
+
+fn modifier_string(modifiers: i32) -> String {
+    MODIFIER_NAMES
+        .iter()
+        .filter(|(bit, _)| modifiers & bit != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+pub(crate) fn type_name(r#type: &Type) -> String {
+    match r#type {
+        Type::Array(single, dimensions) => {
+            format!("{}{}", type_name(&Type::Single(single.clone())), "[]".repeat(*dimensions))
+        }
+        Type::Single(single) => single.name().replace('/', "."),
+    }
+}
+
+fn simple_type_name(r#type: &Type) -> String {
+    match r#type {
+        Type::Array(single, dimensions) => {
+            format!("{}{}", simple_type_name(&Type::Single(single.clone())), "[]".repeat(*dimensions))
+        }
+        Type::Single(SingleType::Object(name)) => {
+            let start = name.rfind(|c| c == '/' || c == '$').map_or(0, |index| index + 1);
+            name[start..].to_string()
+        }
+        Type::Single(single) => single.name(),
+    }
+}
+
+/// The class of the innermost frame that is not part of the reflection machinery.
+fn caller_class(global: &Global) -> Result<Option<String>, WasmJVMError> {
+    Ok(global
+        .current_thread()?
+        .frames()
+        .iter()
+        .rev()
+        .map(|frame| frame.method_ref().class.clone())
+        .find(|class| !class.starts_with("java/lang/reflect/") && class != JAVA_CLASS))
+}
+
+async_box!(async_class_for_name, class_for_name);
+async fn class_for_name(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [name, ..] = &variables[..] {
+        let name = match env.string(name)? {
+            Some(name) => name.replace('.', "/"),
+            None => return Ok(Primitive::Null),
+        };
+
+        if SingleType::primitive(&name).is_some() {
+            return Ok(Primitive::Null);
+        }
+
+        let global = env.global_mut();
+        let class_index = if name.starts_with('[') {
+            Type::from_class_name(&name).and_then(|r#type| global.array_class(&r#type))
+        } else {
+            global.resolve_class(&name)
+        };
+
+        match class_index {
+            Ok(class_index) => Ok(Primitive::Reference(class_index)),
+            Err(_) => Ok(Primitive::Null),
+        }
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_get_primitive_class, class_get_primitive_class);
+async fn class_get_primitive_class(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [name, ..] = &variables[..] {
+        let name = env.string(name)?.unwrap_or_default();
+
+        match SingleType::primitive(&name) {
+            Some(primitive) => Ok(Primitive::Reference(env.global_mut().primitive_class(&primitive)?)),
+            None => Ok(Primitive::Null),
+        }
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_is_primitive, class_is_primitive);
+async fn class_is_primitive(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        Ok(Primitive::Boolean(env.global().class(class_index)?.metadata().is_primitive()))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_is_array, class_is_array);
+async fn class_is_array(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        Ok(Primitive::Boolean(env.global().class(class_index)?.metadata().is_array()))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_get_component_type, class_get_component_type);
+async fn class_get_component_type(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        let global = env.global_mut();
+        let name = global.class(class_index)?.metadata().this_class().to_string();
+
+        match Type::from_class_name(&name)?.component() {
+            Some(component) => Ok(Primitive::Reference(global.type_class(&component)?)),
+            None => Ok(Primitive::Null),
+        }
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_get_simple_name, class_get_simple_name);
+async fn class_get_simple_name(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        let name = env.global().class(class_index)?.metadata().this_class().to_string();
+        let simple_name = simple_type_name(&Type::from_class_name(&name)?);
+
+        Ok(Primitive::Reference(env.new_string(simple_name)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_initialize, class_initialize);
+async fn class_initialize(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        env.initialize(class_index)?;
+
+        Ok(Primitive::Void)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_get_superclass, class_get_superclass);
+async fn class_get_superclass(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        let metadata = env.global().class(class_index)?.metadata();
+
+        if metadata.access_flags().has_type(&AccessFlagType::Interface) {
+            return Ok(Primitive::Null);
+        }
+
+        match metadata.super_class().clone() {
+            Some(super_class) => {
+                Ok(Primitive::Reference(env.global_mut().resolve_class(&super_class)?))
+            }
+            None => Ok(Primitive::Null),
+        }
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_get_interfaces, class_get_interfaces);
+async fn class_get_interfaces(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        let global = env.global_mut();
+        let names: Vec<String> = global
+            .class(class_index)?
+            .metadata()
+            .interfaces()
+            .map(|interfaces| interfaces.map(|interface| interface.name().to_string()).collect())
+            .unwrap_or_default();
+
+        let interfaces = names
+            .iter()
+            .map(|name| global.resolve_class(name).map(Some))
+            .collect::<Result<Vec<Option<usize>>, WasmJVMError>>()?;
+
+        new_reference_array(global, JAVA_CLASS, interfaces)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_is_instance, class_is_instance);
+async fn class_is_instance(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), object, ..] = &env.variables()[..] {
+        let global = env.global();
+        let class_name = global.class(*class_index)?.metadata().this_class();
+
+        Ok(Primitive::Boolean(global.is_instance(object, class_name)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_is_assignable_from, class_is_assignable_from);
+async fn class_is_assignable_from(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), Primitive::Reference(other_index), ..] = env.variables()[..] {
+        let global = env.global();
+        let class_name = global.class(class_index)?.metadata().this_class();
+        let other_name = global.class(other_index)?.metadata().this_class();
+
+        Ok(Primitive::Boolean(global.is_assignable(other_name, class_name)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_get_modifiers, class_get_modifiers);
+async fn class_get_modifiers(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        let metadata = env.global().class(class_index)?.metadata();
+
+        Ok(Primitive::Int((metadata.access_flags().bits() & CLASS_MODIFIERS) as i32))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_get_declared_methods, class_get_declared_methods);
+async fn class_get_declared_methods(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    class_get_declared_executables(env, false)
+}
+
+async_box!(async_class_get_declared_constructors, class_get_declared_constructors);
+async fn class_get_declared_constructors(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    class_get_declared_executables(env, true)
+}
+
+fn class_get_declared_executables(env: &mut NativeEnv, constructors: bool) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        let global = env.global_mut();
+        let methods: Vec<(String, String, u16)> = global
+            .class(class_index)?
+            .metadata()
+            .methods()
+            .map(|methods| {
+                methods
+                    .filter(|method| (method.name() == "<init>") == constructors)
+                    .filter(|method| method.name() != "<clinit>")
+                    .map(|method| {
+                        (
+                            method.name().to_string(),
+                            method.descriptor().descriptor(),
+                            method.access_flags().bits(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (mirror, mask) = if constructors {
+            (JAVA_CONSTRUCTOR, CONSTRUCTOR_MODIFIERS)
+        } else {
+            (JAVA_METHOD, METHOD_MODIFIERS)
+        };
+
+        let members = methods
+            .into_iter()
+            .map(|(name, descriptor, flags)| {
+                new_member(global, mirror, class_index, &name, descriptor, flags & mask)
+            })
+            .collect::<Result<Vec<Option<usize>>, WasmJVMError>>()?;
+
+        new_reference_array(global, mirror, members)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_class_get_declared_fields, class_get_declared_fields);
+async fn class_get_declared_fields(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), ..] = env.variables()[..] {
+        let global = env.global_mut();
+        let fields: Vec<(String, String, u16)> = global
+            .class(class_index)?
+            .metadata()
+            .fields()
+            .map(|fields| {
+                fields
+                    .map(|field| {
+                        (
+                            field.name().to_string(),
+                            field.r#type().descriptor(),
+                            field.access_flags().bits(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let members = fields
+            .into_iter()
+            .map(|(name, descriptor, flags)| {
+                new_member(global, JAVA_FIELD, class_index, &name, descriptor, flags & FIELD_MODIFIERS)
+            })
+            .collect::<Result<Vec<Option<usize>>, WasmJVMError>>()?;
+
+        new_reference_array(global, JAVA_FIELD, members)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_modifier_to_string, modifier_to_string);
+async fn modifier_to_string(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Int(modifiers), ..] = env.variables()[..] {
+        Ok(Primitive::Reference(env.new_string(modifier_string(modifiers))?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_accessible_check_access, accessible_check_access);
+async fn accessible_check_access(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(class_index), Primitive::Int(modifiers), ..] = env.variables()[..] {
+        let global = env.global_mut();
+        let declaring = global.class(class_index)?.metadata().this_class().to_string();
+        let caller = caller_class(global)?.unwrap_or(declaring.clone());
+
+        let flags: Vec<AccessFlagType> = [
+            AccessFlagType::Public,
+            AccessFlagType::Private,
+            AccessFlagType::Protected,
+        ]
+        .into_iter()
+        .filter(|flag| modifiers & *flag as i32 != 0)
+        .collect();
+
+        if global.can_access(&caller, &declaring, &AccessFlags::new(&flags))? {
+            return Ok(Primitive::Null);
+        }
+
+        let message = format!(
+            "class {} cannot access a member of class {} with modifiers \"{}\"",
+            caller.replace('/', "."),
+            declaring.replace('/', "."),
+            modifier_string(modifiers)
+        );
+
+        Ok(Primitive::Reference(env.new_string(message)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_executable_get_parameter_count, executable_get_parameter_count);
+async fn executable_get_parameter_count(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [this, ..] = &env.variables()[..] {
+        let member = Member::from_mirror(env.global(), this)?;
+
+        Ok(Primitive::Int(member.descriptor.parameters().len() as i32))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_executable_check_arguments, executable_check_arguments);
+async fn executable_check_arguments(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, arguments, ..] = &variables[..] {
+        let member = Member::from_mirror(env.global(), this)?;
+
+        match array_arguments(env.global(), &member.descriptor, arguments) {
+            Ok(_) => Ok(Primitive::Null),
+            Err(error) => {
+                let message = error.message().unwrap_or_default();
+                Ok(Primitive::Reference(env.new_string(message)?))
+            }
+        }
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_executable_get_parameter_types, executable_get_parameter_types);
+async fn executable_get_parameter_types(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, ..] = &variables[..] {
+        let global = env.global_mut();
+        let member = Member::from_mirror(global, this)?;
+
+        let parameters = member
+            .descriptor
+            .parameters()
+            .map(|parameter| global.type_class(parameter).map(Some))
+            .collect::<Result<Vec<Option<usize>>, WasmJVMError>>()?;
+
+        new_reference_array(global, JAVA_CLASS, parameters)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_member_get_type, member_get_type);
+async fn member_get_type(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, ..] = &variables[..] {
+        let global = env.global_mut();
+        let member = Member::from_mirror(global, this)?;
+
+        Ok(Primitive::Reference(global.type_class(member.descriptor.output())?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_member_to_string, member_to_string);
+async fn member_to_string(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, ..] = &variables[..] {
+        let global = env.global();
+        let member = Member::from_mirror(global, this)?;
+        let class_name = member.class_name(global)?.replace('/', ".");
+        let parameters: Vec<String> = member.descriptor.parameters().map(type_name).collect();
+
+        let signature = match member.name.as_str() {
+            "<init>" => format!("{}({})", class_name, parameters.join(",")),
+            name if global.object_class_name(this)? == JAVA_FIELD => {
+                format!("{} {}.{}", type_name(member.descriptor.output()), class_name, name)
+            }
+            name => format!(
+                "{} {}.{}({})",
+                type_name(member.descriptor.output()),
+                class_name,
+                name,
+                parameters.join(",")
+            ),
+        };
+
+        let modifiers = modifier_string(member.modifiers);
+        let string = if modifiers.is_empty() {
+            signature
+        } else {
+            format!("{} {}", modifiers, signature)
+        };
+
+        Ok(Primitive::Reference(env.new_string(string)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_method_invoke, method_invoke);
+async fn method_invoke(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, receiver, arguments, ..] = &variables[..] {
+        let global = env.global();
+        let member = Member::from_mirror(global, this)?;
+        let arguments = array_arguments(global, &member.descriptor, arguments)?;
+        let mut method_ref = member.method_ref(global)?;
+
+        if member.is_static() {
+            if env.initialize(member.class_index)? {
+                env.invoke(method_ref, None, arguments, true);
+            }
+        } else {
+            if member.modifiers & AccessFlagType::Private as i32 == 0 {
+                method_ref.class = global.dispatch_class(receiver, &method_ref)?;
+            }
+
+            env.invoke(method_ref, Some(receiver.clone()), arguments, true);
+        }
+
+        Ok(Primitive::Void)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_constructor_allocate, constructor_allocate);
+async fn constructor_allocate(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, ..] = &variables[..] {
+        let member = Member::from_mirror(env.global(), this)?;
+
+        if !env.initialize(member.class_index)? {
+            return Ok(Primitive::Void);
+        }
+
+        let class_name = member.class_name(env.global())?;
+
+        Ok(Primitive::Reference(env.global_mut().new_instance(&class_name)?))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_constructor_construct, constructor_construct);
+async fn constructor_construct(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, instance, arguments, ..] = &variables[..] {
+        let global = env.global();
+        let member = Member::from_mirror(global, this)?;
+        let arguments = array_arguments(global, &member.descriptor, arguments)?;
+        let method_ref = member.method_ref(global)?;
+
+        env.invoke(method_ref, Some(instance.clone()), arguments, false);
+
+        Ok(Primitive::Void)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_field_get, field_get);
+async fn field_get(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, receiver, ..] = &variables[..] {
+        let member = Member::from_mirror(env.global(), this)?;
+
+        let value = if member.is_static() {
+            if !env.initialize(member.class_index)? {
+                return Ok(Primitive::Void);
+            }
+
+            env.global()
+                .class(member.class_index)?
+                .statics
+                .get(&member.name)
+                .cloned()
+                .ok_or_else(|| WasmJVMError::NoSuchFieldError(member.name.clone()))?
+        } else {
+            let field_ref = member.field_ref(env.global())?;
+
+            env.global().field(receiver, &field_ref)?
+        };
+
+        env.global_mut()
+            .box_primitive(member.descriptor.output(), value)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_field_set, field_set);
+async fn field_set(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let variables = env.variables().clone();
+
+    if let [this, receiver, value, ..] = &variables[..] {
+        let member = Member::from_mirror(env.global(), this)?;
+
+        let value = match env.global().unbox_primitive(member.descriptor.output(), value) {
+            Ok(value) => value,
+            Err(_) => return Ok(Primitive::Boolean(false)),
+        };
+
+        if member.is_static() {
+            if !env.initialize(member.class_index)? {
+                return Ok(Primitive::Void);
+            }
+
+            let class = env.global_mut().class_mut(member.class_index)?;
+            class.statics.insert(member.name.clone(), value);
+        } else {
+            let field_ref = member.field_ref(env.global())?;
+
+            env.global_mut()
+                .field_set(receiver.clone(), &field_ref, value)?;
+        }
+
+        Ok(Primitive::Boolean(true))
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
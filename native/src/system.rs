@@ -0,0 +1,245 @@
+use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
+use wasmjvm_common::WasmJVMError;
+
+use crate::{
+    async_box, native, register_method, Array, CallOutcome, JString, NativeEnv, NativeInterface, Primitive, JAVA_OBJECT,
+    JAVA_STRING,
+};
+
+const JAVA_PRIVILEGED_ACTION: &str = "java/security/PrivilegedAction";
+
+/// Properties every front end starts with. Front ends overwrite the host dependent ones, like
+/// `os.name` and `user.dir`, through `VM::set_property` before the VM boots.
+pub const DEFAULT_PROPERTIES: [(&str, &str); 17] = [
+    ("java.version", "1.8.0"),
+    ("java.vendor", "WasmJVM"),
+    ("java.vm.name", "WasmJVM"),
+    ("java.vm.vendor", "WasmJVM"),
+    ("java.specification.version", "1.8"),
+    ("java.class.version", "52.0"),
+    ("java.class.path", ""),
+    ("java.io.tmpdir", "/tmp"),
+    ("os.name", "Unknown"),
+    ("os.arch", "unknown"),
+    ("os.version", "unknown"),
+    ("file.separator", "/"),
+    ("path.separator", ":"),
+    ("line.separator", "\n"),
+    ("file.encoding", "UTF-8"),
+    ("user.dir", "/"),
+    ("user.home", "/"),
+];
+
+/// The heap hands out one slot per object, so `Runtime` reports slots at a nominal size to keep
+/// the numbers in bytes.
+const SLOT_BYTES: i64 = 64;
+
+pub fn register(interface: &mut NativeInterface) {
+    let string = || Type::Single(SingleType::Object(JAVA_STRING.to_string()));
+
+    register_method!(
+        interface,
+        async_system_get_property,
+        "java/lang/System",
+        "getPropertyInternal",
+        vec![string()],
+        string()
+    );
+    register_method!(
+        interface,
+        async_system_set_property,
+        "java/lang/System",
+        "setPropertyInternal",
+        vec![string(), string()],
+        string()
+    );
+    register_method!(
+        interface,
+        async_system_property_names,
+        "java/lang/System",
+        "propertyNames",
+        vec![],
+        Type::Array(SingleType::Object(JAVA_STRING.to_string()), 1)
+    );
+    register_method!(
+        interface,
+        async_system_getenv,
+        "java/lang/System",
+        "getenv",
+        vec![string()],
+        string()
+    );
+    register_method!(
+        interface,
+        async_system_line_separator,
+        "java/lang/System",
+        "lineSeparator",
+        vec![],
+        string()
+    );
+    register_method!(
+        interface,
+        async_shutdown_halt,
+        "java/lang/Shutdown",
+        "halt",
+        vec![Type::Single(SingleType::Int)],
+        Type::Single(SingleType::Void)
+    );
+    register_method!(
+        interface,
+        async_runtime_total_memory,
+        "java/lang/Runtime",
+        "totalMemory",
+        vec![],
+        Type::Single(SingleType::Long)
+    );
+    register_method!(
+        interface,
+        async_runtime_max_memory,
+        "java/lang/Runtime",
+        "maxMemory",
+        vec![],
+        Type::Single(SingleType::Long)
+    );
+    register_method!(
+        interface,
+        async_runtime_free_memory,
+        "java/lang/Runtime",
+        "freeMemory",
+        vec![],
+        Type::Single(SingleType::Long)
+    );
+    register_method!(
+        interface,
+        async_access_controller_do_privileged,
+        "java/security/AccessController",
+        "doPrivileged",
+        vec![Type::Single(SingleType::Object(JAVA_PRIVILEGED_ACTION.to_string()))],
+        Type::Single(SingleType::Object(JAVA_OBJECT.to_string()))
+    );
+}
+
+fn optional_string(env: &mut NativeEnv, value: Option<String>) -> Result<Primitive, WasmJVMError> {
+    match value {
+        Some(value) => Ok(Primitive::Reference(env.global_mut().new_java_string(value)?)),
+        None => Ok(Primitive::Null),
+    }
+}
+
+async_box!(async_system_get_property, system_get_property);
+async fn system_get_property(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [key] = &env.variables()[..] {
+        if let Some(key) = env.global().java_string(key)? {
+            let value = env.global().property(&key)?;
+
+            return optional_string(env, value);
+        }
+    }
+
+    Err(env.invalid_arguments())
+}
+
+async_box!(async_system_set_property, system_set_property);
+async fn system_set_property(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [key, value] = &env.variables()[..] {
+        if let (Some(key), Some(value)) = (env.global().java_string(key)?, env.global().java_string(value)?) {
+            let previous = env.global_mut().set_property(key, value)?;
+
+            return optional_string(env, previous);
+        }
+    }
+
+    Err(env.invalid_arguments())
+}
+
+async_box!(async_system_property_names, system_property_names);
+async fn system_property_names(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let mut names = Vec::new();
+
+    for key in env.global().property_keys()? {
+        names.push(Some(env.global_mut().new_java_string(key)?));
+    }
+
+    let component = Type::Single(SingleType::Object(JAVA_STRING.to_string()));
+
+    Ok(Primitive::Reference(env.global_mut().new_array_from(&component, Array::Reference(names))?))
+}
+
+async_box!(async_system_getenv, system_getenv);
+async fn system_getenv(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [name] = &env.variables()[..] {
+        if let Some(name) = env.global().java_string(name)? {
+            let value = env.global().environment_variable(&name)?;
+
+            return optional_string(env, value);
+        } else {
+            return Err(WasmJVMError::NullPointerException(format!("name")));
+        }
+    }
+
+    Err(env.invalid_arguments())
+}
+
+async_box!(async_system_line_separator, system_line_separator);
+async fn system_line_separator(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let separator = env.global().property("line.separator")?;
+
+    optional_string(env, separator)
+}
+
+#[native("java/lang/System", "loadLibrary")]
+fn system_load_library(env: &mut NativeEnv, name: JString) -> Result<(), WasmJVMError> {
+    if !env.global_mut().native_mut()?.link(&name.0)? {
+        env.throw_new("java/lang/UnsatisfiedLinkError", &format!("no {} in java.library.path", name.0))?;
+    }
+
+    Ok(())
+}
+
+async_box!(async_shutdown_halt, shutdown_halt);
+async fn shutdown_halt(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Int(status)] = env.variables()[..] {
+        env.global_mut().halt(status)?;
+
+        Ok(Primitive::Void)
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
+
+async_box!(async_runtime_total_memory, runtime_total_memory);
+async fn runtime_total_memory(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Long(env.global().heap().capacity() as i64 * SLOT_BYTES))
+}
+
+async_box!(async_runtime_max_memory, runtime_max_memory);
+async fn runtime_max_memory(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Long(env.global().heap().capacity() as i64 * SLOT_BYTES))
+}
+
+async_box!(async_runtime_free_memory, runtime_free_memory);
+async fn runtime_free_memory(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    let heap = env.global().heap();
+
+    Ok(Primitive::Long(heap.capacity().saturating_sub(heap.size()) as i64 * SLOT_BYTES))
+}
+
+async_box!(async_access_controller_do_privileged, access_controller_do_privileged);
+async fn access_controller_do_privileged(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [action] = &env.variables().clone()[..] {
+        let run = MethodRef::new(
+            JAVA_PRIVILEGED_ACTION.to_string(),
+            "run".to_string(),
+            Descriptor::new(vec![], Type::Single(SingleType::Object(JAVA_OBJECT.to_string()))),
+        );
+
+        match env.call(run, Some(action.clone()), Vec::new()).await? {
+            CallOutcome::Return(value) => Ok(value),
+            CallOutcome::Throw(throwable) => env.throw(throwable),
+            // The VM ends the program once this tick is over.
+            CallOutcome::Exit(_) => Ok(Primitive::Null),
+        }
+    } else {
+        Err(env.invalid_arguments())
+    }
+}
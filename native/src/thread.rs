@@ -1,9 +1,10 @@
-use std::collections::HashSet;
-
-use crate::{Global, Object, OpCode, Primitive, RustObject};
+use crate::{
+    Array, ClassState, Global, NativeContinuation, Object, OpCode, Primitive, RustObject,
+    JAVA_ERROR, JAVA_EXCEPTION_IN_INITIALIZER_ERROR, JAVA_NULL_POINTER_EXCEPTION,
+};
 use wasmjvm_class::{
-    AccessFlagType, Constant, Descriptor, ExceptionEntry, MethodRef, SingleType, Type,
-    WithAccessFlags, WithAttributes, WithInterfaces,
+    AccessFlagType, AttributeBody, Constant, Descriptor, MethodRef, Type,
+    WithAccessFlags, WithAttributes,
 };
 use wasmjvm_common::WasmJVMError;
 
@@ -13,8 +14,7 @@ pub struct Frame {
     method_ref: MethodRef,
     local_variables: Vec<Primitive>,
     operand_stack: Vec<Primitive>,
-    throw: Option<Primitive>,
-    throw_entries: Vec<ExceptionEntry>,
+    boxed: bool,
 }
 
 impl Frame {
@@ -27,30 +27,32 @@ impl Frame {
             pc: 0usize,
             local_variables,
             operand_stack: Vec::new(),
-            throw: None,
-            throw_entries: Vec::new(),
+            boxed: false,
         })
     }
 
+    /// Whether the result is boxed on return, for calls made through reflection.
+    pub fn boxed(self: &Self) -> bool {
+        self.boxed
+    }
+
+    pub fn set_boxed(self: &mut Self, boxed: bool) {
+        self.boxed = boxed;
+    }
+
+    pub fn method_ref(self: &Self) -> &MethodRef {
+        &self.method_ref
+    }
+
     pub fn operand_stack(self: &Self) -> &Vec<Primitive> {
         &self.operand_stack
     }
 
-    pub fn all_mut(
-        self: &mut Self,
-    ) -> (
-        &mut usize,
-        &mut Vec<Primitive>,
-        &mut Vec<Primitive>,
-        &mut Option<Primitive>,
-        &mut Vec<ExceptionEntry>,
-    ) {
+    pub fn all_mut(self: &mut Self) -> (&mut usize, &mut Vec<Primitive>, &mut Vec<Primitive>) {
         (
             &mut self.pc,
             &mut self.operand_stack,
             &mut self.local_variables,
-            &mut self.throw,
-            &mut self.throw_entries,
         )
     }
 
@@ -58,7 +60,7 @@ impl Frame {
         &mut self.operand_stack
     }
 
-    pub fn local_variables(self: &mut Self) -> &Vec<Primitive> {
+    pub fn local_variables(self: &Self) -> &Vec<Primitive> {
         &self.local_variables
     }
 
@@ -66,8 +68,18 @@ impl Frame {
         &mut self.local_variables
     }
 
-    pub fn throw_mut(self: &mut Self) -> &mut Option<Primitive> {
-        &mut self.throw
+    pub fn arguments(self: &Self) -> Vec<Primitive> {
+        let mut arguments = Vec::new();
+
+        let mut i = 0;
+        while i < self.local_variables.len() {
+            let variable = &self.local_variables[i];
+
+            i += variable.size();
+            arguments.push(variable.clone());
+        }
+
+        arguments
     }
 
     pub fn pc(self: &Self) -> usize {
@@ -79,9 +91,50 @@ impl Frame {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackTraceEntry {
+    pub class: String,
+    pub method: String,
+    pub file: Option<String>,
+    pub line: i32,
+}
+
+impl StackTraceEntry {
+    pub const UNKNOWN_LINE: i32 = -1;
+    pub const NATIVE_LINE: i32 = -2;
+
+    pub fn is_native(self: &Self) -> bool {
+        self.line == Self::NATIVE_LINE
+    }
+}
+
+impl std::fmt::Display for StackTraceEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let location = if self.is_native() {
+            "Native Method".to_string()
+        } else {
+            match (&self.file, self.line) {
+                (Some(file), line) if line >= 0 => format!("{}:{}", file, line),
+                (Some(file), _) => file.clone(),
+                (None, _) => "Unknown Source".to_string(),
+            }
+        };
+
+        write!(
+            f,
+            "{}.{}({})",
+            self.class.replace('/', "."),
+            self.method,
+            location
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct Thread {
     global: Global,
+    id: usize,
+    name: String,
     frames: Vec<Frame>,
     priority: usize,
 }
@@ -90,35 +143,78 @@ pub enum ThreadResult {
     Continue,
     Stop,
     Result(Primitive),
+    /// The thread died because nothing caught this throwable.
+    Uncaught(Primitive),
+}
+
+/// How a method called from Rust, through the VM or `NativeEnv::call`, ended.
+#[derive(Debug, Clone)]
+pub enum CallOutcome {
+    /// The method returned this value, `Void` for `void` methods and constructors.
+    Return(Primitive),
+    /// Nothing caught this throwable.
+    Throw(Primitive),
+    /// The program called `System.exit` or `Runtime.halt` with this status first.
+    Exit(i32),
 }
 
 impl Thread {
-    pub fn new(global: Global, priority: usize) -> Self {
+    pub fn new(mut global: Global, name: &str, priority: usize) -> Self {
+        let id = global.new_thread_id();
+
         Self {
             global,
+            id,
+            name: name.to_string(),
             frames: Vec::new(),
             priority,
         }
     }
 
     pub fn new_main(global: Global) -> Result<Self, WasmJVMError> {
-        let mut thread = Self {
-            global,
-            frames: Vec::new(),
-            priority: 0,
-        };
+        let mut thread = Self::new(global, "main", 0);
 
         thread.new_main_frame()?;
 
         Ok(thread)
     }
 
+    /// A thread for a native that calls back into Java. It shares `parent`'s id, so it can use
+    /// the classes `parent` is still initializing.
+    pub fn new_call(parent: &Thread) -> Self {
+        Self {
+            global: parent.global.clone(),
+            id: parent.id,
+            name: parent.name.clone(),
+            frames: Vec::new(),
+            priority: parent.priority,
+        }
+    }
+
+    pub fn id(self: &Self) -> usize {
+        self.id
+    }
+
+    pub fn name(self: &Self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn frames(self: &Self) -> &Vec<Frame> {
+        &self.frames
+    }
+
+    /// Drops every frame, so the thread reports `Stop` on its next tick.
+    pub fn stop(self: &mut Self) {
+        self.frames.clear();
+    }
+
     fn build_frame(
         self: &mut Self,
         mut method_ref: MethodRef,
         this: Option<Primitive>,
         local_variables: Vec<Primitive>,
     ) -> Result<Frame, WasmJVMError> {
+        let requested = method_ref.clone();
         let mut checked_this_class = this.is_none();
         loop {
             let class_index =
@@ -138,7 +234,7 @@ impl Thread {
                 let access_flags = method.access_flags();
 
                 if !access_flags.has_type(&AccessFlagType::Static) {
-                    if this.is_none() || this.as_ref().unwrap().is_null() {
+                    if this.as_ref().map_or(true, |this| this.is_null()) {
                         return Err(WasmJVMError::NullPointerException(format!(
                             "self was null when calling {:?}",
                             method_ref
@@ -158,15 +254,25 @@ impl Thread {
             if !checked_this_class {
                 checked_this_class = true;
 
-                let this_object = self.global.reference_p(this.as_ref().unwrap())?;
-                let this_class = self
-                    .global
-                    .reference(*this_object.class().as_ref().unwrap())?;
+                let this = this.as_ref().ok_or_else(|| {
+                    WasmJVMError::NullPointerException(format!(
+                        "self was null when calling {:?}",
+                        method_ref
+                    ))
+                })?;
+                let this_object = self.global.reference_p(this)?;
+                let this_class_index = this_object.class().ok_or_else(|| {
+                    WasmJVMError::VerifyError(format!("Object has no class {:?}", this))
+                })?;
+                let this_class = self.global.reference(this_class_index)?;
 
                 if let RustObject::Class(class) = this_class.inner() {
                     method_ref.class = class.metadata().this_class().to_string();
                 } else {
-                    unreachable!();
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected a class at index {}",
+                        this_class_index
+                    )));
                 }
 
                 continue;
@@ -178,7 +284,7 @@ impl Thread {
                 continue;
             }
 
-            return Err(WasmJVMError::NoSuchMethodError(format!("{:?}", method_ref)));
+            return Err(WasmJVMError::NoSuchMethodError(format!("{:?}", requested)));
         }
 
         let max_locals = {
@@ -188,7 +294,8 @@ impl Thread {
             let method = class.metadata().method(method_index);
 
             if method.access_flags().has_type(&AccessFlagType::Native) {
-                descriptor.parameters().len() + 1
+                descriptor.parameters().map(|r#type| r#type.size()).sum::<usize>()
+                    + this.is_some() as usize
             } else {
                 let attribute = method.attribute(&"Code".to_string())?;
                 let code = &attribute.body;
@@ -205,15 +312,17 @@ impl Thread {
         let mut locals = vec![Primitive::Null; max_locals];
 
         let mut i = if let Some(this) = this {
-            locals[0] = this;
+            *Self::local_mut(&mut locals, 0)? = this;
             1
         } else {
             0
         };
 
         for variable in local_variables {
-            locals[i] = variable;
-            i += 1;
+            let size = variable.size();
+
+            *Self::local_mut(&mut locals, i)? = variable;
+            i += size;
         }
 
         Ok(Frame::new(method_ref, locals)?)
@@ -240,6 +349,60 @@ impl Thread {
         self.new_frame(method_ref, None, local_variables)
     }
 
+    /// Pushes a call to a static method, initializing its class first if needed.
+    pub fn invoke_static(
+        self: &mut Self,
+        method_ref: MethodRef,
+        arguments: Vec<Primitive>,
+    ) -> Result<(), WasmJVMError> {
+        let class_index = self.global.resolve_class(&method_ref.class)?;
+
+        self.new_static_frame(method_ref, arguments)?;
+        self.new_initialize_frames(class_index)
+    }
+
+    /// Pushes a call to an instance method, running the implementation `this`'s class selects.
+    pub fn invoke_virtual(
+        self: &mut Self,
+        mut method_ref: MethodRef,
+        this: Primitive,
+        arguments: Vec<Primitive>,
+    ) -> Result<(), WasmJVMError> {
+        if !this.is_null() {
+            method_ref.class = self.global.dispatch_class(&this, &method_ref)?;
+        }
+
+        self.new_frame(method_ref, Some(this), arguments)
+    }
+
+    /// Allocates an instance of `class` and pushes its `<init>` with `descriptor`, initializing
+    /// the class first if needed. Returns the instance.
+    pub fn invoke_constructor(
+        self: &mut Self,
+        class: &str,
+        descriptor: Descriptor,
+        arguments: Vec<Primitive>,
+    ) -> Result<usize, WasmJVMError> {
+        let class_index = self.global.resolve_class(class)?;
+        let this = self.global.new_instance(class)?;
+
+        let method_ref = MethodRef::new(class.to_string(), "<init>".to_string(), descriptor);
+        self.new_frame(method_ref, Some(Primitive::Reference(this)), arguments)?;
+        self.new_initialize_frames(class_index)?;
+
+        Ok(this)
+    }
+
+    fn new_initialize_frames(self: &mut Self, class_index: usize) -> Result<(), WasmJVMError> {
+        if let Some(frames) = self.global.initialize_class(class_index, self.id)? {
+            for method_ref in frames {
+                self.new_static_frame(method_ref, Vec::new())?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn new_clinit_frame(self: &mut Self, class: usize) -> Result<(), WasmJVMError> {
         let class = self.global.class_mut(class)?;
         let class_name = class.metadata().this_class().clone();
@@ -251,11 +414,8 @@ impl Thread {
         );
 
         if self.global.method(&method_ref).is_ok() {
-            if let Ok(frame) = self.build_frame(method_ref, None, Vec::new()) {
-                self.frames.push(frame);
-            } else {
-                unreachable!()
-            }
+            let frame = self.build_frame(method_ref, None, Vec::new())?;
+            self.frames.push(frame);
         }
 
         Ok(())
@@ -288,60 +448,226 @@ impl Thread {
 
         let mut method_refs = class.metadata().method_refs(&"main".to_string())?;
 
-        let method_ref = method_refs.pop().unwrap();
+        let method_ref = method_refs.pop().ok_or_else(|| {
+            WasmJVMError::NoSuchMethodError(format!("main in {}", class.metadata().this_class()))
+        })?;
 
         self.new_static_frame(method_ref, Vec::new())?;
+        self.new_initialize_frames(class_index)
+    }
 
-        Ok(())
+    fn stack_trace_entry(
+        self: &Self,
+        frame: &Frame,
+        is_top: bool,
+    ) -> Result<StackTraceEntry, WasmJVMError> {
+        let (class_index, method_index, _) = self.global.method(&frame.method_ref)?;
+        let metadata = self.global.class(class_index)?.metadata();
+        let method = metadata.method(method_index);
+
+        let line = if method.access_flags().has_type(&AccessFlagType::Native) {
+            StackTraceEntry::NATIVE_LINE
+        } else {
+            let pc = if is_top {
+                frame.pc()
+            } else {
+                frame.pc().saturating_sub(1)
+            };
+
+            match &method.attribute("Code")?.body {
+                AttributeBody::Code(body) => body
+                    .line_number(pc)
+                    .map_or(StackTraceEntry::UNKNOWN_LINE, |line| line as i32),
+                _ => StackTraceEntry::UNKNOWN_LINE,
+            }
+        };
+
+        Ok(StackTraceEntry {
+            class: metadata.this_class().to_string(),
+            method: method.name().to_string(),
+            file: metadata.source_file().map(|file| file.to_string()),
+            line,
+        })
     }
 
-    pub fn stack_trace(self: &mut Self) -> Result<String, WasmJVMError> {
-        let frame_count = self.frames.len();
+    pub fn stack_trace_entries(self: &Self) -> Result<Vec<StackTraceEntry>, WasmJVMError> {
+        let mut entries = Vec::with_capacity(self.frames.len());
 
-        if frame_count == 0 {
-            return Ok(format!("===== Thread (Empty) ======\n"));
+        for (depth, frame) in self.frames.iter().rev().enumerate() {
+            entries.push(self.stack_trace_entry(frame, depth == 0)?);
         }
 
-        let frame = &self.frames[frame_count - 1];
-        let (class_index, method_index, _) = self.global.method(&frame.method_ref)?;
-        let class = self.global.class(class_index)?;
+        Ok(entries)
+    }
 
-        let method = class.metadata().method(method_index);
+    pub fn backtrace(self: &Self, this: &Primitive) -> Result<Vec<StackTraceEntry>, WasmJVMError> {
+        let mut frames = self.frames.iter().rev().peekable();
 
-        let opcode = if !method.access_flags().has_type(&AccessFlagType::Native) {
-            let code = {
-                let attribute = method.attribute(&"Code".to_string()).unwrap();
-                attribute.body.clone()
-            };
+        while let Some(frame) = frames.peek() {
+            let is_fill = frame.method_ref.name == "fillInStackTrace";
+            let is_init = frame.method_ref.name == "<init>"
+                && match (frame.local_variables.first(), this) {
+                    (Some(Primitive::Reference(local)), Primitive::Reference(this)) => local == this,
+                    _ => false,
+                };
 
-            if let wasmjvm_class::AttributeBody::Code(body) = code {
-                if frame.pc() < body.code.len() {
-                    let opcode = OpCode::from_u8(body.code[frame.pc()]).unwrap();
-                    format!("OpCode: {:?}\n", opcode)
-                } else {
-                    format!("End\n")
-                }
-            } else {
-                format!("Invalid\n")
+            if !is_fill && !is_init {
+                break;
             }
-        } else {
-            format!("Native\n")
-        };
 
-        let frames: Vec<String> = self
-            .frames
+            frames.next();
+        }
+
+        let mut entries = Vec::new();
+        for frame in frames {
+            entries.push(self.stack_trace_entry(frame, false)?);
+        }
+
+        Ok(entries)
+    }
+
+    pub fn stack_trace(self: &mut Self) -> Result<String, WasmJVMError> {
+        if self.frames.len() == 0 {
+            return Ok(format!("Thread \"{}\" (Empty)", self.name));
+        }
+
+        let entries: Vec<String> = self
+            .stack_trace_entries()?
             .iter()
-            .rev()
-            .map(|frame| format!("{:?}", frame))
+            .map(|entry| format!("\tat {}", entry))
             .collect();
-        let frames: String = frames.join("\n");
 
-        let message = format!(
-            "===== Thread ======\n{}\n{}\n================\n",
-            opcode, frames
-        );
+        Ok(format!("Thread \"{}\"\n{}", self.name, entries.join("\n")))
+    }
+
+    fn throw(self: &mut Self, mut throwable: Primitive) -> Result<ThreadResult, WasmJVMError> {
+        if throwable.is_null() {
+            let backtrace = self.stack_trace_entries()?;
+            throwable = Primitive::Reference(self.global.new_throwable(JAVA_NULL_POINTER_EXCEPTION, None, backtrace)?);
+        }
+
+        let mut class_names = self.global.class_names(&throwable)?;
+        let mut is_top = true;
+
+        while let Some(frame) = self.frames.last_mut() {
+            let (class_index, method_index, _) = self.global.method(&frame.method_ref)?;
+            let metadata = self.global.class(class_index)?.metadata();
+            let method = metadata.method(method_index);
+
+            if let Ok(attribute) = method.attribute("Code") {
+                if let AttributeBody::Code(body) = &attribute.body {
+                    let pc = if is_top {
+                        frame.pc
+                    } else {
+                        frame.pc.saturating_sub(1)
+                    };
+
+                    for exception in body.exception_table.iter() {
+                        if pc < exception.start_pc as usize || pc >= exception.end_pc as usize {
+                            continue;
+                        }
+
+                        let is_caught = if exception.catch_type == 0 {
+                            true
+                        } else if let Constant::Class { name } = metadata
+                            .constant(exception.catch_type as usize)
+                            .map_err(|error| error.at(&frame.method_ref.class, &frame.method_ref.name, pc, None))?
+                        {
+                            class_names.contains(name)
+                        } else {
+                            return Err(WasmJVMError::ClassFormatError(format!(
+                                "Invalid catch type {}",
+                                exception.catch_type
+                            )));
+                        };
+
+                        if is_caught {
+                            frame.pc = exception.handler_pc as usize;
+                            frame.operand_stack.clear();
+                            frame.operand_stack.push(throwable);
+
+                            return Ok(ThreadResult::Continue);
+                        }
+                    }
+                }
+            }
+
+            let frame = self
+                .frames
+                .pop()
+                .ok_or_else(|| WasmJVMError::InternalError(format!("Empty call stack")))?;
+            is_top = false;
+
+            if frame.method_ref.name == "<clinit>" {
+                self.global
+                    .set_class_state(&frame.method_ref.class, ClassState::Erroneous)?;
 
-        Ok(message)
+                if !self.global.is_instance(&throwable, JAVA_ERROR)? {
+                    let backtrace = self.stack_trace_entries()?;
+                    let error = Primitive::Reference(self.global.new_throwable(
+                        JAVA_EXCEPTION_IN_INITIALIZER_ERROR,
+                        None,
+                        backtrace,
+                    )?);
+
+                    self.global.field_named_set(&error, "cause", throwable)?;
+
+                    throwable = error;
+                    class_names = self.global.class_names(&throwable)?;
+                }
+
+                is_top = true;
+            }
+        }
+
+        Ok(ThreadResult::Uncaught(throwable))
+    }
+
+    fn java_exception(error: &WasmJVMError) -> Option<&'static str> {
+        match error {
+            WasmJVMError::ArithmeticException(..) => Some("java/lang/ArithmeticException"),
+            WasmJVMError::ArrayStoreException(..) => Some("java/lang/ArrayStoreException"),
+            WasmJVMError::ClassCastException(..) => Some("java/lang/ClassCastException"),
+            WasmJVMError::IllegalArgumentException(..) => Some("java/lang/IllegalArgumentException"),
+            WasmJVMError::CloneNotSupportedException(..) => {
+                Some("java/lang/CloneNotSupportedException")
+            }
+            WasmJVMError::NullPointerException(..) => Some("java/lang/NullPointerException"),
+            WasmJVMError::NumberFormatException(..) => Some("java/lang/NumberFormatException"),
+            WasmJVMError::IndexOutOfBoundException(..) => {
+                Some("java/lang/ArrayIndexOutOfBoundsException")
+            }
+            WasmJVMError::NegativeArraySizeException(..) => {
+                Some("java/lang/NegativeArraySizeException")
+            }
+            WasmJVMError::OutOfMemoryError(..) => Some("java/lang/OutOfMemoryError"),
+            WasmJVMError::NoClassDefFoundError(..) => Some("java/lang/NoClassDefFoundError"),
+            WasmJVMError::NoSuchFieldError(..) => Some("java/lang/NoSuchFieldError"),
+            WasmJVMError::IncompatibleClassChangeError(..) => {
+                Some("java/lang/IncompatibleClassChangeError")
+            }
+            WasmJVMError::IllegalAccessError(..) => Some("java/lang/IllegalAccessError"),
+            _ => None,
+        }
+    }
+
+    fn fail(
+        self: &mut Self,
+        error: WasmJVMError,
+        method_ref: &MethodRef,
+        pc: usize,
+        opcode: Option<String>,
+    ) -> Result<ThreadResult, WasmJVMError> {
+        match Self::java_exception(&error) {
+            Some(class) => {
+                let message = error.message();
+                let backtrace = self.stack_trace_entries()?;
+                let throwable = self.global.new_throwable(class, message, backtrace)?;
+
+                self.throw(Primitive::Reference(throwable))
+            }
+            None => Err(error.at(&method_ref.class, &method_ref.name, pc, opcode)),
+        }
     }
 
     pub async fn tick(self: &mut Self) -> Result<ThreadResult, WasmJVMError> {
@@ -361,87 +687,99 @@ impl Thread {
 
         let frame_count = self.frames.len();
         let frame = &mut self.frames[frame_count - 1];
-        let variables = frame.local_variables().clone();
+        let variables = frame.arguments();
+        let method_ref = frame.method_ref.clone();
+        let start_pc = frame.pc;
 
         let mut out_frames: Vec<(MethodRef, Option<Primitive>, Vec<Primitive>)> = Vec::new();
         let mut out_return: Option<Primitive> = None;
-        let mut out_throw = false;
+        let mut out_throw: Option<Primitive> = None;
+        let mut out_opcode: Option<String> = None;
 
-        let (class_index, method_index, descriptor) = self.global.method(&frame.method_ref)?;
+        let (class_index, method_index, descriptor) = match self.global.method(&method_ref) {
+            Ok(method) => method,
+            Err(error) => return self.fail(error, &method_ref, start_pc, None),
+        };
         let class = self.global.class(class_index)?;
         let method = class.metadata().method(method_index);
 
         if method.access_flags().has_type(&AccessFlagType::Native) {
-            let result = self.global.native_invoke(&frame.method_ref, variables).await?;
-            out_return = Some(result.into_type(descriptor.output())?);
-        } else {
-            let code = {
-                let attribute = method.attribute(&"Code".to_string())?;
-                attribute.body.clone()
+            let (result, continuation) = match self
+                .global
+                .native_invoke(&method_ref, variables)
+                .await
+            {
+                Ok(result) => result,
+                Err(error) => return self.fail(error, &method_ref, 0, None),
             };
 
-            if let wasmjvm_class::AttributeBody::Code(body) = code {
-                let (pc, stack, locals, frame_throw, throw_entries) = frame.all_mut();
-
-                if frame_throw.is_some() && throw_entries.len() == 0 {
-                    let throw_reference = frame_throw.as_ref().unwrap();
-                    let throw_object = self.global.reference_p(&throw_reference)?;
-                    let throw_class = self.global.class(throw_object.class().unwrap())?;
-                    let mut class_names = HashSet::new();
-                    let mut class_name_queue = vec![throw_class.metadata().this_class()];
-                    while class_name_queue.len() > 0 {
-                        let class_name = class_name_queue.pop().unwrap();
-                        let class_index = self.global.class_index(class_name)?;
-                        let class = self.global.class(class_index)?;
-
-                        class_names.insert(class_name);
-
-                        if let Some(super_class) = class.metadata().super_class() {
-                            class_name_queue.push(super_class);
-                        }
-                    }
+            match continuation {
+                Some(NativeContinuation::Invoke {
+                    method_ref: invoked,
+                    this,
+                    arguments,
+                    boxed,
+                }) => {
+                    let mut frame = match self.build_frame(invoked, this, arguments) {
+                        Ok(frame) => frame,
+                        Err(error) => return self.fail(error, &method_ref, 0, None),
+                    };
+                    frame.set_boxed(boxed);
 
-                    for exception in body.exception_table.iter() {
-                        if !(*pc >= exception.start_pc as usize && *pc <= exception.end_pc as usize)
-                        {
-                            continue;
-                        }
+                    self.frames.pop();
+                    self.frames.push(frame);
 
-                        if exception.catch_type as usize == 0 {
-                            throw_entries.push(exception.clone());
-                        } else {
-                            let exception_constant =
-                                class.metadata().constant(exception.catch_type as usize);
-                            if let Constant::Class { name } = exception_constant {
-                                if class_names.contains(name.as_str()) {
-                                    throw_entries.push(exception.clone());
-                                }
-                            } else {
-                                todo!("{:?}", exception_constant);
-                            }
+                    return Ok(ThreadResult::Continue);
+                }
+                Some(NativeContinuation::Retry(method_refs)) => {
+                    for clinit in method_refs {
+                        if let Err(error) = self.new_static_frame(clinit, Vec::new()) {
+                            return self.fail(error, &method_ref, 0, None);
                         }
                     }
 
-                    throw_entries.reverse();
-
-                    if throw_entries.len() > 0 {
-                        *pc = throw_entries[throw_entries.len() - 1].handler_pc as usize;
-                        stack.push(frame_throw.as_ref().unwrap().clone());
-                    } else {
-                        out_throw = true;
-                    }
+                    return Ok(ThreadResult::Continue);
                 }
+                Some(NativeContinuation::Throw(throwable)) => out_throw = Some(throwable),
+                None => match result.into_type(descriptor.output()) {
+                    Ok(result) => out_return = Some(result),
+                    Err(error) => return self.fail(error, &method_ref, 0, None),
+                },
+            }
+        } else {
+            let code = {
+                let attribute = method.attribute(&"Code".to_string())?;
+                attribute.body.clone()
+            };
 
-                if !out_throw {
-                    let (mut new_frames, r#return, offset, throw) = Self::code_tick(
-                        &mut self.global.clone(),
-                        pc,
-                        &body.code,
-                        stack,
-                        locals,
-                        class.metadata(),
-                    ).await?;
+            if let AttributeBody::Code(body) = code {
+                let (pc, stack, locals) = frame.all_mut();
+
+                out_opcode = body
+                    .code
+                    .get(start_pc)
+                    .and_then(|opcode| OpCode::from_u8(*opcode).ok())
+                    .map(|opcode| format!("{:?}", opcode));
+
+                let result = Self::code_tick(
+                    &mut self.global.clone(),
+                    self.id,
+                    pc,
+                    &body.code,
+                    stack,
+                    locals,
+                    class.metadata(),
+                )
+                .await;
+
+                let (mut new_frames, r#return, offset, throw) = match result {
+                    Ok(result) => result,
+                    Err(error) => return self.fail(error, &method_ref, start_pc, out_opcode),
+                };
 
+                if throw.is_some() {
+                    out_throw = throw;
+                } else {
                     if offset >= 0 {
                         *pc += offset as usize;
                     } else {
@@ -449,38 +787,37 @@ impl Thread {
                     }
 
                     out_frames.append(&mut new_frames);
-                    if r#return.is_some() && frame_throw.is_none() {
-                        if let Some(r#return) = r#return {
-                            out_return = Some(r#return.into_type(descriptor.output())?);
-                        }
-                    } else if r#return.is_some()
-                        && frame_throw.is_some()
-                        && throw_entries
-                            .iter()
-                            .any(|entry| *pc == entry.handler_pc as usize)
-                    {
-                        throw_entries.pop();
-
-                        if throw_entries.len() == 0 {
-                            out_throw = true;
-                        } else {
-                            let exception = &throw_entries[throw_entries.len() - 1];
-                            *pc = exception.handler_pc as usize;
-                            stack.push(frame_throw.as_ref().unwrap().clone());
-                        }
-                    }
 
-                    if let Some(throw) = throw {
-                        *frame_throw = Some(throw);
+                    if let Some(r#return) = r#return {
+                        match r#return.into_type(descriptor.output()) {
+                            Ok(r#return) => out_return = Some(r#return),
+                            Err(error) => return self.fail(error, &method_ref, start_pc, out_opcode),
+                        }
                     }
                 }
             } else {
-                return Err(WasmJVMError::TODO(28));
+                return Err(WasmJVMError::TODO(28).at(&method_ref.class, &method_ref.name, start_pc, None));
             }
         }
 
-        if let Some(r#return) = out_return {
-            self.frames.pop();
+        if let Some(throwable) = out_throw {
+            return self.throw(throwable).map_err(|error| error.at(&method_ref.class, &method_ref.name, start_pc, out_opcode));
+        }
+
+        if let Some(mut r#return) = out_return {
+            if let Some(frame) = self.frames.pop() {
+                if frame.method_ref.name == "<clinit>" {
+                    self.global
+                        .set_class_state(&frame.method_ref.class, ClassState::Initialized)?;
+                }
+
+                if frame.boxed {
+                    r#return = self
+                        .global
+                        .box_primitive(descriptor.output(), r#return)
+                        .map_err(|error| error.at(&method_ref.class, &method_ref.name, start_pc, out_opcode))?;
+                }
+            }
 
             let frame_count = self.frames.len();
             if frame_count == 0 {
@@ -489,28 +826,49 @@ impl Thread {
                 let frame = &mut self.frames[frame_count - 1];
                 frame.operand_stack_mut().push(r#return);
             }
-        } else if out_throw {
-            let mut frame = self.frames.pop().unwrap();
-
-            if self.frames.len() == 0 {
-                return Err(WasmJVMError::UnhandledException(format!(
-                    "{:?}",
-                    self.global.reference_p(&frame.throw.unwrap()).unwrap()
-                )));
-            } else {
-                let mut next_frame = self.frames.pop().unwrap();
-                *next_frame.throw_mut() = Some(frame.throw_mut().as_ref().unwrap().clone());
-                self.frames.push(next_frame);
-            }
         } else {
-            for (method_ref, this, locals) in out_frames {
-                self.new_frame(method_ref, this, locals)?;
+            let caller = self.frames.len();
+
+            for (callee, this, locals) in out_frames {
+                if let Err(error) = self.new_frame(callee, this, locals) {
+                    // Report the failed call from the invoke instruction, where a handler can catch it.
+                    self.frames.truncate(caller);
+                    if let Some(frame) = self.frames.last_mut() {
+                        frame.pc = start_pc;
+                    }
+
+                    return self.fail(error, &method_ref, start_pc, out_opcode);
+                }
             }
         }
 
         Ok(ThreadResult::Continue)
     }
 
+    fn pop(stack: &mut Vec<Primitive>) -> Result<Primitive, WasmJVMError> {
+        stack
+            .pop()
+            .ok_or_else(|| WasmJVMError::VerifyError(format!("Operand stack underflow")))
+    }
+
+    fn byte(code: &Vec<u8>, index: usize) -> Result<u8, WasmJVMError> {
+        code.get(index).copied().ok_or_else(|| {
+            WasmJVMError::VerifyError(format!("Code index {} out of bounds", index))
+        })
+    }
+
+    fn local(locals: &Vec<Primitive>, index: usize) -> Result<&Primitive, WasmJVMError> {
+        locals.get(index).ok_or_else(|| {
+            WasmJVMError::VerifyError(format!("Local variable {} out of bounds", index))
+        })
+    }
+
+    fn local_mut(locals: &mut Vec<Primitive>, index: usize) -> Result<&mut Primitive, WasmJVMError> {
+        locals.get_mut(index).ok_or_else(|| {
+            WasmJVMError::VerifyError(format!("Local variable {} out of bounds", index))
+        })
+    }
+
     fn pop_locals(
         descriptor: &Descriptor,
         stack: &mut Vec<Primitive>,
@@ -518,15 +876,71 @@ impl Thread {
         let mut locals = Vec::new();
 
         for r#type in descriptor.parameters().rev() {
-            locals.push(stack.pop().unwrap().into_type(r#type)?);
+            locals.push(Self::pop(stack)?.into_type(r#type)?);
         }
         locals.reverse();
 
         Ok(locals)
     }
 
+    fn initialize_class(
+        global: &mut Global,
+        thread_id: usize,
+        class_index: usize,
+    ) -> Result<Option<Vec<(MethodRef, Option<Primitive>, Vec<Primitive>)>>, WasmJVMError> {
+        Ok(global
+            .initialize_class(class_index, thread_id)?
+            .map(|method_refs| {
+                method_refs
+                    .into_iter()
+                    .map(|method_ref| (method_ref, None, Vec::new()))
+                    .collect()
+            }))
+    }
+
+    fn load_constant(
+        global: &mut Global,
+        metadata: &wasmjvm_class::Class,
+        constant: &Constant,
+    ) -> Result<Primitive, WasmJVMError> {
+        match constant {
+            Constant::String(value) => Ok(Primitive::Reference(global.intern_string(value)?)),
+            Constant::Class { name } => {
+                let class_index = global.constant_class(name)?;
+                global.check_class_access(metadata.this_class(), name)?;
+
+                Ok(Primitive::Reference(class_index))
+            }
+            Constant::MethodType { descriptor } => {
+                Ok(Primitive::Reference(global.new_method_type(descriptor)?))
+            }
+            Constant::MethodHandle {
+                reference_kind,
+                reference_index,
+            } => {
+                let member = metadata.constant(*reference_index as usize)?;
+
+                match member {
+                    Constant::FieldRef(field_ref) => {
+                        global.resolve_class(&field_ref.class)?;
+                        global.check_field_access(metadata.this_class(), field_ref)?;
+                    }
+                    Constant::MethodRef(method_ref) | Constant::InterfaceMethodRef(method_ref) => {
+                        global.resolve_class(&method_ref.class)?;
+                        global.check_method_access(metadata.this_class(), method_ref)?;
+                    }
+                    _ => {}
+                }
+
+                Ok(Primitive::Reference(global.new_method_handle(*reference_kind, member)?))
+            }
+            _ => Primitive::try_from(constant.clone()),
+        }
+    }
+
     async fn code_tick(
         global: &mut Global,
+        thread_id: usize,
         pc: &mut usize,
         code: &Vec<u8>,
         stack: &mut Vec<Primitive>,
@@ -545,7 +959,7 @@ impl Thread {
         let mut r#return = None;
         let mut throw = None;
 
-        let opcode_raw = code[*pc];
+        let opcode_raw = Self::byte(code, *pc)?;
         let opcode = OpCode::from_u8(opcode_raw)?;
 
         let offset: isize = match opcode {
@@ -590,86 +1004,72 @@ impl Thread {
                 1
             }
             OpCode::BiPush => {
-                let value = code[*pc + 1];
+                let value = Self::byte(code, *pc + 1)? as i8;
 
-                stack.push(Primitive::Byte(value));
+                stack.push(Primitive::Int(value as i32));
 
                 2
             }
             OpCode::SiPush => {
-                let value = (code[*pc + 1] as u16) << 8 | code[*pc + 2] as u16;
+                let value = ((Self::byte(code, *pc + 1)? as u16) << 8 | Self::byte(code, *pc + 2)? as u16) as i16;
 
-                stack.push(Primitive::Short(value));
+                stack.push(Primitive::Int(value as i32));
 
                 3
             }
             OpCode::Ldc => {
                 // TODO: Symbolic resolution.
-                let index = code[*pc + 1] as usize;
-                let constant = metadata.constant(index);
-
-                match constant {
-                    Constant::String(value) => {
-                        let string_ref = global.new_java_string(value.clone())?;
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let constant = metadata.constant(index)?;
 
-                        let reference = Primitive::Reference(string_ref);
-
-                        stack.push(reference.clone());
-                    }
-                    _ => {
-                        let primitive = Primitive::from(constant.clone());
-
-                        stack.push(primitive);
-                    }
-                }
+                stack.push(Self::load_constant(global, metadata, constant)?);
 
                 2
             }
             OpCode::LdcW | OpCode::Ldc2W => {
                 // TODO: Symbolic resolution.
-                let index = (code[*pc + 1] as usize) << 8 | code[*pc + 2] as usize;
-                let constant = metadata.constant(index);
-                let primitive = Primitive::from(constant.clone());
+                let index = (Self::byte(code, *pc + 1)? as usize) << 8 | Self::byte(code, *pc + 2)? as usize;
+                let constant = metadata.constant(index)?;
 
-                stack.push(primitive);
+                stack.push(Self::load_constant(global, metadata, constant)?);
 
                 3
             }
             OpCode::Iload => {
-                let index = code[*pc + 1] as usize;
-                let variable = locals[index].into_int()?;
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let variable = Self::local(locals, index)?.into_int()?;
 
                 stack.push(variable);
 
                 2
             }
             OpCode::Lload => {
-                let index = code[*pc + 1] as usize;
-                let variable = locals[index].into_long()?;
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let variable = Self::local(locals, index)?.into_long()?;
 
                 stack.push(variable);
 
                 2
             }
             OpCode::Fload => {
-                let index = code[*pc + 1] as usize;
-                let variable = locals[index].into_float()?;
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let variable = Self::local(locals, index)?.into_float()?;
 
                 stack.push(variable);
 
                 2
             }
             OpCode::Dload => {
-                let index = code[*pc + 1] as usize;
-                let variable = locals[index].into_double()?;
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let variable = Self::local(locals, index)?.into_double()?;
 
                 stack.push(variable);
 
                 2
             }
             OpCode::Aload => {
-                let index = code[*pc + 1] as usize;
-                let variable = locals[index].into_ref()?;
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let variable = Self::local(locals, index)?.into_ref()?;
 
                 stack.push(variable);
 
@@ -677,7 +1077,7 @@ impl Thread {
             }
             OpCode::Iload0 | OpCode::Iload1 | OpCode::Iload2 | OpCode::Iload3 => {
                 let index = opcode_raw as usize - OpCode::Iload0 as usize;
-                let variable = locals[index].into_int()?;
+                let variable = Self::local(locals, index)?.into_int()?;
 
                 stack.push(variable);
 
@@ -685,7 +1085,7 @@ impl Thread {
             }
             OpCode::Lload0 | OpCode::Lload1 | OpCode::Lload2 | OpCode::Lload3 => {
                 let index = opcode_raw as usize - OpCode::Lload0 as usize;
-                let variable = locals[index].into_long()?;
+                let variable = Self::local(locals, index)?.into_long()?;
 
                 stack.push(variable);
 
@@ -693,7 +1093,7 @@ impl Thread {
             }
             OpCode::Fload0 | OpCode::Fload1 | OpCode::Fload2 | OpCode::Fload3 => {
                 let index = opcode_raw as usize - OpCode::Fload0 as usize;
-                let variable = locals[index].into_float()?;
+                let variable = Self::local(locals, index)?.into_float()?;
 
                 stack.push(variable);
 
@@ -701,7 +1101,7 @@ impl Thread {
             }
             OpCode::Dload0 | OpCode::Dload1 | OpCode::Dload2 | OpCode::Dload3 => {
                 let index = opcode_raw as usize - OpCode::Dload0 as usize;
-                let variable = locals[index].into_double()?;
+                let variable = Self::local(locals, index)?.into_double()?;
 
                 stack.push(variable);
 
@@ -709,7 +1109,7 @@ impl Thread {
             }
             OpCode::Aload0 | OpCode::Aload1 | OpCode::Aload2 | OpCode::Aload3 => {
                 let index = opcode_raw as usize - OpCode::Aload0 as usize;
-                let variable = locals[index].into_ref()?;
+                let variable = Self::local(locals, index)?.into_ref()?;
 
                 stack.push(variable);
 
@@ -723,126 +1123,90 @@ impl Thread {
             | OpCode::BAload
             | OpCode::CAload
             | OpCode::SAload => {
-                let index = stack.pop().unwrap().into_int()?;
-                let reference = stack.pop().unwrap();
-                let object = global.reference_p(&reference)?;
-
-                match (object.inner(), index) {
-                    (RustObject::Array(array), Primitive::Int(index)) => {
-                        if index < 0 {
-                            return Err(WasmJVMError::IndexOutOfBoundException(format!(
-                                "{} < 0",
-                                index
-                            )));
-                        }
-
-                        if index >= array.len() as i32 {
-                            return Err(WasmJVMError::IndexOutOfBoundException(format!(
-                                "{} >= {}",
-                                index,
-                                array.len()
-                            )));
-                        }
-
-                        let value = array[index as usize].clone();
-
-                        match opcode {
-                            OpCode::IAload => value.into_int()?,
-                            OpCode::LAload => value.into_long()?,
-                            OpCode::FAload => value.into_float()?,
-                            OpCode::DAload => value.into_double()?,
-                            OpCode::AAload => value.into_ref()?,
-                            // TODO: Byte or boolean.
-                            OpCode::BAload => value.into_byte()?,
-                            OpCode::CAload => value.into_char()?,
-                            OpCode::SAload => value.into_short()?,
-                            _ => unreachable!(),
-                        };
+                let index = Self::pop(stack)?.as_int()?;
+                let reference = Self::pop(stack)?;
 
-                        stack.push(value);
-                    }
-                    _ => unreachable!(),
-                }
+                stack.push(global.array_get(&reference, index)?);
 
                 1
             }
             OpCode::Istore => {
-                let index = code[*pc + 1] as usize;
-                let last = stack.pop().unwrap();
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 2
             }
             OpCode::Lstore => {
-                let index = code[*pc + 1] as usize;
-                let last = stack.pop().unwrap();
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 2
             }
             OpCode::Fstore => {
-                let index = code[*pc + 1] as usize;
-                let last = stack.pop().unwrap();
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 2
             }
             OpCode::Dstore => {
-                let index = code[*pc + 1] as usize;
-                let last = stack.pop().unwrap();
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 2
             }
             OpCode::Astore => {
-                let index = code[*pc + 1] as usize;
-                let last = stack.pop().unwrap();
+                let index = Self::byte(code, *pc + 1)? as usize;
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 2
             }
             OpCode::Istore0 | OpCode::Istore1 | OpCode::Istore2 | OpCode::Istore3 => {
                 let index = opcode_raw as usize - OpCode::Istore0 as usize;
-                let last = stack.pop().unwrap();
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 1
             }
             OpCode::Lstore0 | OpCode::Lstore1 | OpCode::Lstore2 | OpCode::Lstore3 => {
                 let index = opcode_raw as usize - OpCode::Lstore0 as usize;
-                let last = stack.pop().unwrap();
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 1
             }
             OpCode::Fstore0 | OpCode::Fstore1 | OpCode::Fstore2 | OpCode::Fstore3 => {
                 let index = opcode_raw as usize - OpCode::Fstore0 as usize;
-                let last = stack.pop().unwrap();
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 1
             }
             OpCode::Dstore0 | OpCode::Dstore1 | OpCode::Dstore2 | OpCode::Dstore3 => {
                 let index = opcode_raw as usize - OpCode::Dstore0 as usize;
-                let last = stack.pop().unwrap();
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 1
             }
             OpCode::Astore0 | OpCode::Astore1 | OpCode::Astore2 | OpCode::Astore3 => {
                 let index = opcode_raw as usize - OpCode::Astore0 as usize;
-                let last = stack.pop().unwrap();
+                let last = Self::pop(stack)?;
 
-                locals[index] = last;
+                *Self::local_mut(locals, index)? = last;
 
                 1
             }
@@ -854,27 +1218,28 @@ impl Thread {
             | OpCode::CAstore
             | OpCode::BAstore
             | OpCode::SAstore => {
-                let value = stack.pop().unwrap();
-                let index = stack.pop().unwrap().into_int()?;
-                let reference = stack.pop().unwrap();
+                let value = Self::pop(stack)?;
+                let index = Self::pop(stack)?;
+                let reference = Self::pop(stack)?;
 
                 global.array_set(reference, index, value)?;
 
                 1
             }
             OpCode::Pop => {
-                stack.pop().unwrap();
+                Self::pop(stack)?;
 
                 1
             }
             OpCode::Pop2 => {
-                stack.pop().unwrap();
-                stack.pop().unwrap();
+                if Self::pop(stack)?.size() == 1 {
+                    Self::pop(stack)?;
+                }
 
                 1
             }
             OpCode::Dup => {
-                let value = stack.pop().unwrap();
+                let value = Self::pop(stack)?;
 
                 stack.push(value.clone());
                 stack.push(value);
@@ -882,18 +1247,26 @@ impl Thread {
                 1
             }
             OpCode::DupX1 => {
-                // TODO: Check for category 2.
-                let value1 = stack.pop().unwrap();
-                let value2 = stack.pop().unwrap();
+                let value1 = Self::pop(stack)?;
+                let value2 = Self::pop(stack)?;
+
+                stack.push(value1.clone());
+                stack.push(value2);
+                stack.push(value1);
 
-                if true {
+                1
+            }
+            OpCode::DupX2 => {
+                let value1 = Self::pop(stack)?;
+                let value2 = Self::pop(stack)?;
+
+                if value2.size() == 2 {
                     stack.push(value1.clone());
                     stack.push(value2);
                     stack.push(value1);
                 } else {
-                    let value3 = stack.pop().unwrap();
+                    let value3 = Self::pop(stack)?;
 
-                    stack.push(value2.clone());
                     stack.push(value1.clone());
                     stack.push(value3);
                     stack.push(value2);
@@ -902,391 +1275,456 @@ impl Thread {
 
                 1
             }
-            OpCode::DupX2 => todo!(),
             OpCode::Dup2 => {
-                let v = stack.pop().unwrap();
+                let value1 = Self::pop(stack)?;
 
-                match &v {
-                    Primitive::Long(..) | Primitive::Double(..) => {
-                        // TODO: Check if valid.
-                        stack.push(v.clone());
-                        stack.push(v);
-                    }
-                    _ => {
-                        let v2 = stack.pop().unwrap();
+                if value1.size() == 2 {
+                    stack.push(value1.clone());
+                    stack.push(value1);
+                } else {
+                    let value2 = Self::pop(stack)?;
+
+                    stack.push(value2.clone());
+                    stack.push(value1.clone());
+                    stack.push(value2);
+                    stack.push(value1);
+                }
+
+                1
+            }
+            OpCode::Dup2X1 => {
+                let value1 = Self::pop(stack)?;
+
+                if value1.size() == 2 {
+                    let value2 = Self::pop(stack)?;
+
+                    stack.push(value1.clone());
+                    stack.push(value2);
+                    stack.push(value1);
+                } else {
+                    let value2 = Self::pop(stack)?;
+                    let value3 = Self::pop(stack)?;
+
+                    stack.push(value2.clone());
+                    stack.push(value1.clone());
+                    stack.push(value3);
+                    stack.push(value2);
+                    stack.push(value1);
+                }
+
+                1
+            }
+            OpCode::Dup2X2 => {
+                let value1 = Self::pop(stack)?;
 
-                        stack.push(v2.clone());
-                        stack.push(v.clone());
-                        stack.push(v2);
-                        stack.push(v);
+                if value1.size() == 2 {
+                    let value2 = Self::pop(stack)?;
+
+                    if value2.size() == 2 {
+                        stack.push(value1.clone());
+                        stack.push(value2);
+                        stack.push(value1);
+                    } else {
+                        let value3 = Self::pop(stack)?;
+
+                        stack.push(value1.clone());
+                        stack.push(value3);
+                        stack.push(value2);
+                        stack.push(value1);
+                    }
+                } else {
+                    let value2 = Self::pop(stack)?;
+                    let value3 = Self::pop(stack)?;
+
+                    if value3.size() == 2 {
+                        stack.push(value2.clone());
+                        stack.push(value1.clone());
+                        stack.push(value3);
+                        stack.push(value2);
+                        stack.push(value1);
+                    } else {
+                        let value4 = Self::pop(stack)?;
+
+                        stack.push(value2.clone());
+                        stack.push(value1.clone());
+                        stack.push(value4);
+                        stack.push(value3);
+                        stack.push(value2);
+                        stack.push(value1);
                     }
                 }
 
                 1
             }
-            OpCode::Dup2X1 => todo!(),
-            OpCode::Dup2X2 => todo!(),
-            OpCode::Swap => todo!(),
+            OpCode::Swap => {
+                let value1 = Self::pop(stack)?;
+                let value2 = Self::pop(stack)?;
+
+                stack.push(value1);
+                stack.push(value2);
+
+                1
+            }
             OpCode::Iadd => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.add(&right.into_int()?)?);
 
                 1
             }
             OpCode::Ladd => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.add(&right.into_long()?)?);
 
                 1
             }
             OpCode::Fadd => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_float()?.add(&right.into_float()?)?);
 
                 1
             }
             OpCode::Dadd => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_double()?.add(&right.into_double()?)?);
 
                 1
             }
             OpCode::Isub => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.sub(&right.into_int()?)?);
 
                 1
             }
             OpCode::Lsub => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.sub(&right.into_long()?)?);
 
                 1
             }
             OpCode::Fsub => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_float()?.sub(&right.into_float()?)?);
 
                 1
             }
             OpCode::Dsub => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_double()?.sub(&right.into_double()?)?);
 
                 1
             }
             OpCode::Imul => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.mul(&right.into_int()?)?);
 
                 1
             }
             OpCode::Lmul => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.mul(&right.into_long()?)?);
 
                 1
             }
             OpCode::Fmul => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_float()?.mul(&right.into_float()?)?);
 
                 1
             }
             OpCode::Dmul => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_double()?.mul(&right.into_double()?)?);
 
                 1
             }
             OpCode::Idiv => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.div(&right.into_int()?)?);
 
                 1
             }
             OpCode::Ldiv => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.div(&right.into_long()?)?);
 
                 1
             }
             OpCode::Fdiv => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_float()?.div(&right.into_float()?)?);
 
                 1
             }
             OpCode::Ddiv => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_double()?.div(&right.into_double()?)?);
 
                 1
             }
             OpCode::Irem => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.rem(&right.into_int()?)?);
 
                 1
             }
             OpCode::Lrem => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.rem(&right.into_long()?)?);
 
                 1
             }
             OpCode::Frem => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_float()?.rem(&right.into_float()?)?);
 
                 1
             }
             OpCode::Drem => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_double()?.rem(&right.into_double()?)?);
 
                 1
             }
             OpCode::Ineg | OpCode::Lneg | OpCode::Fneg | OpCode::Dneg => {
-                let value = stack.pop().unwrap();
+                let value = Self::pop(stack)?;
 
                 stack.push(value.neg()?);
 
                 1
             }
             OpCode::Ishl => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.shl(&right.into_int()?)?);
 
                 1
             }
             OpCode::Lshl => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.shl(&right.into_long()?)?);
 
                 1
             }
             OpCode::Ishr => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.shr(&right.into_int()?)?);
 
                 1
             }
             OpCode::Lshr => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.shr(&right.into_long()?)?);
 
                 1
             }
             OpCode::Iushr => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.ushr(&right.into_int()?)?);
 
                 1
             }
             OpCode::Lushr => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.ushr(&right.into_long()?)?);
 
                 1
             }
             OpCode::Iand => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.and(&right.into_int()?)?);
 
                 1
             }
             OpCode::Land => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.and(&right.into_long()?)?);
 
                 1
             }
             OpCode::Ior => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.or(&right.into_int()?)?);
 
                 1
             }
             OpCode::Lor => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.or(&right.into_long()?)?);
 
                 1
             }
             OpCode::Ixor => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_int()?.xor(&right.into_int()?)?);
 
                 1
             }
             OpCode::Lxor => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.xor(&right.into_long()?)?);
 
                 1
             }
             OpCode::Iinc => {
-                let index = code[*pc + 1];
-                let r#const = code[*pc + 2] as i8;
-                let local = locals.get_mut(index as usize).unwrap();
+                let index = Self::byte(code, *pc + 1)?;
+                let r#const = Self::byte(code, *pc + 2)? as i8;
+                let local = Self::local_mut(locals, index as usize)?;
 
                 if let Primitive::Int(raw) = local.into_int()? {
-                    *local = Primitive::Int(raw + r#const as i32);
+                    *local = Primitive::Int(raw.wrapping_add(r#const as i32));
                 } else {
                     return Err(WasmJVMError::TODO(29));
                 }
 
                 3
             }
-            OpCode::I2l | OpCode::I2f | OpCode::I2d => {
-                let value = stack.pop().unwrap();
+            OpCode::I2l | OpCode::F2l | OpCode::D2l => {
+                let value = Self::pop(stack)?;
 
-                stack.push(value.into_int()?);
+                stack.push(value.into_long()?);
 
                 1
             }
-            OpCode::L2i | OpCode::L2f | OpCode::L2d => {
-                let value = stack.pop().unwrap();
+            OpCode::L2i | OpCode::F2i | OpCode::D2i => {
+                let value = Self::pop(stack)?;
 
-                stack.push(value.into_long()?);
+                stack.push(value.into_int()?);
 
                 1
             }
-            OpCode::F2i | OpCode::F2l | OpCode::F2d => {
-                let value = stack.pop().unwrap();
+            OpCode::I2f | OpCode::L2f | OpCode::D2f => {
+                let value = Self::pop(stack)?;
 
                 stack.push(value.into_float()?);
 
                 1
             }
-            OpCode::D2i | OpCode::D2l | OpCode::D2f => {
-                let value = stack.pop().unwrap();
+            OpCode::I2d | OpCode::L2d | OpCode::F2d => {
+                let value = Self::pop(stack)?;
 
                 stack.push(value.into_double()?);
 
                 1
             }
             OpCode::I2b => {
-                let value = stack.pop().unwrap();
+                let value = Self::pop(stack)?.as_int()?;
 
-                stack.push(value.into_byte()?);
+                stack.push(Primitive::Int(value as i8 as i32));
 
                 1
             }
             OpCode::I2c => {
-                let value = stack.pop().unwrap();
+                let value = Self::pop(stack)?.as_int()?;
 
-                stack.push(value.into_char()?);
+                stack.push(Primitive::Int(value as u16 as i32));
 
                 1
             }
             OpCode::I2s => {
-                let value = stack.pop().unwrap();
+                let value = Self::pop(stack)?.as_int()?;
 
-                stack.push(value.into_short()?);
+                stack.push(Primitive::Int(value as i16 as i32));
 
                 1
             }
             OpCode::Lcmp => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_long()?.cmp(&right.into_long()?)?);
 
                 1
             }
             OpCode::Fcmpl => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_float()?.cmpl(&right.into_float()?)?);
 
                 1
             }
             OpCode::Fcmpg => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_float()?.cmpg(&right.into_float()?)?);
 
                 1
             }
             OpCode::Dcmpl => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_double()?.cmpl(&right.into_double()?)?);
 
                 1
             }
             OpCode::Dcmpg => {
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 stack.push(left.into_double()?.cmpg(&right.into_double()?)?);
 
@@ -1298,11 +1736,11 @@ impl Thread {
             | OpCode::Ifge
             | OpCode::Ifgt
             | OpCode::Ifle => {
-                let b1 = code[*pc + 1] as u16;
-                let b2 = code[*pc + 2] as u16;
+                let b1 = Self::byte(code, *pc + 1)? as u16;
+                let b2 = Self::byte(code, *pc + 2)? as u16;
                 let branch = (b1 << 8 | b2) as i16;
 
-                let value = stack.pop().unwrap().into_int()?;
+                let value = Self::pop(stack)?.into_int()?;
                 let int = if let Primitive::Int(value) = value {
                     value
                 } else {
@@ -1327,11 +1765,11 @@ impl Thread {
                 }
             }
             OpCode::IfNull | OpCode::IfNonNull => {
-                let b1 = code[*pc + 1] as u16;
-                let b2 = code[*pc + 2] as u16;
+                let b1 = Self::byte(code, *pc + 1)? as u16;
+                let b2 = Self::byte(code, *pc + 2)? as u16;
                 let branch = (b1 << 8 | b2) as i16;
 
-                let value = stack.pop().unwrap();
+                let value = Self::pop(stack)?;
                 // TODO: Check if correct.
                 let condition = (opcode_raw == OpCode::IfNull as u8) == value.is_null();
 
@@ -1347,12 +1785,12 @@ impl Thread {
             | OpCode::IfIcmpge
             | OpCode::IfIcmpgt
             | OpCode::IfIcmple => {
-                let b1 = code[*pc + 1] as u16;
-                let b2 = code[*pc + 2] as u16;
+                let b1 = Self::byte(code, *pc + 1)? as u16;
+                let b2 = Self::byte(code, *pc + 2)? as u16;
                 let branch = (b1 << 8 | b2) as i16;
 
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 if let Primitive::Int(cmp) = left.into_int()?.cmp(&right.into_int()?)? {
                     let condition: bool = match opcode {
@@ -1371,16 +1809,19 @@ impl Thread {
                         3
                     }
                 } else {
-                    unreachable!();
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected an int operand for {:?}",
+                        opcode
+                    )));
                 }
             }
             OpCode::IfAcmpeq | OpCode::IfAcmpne => {
-                let b1 = code[*pc + 1] as u16;
-                let b2 = code[*pc + 2] as u16;
+                let b1 = Self::byte(code, *pc + 1)? as u16;
+                let b2 = Self::byte(code, *pc + 2)? as u16;
                 let branch = (b1 << 8 | b2) as i16;
 
-                let right = stack.pop().unwrap();
-                let left = stack.pop().unwrap();
+                let right = Self::pop(stack)?;
+                let left = Self::pop(stack)?;
 
                 if let Primitive::Int(cmp) = left.cmp(&right)? {
                     let condition: bool = match opcode {
@@ -1395,49 +1836,56 @@ impl Thread {
                         3
                     }
                 } else {
-                    unreachable!();
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected an int operand for {:?}",
+                        opcode
+                    )));
                 }
             }
             OpCode::Goto => {
-                let b1 = code[*pc + 1] as u16;
-                let b2 = code[*pc + 2] as u16;
+                let b1 = Self::byte(code, *pc + 1)? as u16;
+                let b2 = Self::byte(code, *pc + 2)? as u16;
                 let branch = (b1 << 8 | b2) as i16;
 
                 branch as isize
             }
-            OpCode::Jsr => todo!(),
-            OpCode::Ret => todo!(),
+            OpCode::Jsr => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
+            OpCode::Ret => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
             OpCode::Tableswitch => {
                 let offset = if (*pc + 1) % 4 == 0 { 1 } else { 5 - ((*pc + 1) % 4) };
 
-                let defaultbyte1 = code[*pc + offset] as u32;
-                let defaultbyte2 = code[*pc + 1 + offset] as u32;
-                let defaultbyte3 = code[*pc + 2 + offset] as u32;
-                let defaultbyte4 = code[*pc + 3 + offset] as u32;
+                let defaultbyte1 = Self::byte(code, *pc + offset)? as u32;
+                let defaultbyte2 = Self::byte(code, *pc + 1 + offset)? as u32;
+                let defaultbyte3 = Self::byte(code, *pc + 2 + offset)? as u32;
+                let defaultbyte4 = Self::byte(code, *pc + 3 + offset)? as u32;
                 let default = ((defaultbyte1 << 24) | (defaultbyte2 << 16) | (defaultbyte3 << 8) | defaultbyte4) as i32;
 
-                let lowbyte1 = code[*pc + 4 + offset] as u32;
-                let lowbyte2 = code[*pc + 5 + offset] as u32;
-                let lowbyte3 = code[*pc + 6 + offset] as u32;
-                let lowbyte4 = code[*pc + 7 + offset] as u32;
+                let lowbyte1 = Self::byte(code, *pc + 4 + offset)? as u32;
+                let lowbyte2 = Self::byte(code, *pc + 5 + offset)? as u32;
+                let lowbyte3 = Self::byte(code, *pc + 6 + offset)? as u32;
+                let lowbyte4 = Self::byte(code, *pc + 7 + offset)? as u32;
                 let low = ((lowbyte1 << 24) | (lowbyte2 << 16) | (lowbyte3 << 8) | lowbyte4) as i32;
 
-                let highbyte1 = code[*pc + 8 + offset] as u32;
-                let highbyte2 = code[*pc + 9 + offset] as u32;
-                let highbyte3 = code[*pc + 10 + offset] as u32;
-                let highbyte4 = code[*pc + 11 + offset] as u32;
+                let highbyte1 = Self::byte(code, *pc + 8 + offset)? as u32;
+                let highbyte2 = Self::byte(code, *pc + 9 + offset)? as u32;
+                let highbyte3 = Self::byte(code, *pc + 10 + offset)? as u32;
+                let highbyte4 = Self::byte(code, *pc + 11 + offset)? as u32;
                 let high = ((highbyte1 << 24) | (highbyte2 << 16) | (highbyte3 << 8) | highbyte4) as i32;
 
-                if let Primitive::Int(index) = stack.pop().unwrap().into_int()? {
+                if let Primitive::Int(index) = Self::pop(stack)?.into_int()? {
                     let offset = if index < low || index > high {
                         default as isize
                     } else {
-                        let jumpindex = index as isize * 4;
+                        let jumpindex = (index - low) as isize * 4;
 
-                        let jumpbyte1 = code[((*pc + 12) as isize + offset as isize + jumpindex) as usize] as u32;
-                        let jumpbyte2 = code[((*pc + 13) as isize + offset as isize + jumpindex) as usize] as u32;
-                        let jumpbyte3 = code[((*pc + 14) as isize + offset as isize + jumpindex) as usize] as u32;
-                        let jumpbyte4 = code[((*pc + 15) as isize + offset as isize + jumpindex) as usize] as u32;
+                        let jumpbyte1 = Self::byte(code, ((*pc + 12) as isize + offset as isize + jumpindex) as usize)? as u32;
+                        let jumpbyte2 = Self::byte(code, ((*pc + 13) as isize + offset as isize + jumpindex) as usize)? as u32;
+                        let jumpbyte3 = Self::byte(code, ((*pc + 14) as isize + offset as isize + jumpindex) as usize)? as u32;
+                        let jumpbyte4 = Self::byte(code, ((*pc + 15) as isize + offset as isize + jumpindex) as usize)? as u32;
                         let jump = ((jumpbyte1 << 24) | (jumpbyte2 << 16) | (jumpbyte3 << 8) | jumpbyte4) as i32;
 
                         jump as isize
@@ -1445,40 +1893,43 @@ impl Thread {
 
                     offset
                 } else {
-                    unreachable!();
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected an int operand for {:?}",
+                        opcode
+                    )));
                 }
             },
             OpCode::Lookupswitch => {
                 let offset = if (*pc + 1) % 4 == 0 { 1 } else { 5 - ((*pc + 1) % 4) };
 
-                let defaultbyte1 = code[*pc + offset] as u32;
-                let defaultbyte2 = code[*pc + 1 + offset] as u32;
-                let defaultbyte3 = code[*pc + 2 + offset] as u32;
-                let defaultbyte4 = code[*pc + 3 + offset] as u32;
+                let defaultbyte1 = Self::byte(code, *pc + offset)? as u32;
+                let defaultbyte2 = Self::byte(code, *pc + 1 + offset)? as u32;
+                let defaultbyte3 = Self::byte(code, *pc + 2 + offset)? as u32;
+                let defaultbyte4 = Self::byte(code, *pc + 3 + offset)? as u32;
                 let default = ((defaultbyte1 << 24) | (defaultbyte2 << 16) | (defaultbyte3 << 8) | defaultbyte4) as i32;
 
-                let npairsbyte1 = code[*pc + 4 + offset] as u32;
-                let npairsbyte2 = code[*pc + 5 + offset] as u32;
-                let npairsbyte3 = code[*pc + 6 + offset] as u32;
-                let npairsbyte4 = code[*pc + 7 + offset] as u32;
+                let npairsbyte1 = Self::byte(code, *pc + 4 + offset)? as u32;
+                let npairsbyte2 = Self::byte(code, *pc + 5 + offset)? as u32;
+                let npairsbyte3 = Self::byte(code, *pc + 6 + offset)? as u32;
+                let npairsbyte4 = Self::byte(code, *pc + 7 + offset)? as u32;
                 let npairs = ((npairsbyte1 << 24) | (npairsbyte2 << 16) | (npairsbyte3 << 8) | npairsbyte4) as i32;
 
-                if let Primitive::Int(key) = stack.pop().unwrap().into_int()? {
+                if let Primitive::Int(key) = Self::pop(stack)?.into_int()? {
                     // TODO: Binary search
 
                     let mut jump_offset = None;
                     for i in 0..npairs {
-                        let intbyte1 = code[*pc + 8 + offset + i as usize * 8] as u32;
-                        let intbyte2 = code[*pc + 9 + offset + i as usize * 8] as u32;
-                        let intbyte3 = code[*pc + 10 + offset + i as usize * 8] as u32;
-                        let intbyte4 = code[*pc + 11 + offset + i as usize * 8] as u32;
+                        let intbyte1 = Self::byte(code, *pc + 8 + offset + i as usize * 8)? as u32;
+                        let intbyte2 = Self::byte(code, *pc + 9 + offset + i as usize * 8)? as u32;
+                        let intbyte3 = Self::byte(code, *pc + 10 + offset + i as usize * 8)? as u32;
+                        let intbyte4 = Self::byte(code, *pc + 11 + offset + i as usize * 8)? as u32;
                         let int = ((intbyte1 << 24) | (intbyte2 << 16) | (intbyte3 << 8) | intbyte4) as i32;
 
                         if int == key {
-                            let jump1 = code[*pc + 12 + offset + i as usize * 8] as u32;
-                            let jump2 = code[*pc + 13 + offset + i as usize * 8] as u32;
-                            let jump3 = code[*pc + 14 + offset + i as usize * 8] as u32;
-                            let jump4 = code[*pc + 15 + offset + i as usize * 8] as u32;
+                            let jump1 = Self::byte(code, *pc + 12 + offset + i as usize * 8)? as u32;
+                            let jump2 = Self::byte(code, *pc + 13 + offset + i as usize * 8)? as u32;
+                            let jump3 = Self::byte(code, *pc + 14 + offset + i as usize * 8)? as u32;
+                            let jump4 = Self::byte(code, *pc + 15 + offset + i as usize * 8)? as u32;
                             let jump = ((jump1 << 24) | (jump2 << 16) | (jump3 << 8) | jump4) as i32;
 
                             jump_offset = Some(jump as isize);
@@ -1492,7 +1943,10 @@ impl Thread {
                         default as isize
                     }
                 } else {
-                    unreachable!();
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected an int operand for {:?}",
+                        opcode
+                    )));
                 }
             },
             OpCode::Ireturn
@@ -1500,7 +1954,7 @@ impl Thread {
             | OpCode::Freturn
             | OpCode::Dreturn
             | OpCode::Areturn => {
-                let value = stack.pop().unwrap();
+                let value = Self::pop(stack)?;
 
                 r#return = Some(match opcode {
                     OpCode::Ireturn => value.into_int()?,
@@ -1519,33 +1973,45 @@ impl Thread {
                 1
             }
             OpCode::GetStatic => {
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
 
-                let field_ref = metadata.constant(index);
+                let field_ref = metadata.constant(index)?;
 
                 if let Constant::FieldRef(field_ref) = field_ref {
-                    if let Ok(field) = global.static_field(field_ref) {
-                        stack.push(field);
+                    global.resolve_class(&field_ref.class)?;
+                    global.check_field_access(metadata.this_class(), field_ref)?;
+                    let class_index = global.resolve_field(field_ref, true)?;
 
-                        3
-                    } else {
-                        0
+                    if let Some(frames) = Self::initialize_class(global, thread_id, class_index)? {
+                        return Ok((frames, None, 0, None));
                     }
+
+                    stack.push(global.static_field(field_ref)?);
+
+                    3
                 } else {
                     return Err(WasmJVMError::TODO(32));
                 }
             }
             OpCode::PutStatic => {
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
 
-                let field_ref = metadata.constant(index);
+                let field_ref = metadata.constant(index)?;
 
                 if let Constant::FieldRef(field_ref) = field_ref {
-                    let value = stack.pop().unwrap();
+                    global.resolve_class(&field_ref.class)?;
+                    global.check_field_access(metadata.this_class(), field_ref)?;
+                    let class_index = global.resolve_field(field_ref, true)?;
+
+                    if let Some(frames) = Self::initialize_class(global, thread_id, class_index)? {
+                        return Ok((frames, None, 0, None));
+                    }
+
+                    let value = Self::pop(stack)?;
 
                     global.static_field_set(field_ref, value)?;
                 } else {
@@ -1555,22 +2021,18 @@ impl Thread {
                 3
             }
             OpCode::GetField => {
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
 
-                let field_ref = metadata.constant(index);
-                let reference = stack.pop().unwrap();
-                let object = global.reference_p(&reference)?;
+                let field_ref = metadata.constant(index)?;
+                let reference = Self::pop(stack)?;
 
                 if let Constant::FieldRef(field_ref) = field_ref {
-                    let field = object.fields.get(&field_ref.name);
+                    global.resolve_class(&field_ref.class)?;
+                    global.check_field_access(metadata.this_class(), field_ref)?;
 
-                    if let Some(field) = field {
-                        stack.push(field.clone());
-                    } else {
-                        return Err(WasmJVMError::TODO(34));
-                    }
+                    stack.push(global.field(&reference, field_ref)?);
                 } else {
                     return Err(WasmJVMError::TODO(35));
                 }
@@ -1578,15 +2040,18 @@ impl Thread {
                 3
             }
             OpCode::PutField => {
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
 
-                let field_ref = metadata.constant(index);
-                let value = stack.pop().unwrap();
-                let object_ref = stack.pop().unwrap();
+                let field_ref = metadata.constant(index)?;
+                let value = Self::pop(stack)?;
+                let object_ref = Self::pop(stack)?;
 
                 if let Constant::FieldRef(field_ref) = field_ref {
+                    global.resolve_class(&field_ref.class)?;
+                    global.check_field_access(metadata.this_class(), field_ref)?;
+
                     global.field_set(object_ref, field_ref, value)?;
                 } else {
                     return Err(WasmJVMError::TODO(36));
@@ -1595,74 +2060,117 @@ impl Thread {
                 3
             }
             OpCode::InvokeSpecial | OpCode::InvokeVirtual => {
-                // TODO: Check InvokeVirtual
-
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
 
-                let method_ref = metadata.constant(index);
+                let method_ref = metadata.constant(index)?;
+
+                // invokespecial may also name an interface method, for private and `super` calls.
+                if let Constant::MethodRef(method_ref) | Constant::InterfaceMethodRef(method_ref) = method_ref {
+                    global.constant_class(&method_ref.class)?;
+                    global.check_method_access(metadata.this_class(), method_ref)?;
 
-                if let Constant::MethodRef(method_ref) = method_ref {
                     let locals = Self::pop_locals(&method_ref.descriptor, stack)?;
-                    let this = stack.pop().unwrap();
+                    let this = Self::pop(stack)?;
 
-                    frames.push((method_ref.clone(), Some(this), locals));
+                    let mut method_ref = method_ref.clone();
+                    if matches!(opcode, OpCode::InvokeVirtual) && !this.is_null() {
+                        method_ref.class = global.dispatch_class(&this, &method_ref)?;
+                    }
+
+                    frames.push((method_ref, Some(this), locals));
                 } else {
-                    panic!("Expecting a method.");
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected a method at constant {}",
+                        index
+                    )));
                 }
 
                 3
             }
             OpCode::InvokeStatic => {
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
 
-                let constant = metadata.constant(index);
+                let constant = metadata.constant(index)?;
+
+                if let Constant::MethodRef(method_ref) | Constant::InterfaceMethodRef(method_ref) = constant {
+                    let class_index = global.resolve_class(&method_ref.class)?;
+                    global.check_method_access(metadata.this_class(), method_ref)?;
+
+                    if let Some(frames) = Self::initialize_class(global, thread_id, class_index)? {
+                        return Ok((frames, None, 0, None));
+                    }
 
-                if let Constant::MethodRef(method_ref) = constant {
                     let locals = Self::pop_locals(&method_ref.descriptor, stack)?;
 
                     frames.push((method_ref.clone(), None, locals));
                 } else {
-                    panic!("Expecting a method.");
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected a method at constant {}",
+                        index
+                    )));
                 }
 
                 3
             }
             OpCode::InvokeInterface => {
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
-                let _count = code[*pc + 3] as u8;
-                let _zero = code[*pc + 4] as u8;
+                let _count = Self::byte(code, *pc + 3)? as u8;
+                let _zero = Self::byte(code, *pc + 4)? as u8;
 
-                let constant = metadata.constant(index);
+                let constant = metadata.constant(index)?;
 
                 if let Constant::InterfaceMethodRef(method_ref) = constant {
+                    global.resolve_class(&method_ref.class)?;
+                    global.check_method_access(metadata.this_class(), method_ref)?;
+
                     let locals = Self::pop_locals(&method_ref.descriptor, stack)?;
-                    let this = stack.pop().unwrap();
+                    let this = Self::pop(stack)?;
+
+                    let mut method_ref = method_ref.clone();
+                    if !this.is_null() {
+                        method_ref.class = global.dispatch_class(&this, &method_ref)?;
+                    }
 
-                    frames.push((method_ref.clone(), Some(this), locals));
+                    frames.push((method_ref, Some(this), locals));
                 } else {
-                    panic!("Expected interface method.");
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected an interface method at constant {}",
+                        index
+                    )));
                 }
 
                 5
             }
-            OpCode::InvokeDynamic => todo!(),
+            OpCode::InvokeDynamic => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
             OpCode::New => {
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
 
-                let class_ref = metadata.constant(index);
+                let class_ref = metadata.constant(index)?;
 
                 let instance_ref = if let Constant::Class { name, .. } = &class_ref {
+                    let class_index = global.resolve_class(name)?;
+                    global.check_class_access(metadata.this_class(), name)?;
+
+                    if let Some(frames) = Self::initialize_class(global, thread_id, class_index)? {
+                        return Ok((frames, None, 0, None));
+                    }
+
                     global.new_instance(name)?
                 } else {
-                    panic!("Expecting a class.");
+                    return Err(WasmJVMError::VerifyError(format!(
+                        "Expected a class at constant {}",
+                        index
+                    )));
                 };
 
                 stack.push(Primitive::Reference(instance_ref));
@@ -1670,26 +2178,25 @@ impl Thread {
                 3
             }
             OpCode::NewArray => {
-                // TODO: Check constant pool.
-                let _type = code[*pc + 1] as usize;
-                let count = stack.pop().unwrap().into_int()?;
+                let component = Array::atype_component(Self::byte(code, *pc + 1)?)?;
+                let count = Self::pop(stack)?.as_int()?;
 
-                if let Primitive::Int(count) = count {
-                    let index = global.new_object(Object::new_empty_array(count as usize)?)?;
-                    stack.push(Primitive::Reference(index));
-                } else {
-                    return Err(WasmJVMError::TODO(37));
-                }
+                let index = global.new_array(&component, count)?;
+                stack.push(Primitive::Reference(index));
 
                 2
             }
             OpCode::ANewArray => {
-                // TODO: Check constant pool.
-                let _index = (code[*pc + 1] as usize) << 8 | code[*pc + 2] as usize;
-                let count = stack.pop().unwrap().into_int()?;
+                let index = (Self::byte(code, *pc + 1)? as usize) << 8 | Self::byte(code, *pc + 2)? as usize;
+                let count = Self::pop(stack)?.as_int()?;
+
+                if let Constant::Class { name } = metadata.constant(index)? {
+                    global.constant_class(name)?;
+                    global.check_class_access(metadata.this_class(), name)?;
 
-                if let Primitive::Int(count) = count {
-                    let index = global.new_object(Object::new_empty_array(count as usize)?)?;
+                    let component = Type::from_class_name(name)?;
+
+                    let index = global.new_array(&component, count)?;
                     stack.push(Primitive::Reference(index));
                 } else {
                     return Err(WasmJVMError::TODO(38));
@@ -1698,145 +2205,59 @@ impl Thread {
                 3
             }
             OpCode::ArrayLength => {
-                let array_ref = stack.pop().unwrap();
-                let object = global.reference_p(&array_ref)?;
+                let array_ref = Self::pop(stack)?;
+                let length = global.array(&array_ref)?.len();
 
-                if let RustObject::Array(raw) = object.inner() {
-                    stack.push(Primitive::Int(raw.len() as i32));
-                } else {
-                    return Err(WasmJVMError::TODO(39));
-                }
+                stack.push(Primitive::Int(length as i32));
 
                 1
             }
             OpCode::Athrow => {
-                let object_ref = stack.pop().unwrap();
+                let object_ref = Self::pop(stack)?;
+
+                if object_ref.is_null() {
+                    return Err(WasmJVMError::NullPointerException(format!(
+                        "Cannot throw exception because the value is null"
+                    )));
+                }
 
                 throw = Some(object_ref);
 
                 1
             }
             OpCode::Instanceof | OpCode::CheckCast => {
-                let i1 = code[*pc + 1] as u16;
-                let i2 = code[*pc + 2] as u16;
+                let i1 = Self::byte(code, *pc + 1)? as u16;
+                let i2 = Self::byte(code, *pc + 2)? as u16;
                 let index = (i1 << 8 | i2) as usize;
-                let class_ref = metadata.constant(index);
-                let object_ref = stack.pop().unwrap();
-
-                let mut instanceof = true;
-                if let Constant::Class { name } = class_ref {
-                    let r#type: Type;
-                    if name.starts_with("[") {
-                        let descriptor = Descriptor::from_str(name)?;
-                        r#type = descriptor.output().clone();
-                    } else {
-                        r#type = Type::Single(SingleType::Object(name.clone()));
-                    }
-
-                    let single_type: SingleType;
-                    let array_size: usize;
-                    match r#type {
-                        Type::Single(r#type) => {
-                            single_type = r#type;
-                            array_size = 0;
-                        }
-                        Type::Array(r#type, size) => {
-                            single_type = r#type;
-                            array_size = size;
-                        }
-                    }
+                let class_ref = metadata.constant(index)?;
+                let object_ref = Self::pop(stack)?;
 
-                    let class_type: String;
-                    if let SingleType::Object(class) = single_type {
-                        class_type = class;
-                    } else {
-                        todo!("{:?}", name);
-                    }
-
-                    let mut object_array_size = 0;
-                    let mut array_queue: Vec<&Primitive> = vec![&object_ref];
-                    let mut next_array_queue: Vec<&Primitive> = Vec::new();
-
-                    while array_queue.len() > 0 {
-                        while array_queue.len() > 0 {
-                            let next_primitive = array_queue.pop().unwrap();
-
-                            match next_primitive {
-                                Primitive::Reference(index) => {
-                                    let next_object = global.reference(*index)?;
-
-                                    match next_object.inner() {
-                                        RustObject::Array(primitives) => {
-                                            for primitive in primitives.iter() {
-                                                next_array_queue.push(primitive);
-                                            }
-                                        }
-                                        _ => {
-                                            let mut class_index = next_object.class().unwrap();
-
-                                            loop {
-                                                let class = global.class(class_index)?;
-                                                let class_metadata = class.metadata();
-
-                                                if class_metadata.this_class() == &class_type {
-                                                    break;
-                                                }
-
-                                                if class_metadata.interface(&class_type).is_ok() {
-                                                    break;
-                                                }
-
-                                                if let Some(super_class) =
-                                                    class_metadata.super_class()
-                                                {
-                                                    if super_class == &class_type {
-                                                        break;
-                                                    }
-
-                                                    class_index =
-                                                        global.class_index(super_class)?;
-                                                } else {
-                                                    instanceof = false;
-                                                    break;
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                _ => instanceof = false,
-                            };
-
-                            if !instanceof {
-                                break;
-                            }
-                        }
-
-                        if !instanceof || next_array_queue.len() == 0 {
-                            break;
-                        }
-
-                        object_array_size += 1;
-                        if object_array_size > array_size {
-                            instanceof = false;
-                            break;
-                        }
-
-                        array_queue.append(&mut next_array_queue);
-                    }
+                let name = if let Constant::Class { name } = class_ref {
+                    name
                 } else {
                     return Err(WasmJVMError::TODO(40));
-                }
+                };
+
+                global.constant_class(name)?;
+                global.check_class_access(metadata.this_class(), name)?;
+
+                let instanceof = global.is_instance(&object_ref, name)?;
 
                 match opcode {
                     OpCode::Instanceof => {
                         stack.push(Primitive::Int(instanceof as i32));
                     }
                     OpCode::CheckCast => {
-                        if instanceof {
+                        if instanceof || object_ref.is_null() {
                             stack.push(object_ref);
                         } else {
-                            // TODO: Better output.
-                            return Err(WasmJVMError::ClassCastException(String::new()));
+                            let object_class = global.object_class_name(&object_ref)?;
+
+                            return Err(WasmJVMError::ClassCastException(format!(
+                                "class {} cannot be cast to class {}",
+                                object_class.replace('/', "."),
+                                name.replace('/', ".")
+                            )));
                         }
                     }
                     _ => unreachable!(),
@@ -1844,33 +2265,57 @@ impl Thread {
 
                 3
             }
-            OpCode::MonitorEnter => todo!(),
-            OpCode::MonitorExit => todo!(),
-            OpCode::Wide => todo!(),
+            OpCode::MonitorEnter => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
+            OpCode::MonitorExit => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
+            OpCode::Wide => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
             OpCode::MultiANewArray => {
-                // TODO: Check constant pool.
-                let _index = (code[*pc + 1] as usize) << 8 | code[*pc + 2] as usize;
-                let dimensions = code[*pc + 3] as u8;
+                let index = (Self::byte(code, *pc + 1)? as usize) << 8 | Self::byte(code, *pc + 2)? as usize;
+                let dimensions = Self::byte(code, *pc + 3)? as u8;
                 let mut counts = Vec::new();
 
                 for _ in 0..dimensions {
-                    if let Primitive::Int(int) = stack.pop().unwrap() {
-                        counts.push(int as usize);
-                    } else {
-                        todo!();
-                    }
+                    counts.push(Self::pop(stack)?.as_int()?);
                 }
                 counts.reverse();
 
-                stack.push(Object::new_deep_array(global, &counts, 0)?);
+                if let Some(count) = counts.iter().find(|count| **count < 0) {
+                    return Err(WasmJVMError::NegativeArraySizeException(format!("{}", count)));
+                }
+
+                if let Constant::Class { name } = metadata.constant(index)? {
+                    global.constant_class(name)?;
+                    global.check_class_access(metadata.this_class(), name)?;
+
+                    let r#type = Type::from_class_name(name)?;
+
+                    stack.push(Object::new_deep_array(global, &r#type, &counts, 0)?);
+                } else {
+                    return Err(WasmJVMError::TODO(41));
+                }
 
                 4
             }
-            OpCode::GotoW => todo!(),
-            OpCode::JsrW => todo!(),
-            OpCode::Breakpoint => todo!(),
-            OpCode::Impdep1 => todo!(),
-            OpCode::Impdep2 => todo!(),
+            OpCode::GotoW => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
+            OpCode::JsrW => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
+            OpCode::Breakpoint => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
+            OpCode::Impdep1 => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
+            OpCode::Impdep2 => {
+                return Err(WasmJVMError::InternalError(format!("Unsupported opcode {:?}", opcode)))
+            }
         };
 
         Ok((frames, r#return, offset, throw))
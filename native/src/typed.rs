@@ -0,0 +1,305 @@
+//! The Rust types `#[native]` functions take and return, and how they map to Java values.
+
+use std::{future::Future, pin::Pin};
+
+use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
+use wasmjvm_common::WasmJVMError;
+
+use crate::{Array, NativeEnv, NativeInterface, Primitive, JAVA_OBJECT, JAVA_STRING};
+
+pub type NativeFuture<'a> = Pin<Box<dyn Future<Output = Result<Primitive, WasmJVMError>> + 'a>>;
+
+/// The parameter and return types of a native's descriptor.
+pub type NativeSignature = (Vec<Type>, Type);
+
+/// A native declared with `#[native]`, collected from every crate linked into the VM.
+pub struct NativeRegistration {
+    /// The native library that links it, `JAVA_LIBRARY` unless the attribute names another.
+    pub library: &'static str,
+    pub class: &'static str,
+    pub name: &'static str,
+    pub signature: fn() -> NativeSignature,
+    pub method: for<'a> fn(&'a mut NativeEnv) -> NativeFuture<'a>,
+}
+
+inventory::collect!(NativeRegistration);
+
+impl NativeInterface {
+    /// Registers the `#[native]` functions that belong to `library`.
+    pub(crate) fn register_declared(self: &mut Self, library: &str) -> Result<(), WasmJVMError> {
+        for registration in inventory::iter::<NativeRegistration> {
+            if registration.library != library {
+                continue;
+            }
+
+            let (parameters, output) = (registration.signature)();
+            let method_ref = MethodRef::new(
+                registration.class.to_string(),
+                registration.name.to_string(),
+                Descriptor::new(parameters, output),
+            );
+
+            self.register(method_ref, Box::new(registration.method))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn mismatch(expected: &Type, value: &Primitive) -> WasmJVMError {
+    WasmJVMError::IllegalArgumentException(format!("argument type mismatch: expected {:?} but got {:?}", expected, value))
+}
+
+/// The native's arguments, with `this` first, when there are as many as the descriptor says.
+pub fn native_variables(env: &NativeEnv, count: usize) -> Result<Vec<Primitive>, WasmJVMError> {
+    let variables = env.variables();
+
+    if variables.len() != count {
+        return Err(WasmJVMError::IllegalArgumentException(format!(
+            "wrong number of arguments: expected {} but got {}",
+            count,
+            variables.len()
+        )));
+    }
+
+    Ok(variables.clone())
+}
+
+pub trait NativeType {
+    fn java_type() -> Type;
+}
+
+pub trait FromNative: Sized {
+    fn from_native(env: &NativeEnv, value: &Primitive) -> Result<Self, WasmJVMError>;
+}
+
+pub trait IntoNative {
+    fn into_native(self: Self, env: &mut NativeEnv) -> Result<Primitive, WasmJVMError>;
+}
+
+macro_rules! native_primitive {
+    ($rust: ty, $single: ident, $variant: ident) => {
+        impl NativeType for $rust {
+            fn java_type() -> Type {
+                Type::Single(SingleType::$single)
+            }
+        }
+
+        impl FromNative for $rust {
+            fn from_native(_env: &NativeEnv, value: &Primitive) -> Result<Self, WasmJVMError> {
+                let java_type = Self::java_type();
+
+                match value.into_type(&java_type) {
+                    Ok(Primitive::$variant(value)) => Ok(value as $rust),
+                    _ => Err(mismatch(&java_type, value)),
+                }
+            }
+        }
+
+        impl IntoNative for $rust {
+            fn into_native(self: Self, _env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+                Ok(Primitive::$variant(self as _))
+            }
+        }
+    };
+}
+
+native_primitive!(bool, Boolean, Boolean);
+native_primitive!(i8, Byte, Byte);
+native_primitive!(u8, Byte, Byte);
+native_primitive!(u16, Char, Char);
+native_primitive!(i16, Short, Short);
+native_primitive!(i32, Int, Int);
+native_primitive!(i64, Long, Long);
+native_primitive!(f32, Float, Float);
+native_primitive!(f64, Double, Double);
+
+impl NativeType for () {
+    fn java_type() -> Type {
+        Type::Single(SingleType::Void)
+    }
+}
+
+impl IntoNative for () {
+    fn into_native(self: Self, _env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+        Ok(Primitive::Void)
+    }
+}
+
+/// Any reference, including `null`. Its descriptor type is `java/lang/Object`; give `#[native]`
+/// a `descriptor` to declare a more specific class.
+#[derive(Debug, Clone)]
+pub struct JObject(pub Primitive);
+
+impl JObject {
+    pub fn is_null(self: &Self) -> bool {
+        self.0.is_null()
+    }
+
+    pub fn reference(self: &Self) -> Option<usize> {
+        match self.0 {
+            Primitive::Reference(index) => Some(index),
+            _ => None,
+        }
+    }
+}
+
+impl NativeType for JObject {
+    fn java_type() -> Type {
+        Type::Single(SingleType::Object(JAVA_OBJECT.to_string()))
+    }
+}
+
+impl FromNative for JObject {
+    fn from_native(_env: &NativeEnv, value: &Primitive) -> Result<Self, WasmJVMError> {
+        match value {
+            Primitive::Null | Primitive::Reference(..) => Ok(JObject(value.clone())),
+            _ => Err(mismatch(&Self::java_type(), value)),
+        }
+    }
+}
+
+impl IntoNative for JObject {
+    fn into_native(self: Self, _env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+        Ok(self.0)
+    }
+}
+
+/// A `java/lang/String`. A `null` argument throws a `NullPointerException`; take an
+/// `Option<JString>` to accept it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JString(pub String);
+
+impl NativeType for JString {
+    fn java_type() -> Type {
+        Type::Single(SingleType::Object(JAVA_STRING.to_string()))
+    }
+}
+
+impl FromNative for JString {
+    fn from_native(env: &NativeEnv, value: &Primitive) -> Result<Self, WasmJVMError> {
+        match value {
+            Primitive::Reference(..) => match env.global().java_string(value)? {
+                Some(string) => Ok(JString(string)),
+                None => Err(mismatch(&Self::java_type(), value)),
+            },
+            Primitive::Null => Err(WasmJVMError::NullPointerException(format!("String argument is null"))),
+            _ => Err(mismatch(&Self::java_type(), value)),
+        }
+    }
+}
+
+impl IntoNative for JString {
+    fn into_native(self: Self, env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+        Ok(Primitive::Reference(env.global_mut().new_java_string(self.0)?))
+    }
+}
+
+/// An array whose elements are copied in and out of the heap. A `null` argument throws a
+/// `NullPointerException`; take an `Option<JArray<T>>` to accept it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JArray<T>(pub Vec<T>);
+
+/// A Rust type that can be the element of a `JArray`.
+pub trait ArrayElement: NativeType + Sized {
+    fn from_array(array: &Array) -> Option<Vec<Self>>;
+    fn into_array(elements: Vec<Self>) -> Array;
+}
+
+macro_rules! array_element {
+    ($rust: ty, $variant: ident) => {
+        impl ArrayElement for $rust {
+            fn from_array(array: &Array) -> Option<Vec<Self>> {
+                match array {
+                    Array::$variant(values) => Some(values.iter().map(|value| *value as $rust).collect()),
+                    _ => None,
+                }
+            }
+
+            fn into_array(elements: Vec<Self>) -> Array {
+                Array::$variant(elements.into_iter().map(|value| value as _).collect())
+            }
+        }
+    };
+}
+
+array_element!(bool, Boolean);
+array_element!(i8, Byte);
+array_element!(u8, Byte);
+array_element!(u16, Char);
+array_element!(i16, Short);
+array_element!(i32, Int);
+array_element!(i64, Long);
+array_element!(f32, Float);
+array_element!(f64, Double);
+
+impl ArrayElement for JObject {
+    fn from_array(array: &Array) -> Option<Vec<Self>> {
+        match array {
+            Array::Reference(values) => Some(
+                values
+                    .iter()
+                    .map(|value| JObject(value.map_or(Primitive::Null, Primitive::Reference)))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn into_array(elements: Vec<Self>) -> Array {
+        Array::Reference(elements.iter().map(JObject::reference).collect())
+    }
+}
+
+impl<T: ArrayElement> NativeType for JArray<T> {
+    fn java_type() -> Type {
+        match T::java_type() {
+            Type::Single(single) => Type::Array(single, 1),
+            Type::Array(single, dimensions) => Type::Array(single, dimensions + 1),
+        }
+    }
+}
+
+impl<T: ArrayElement> FromNative for JArray<T> {
+    fn from_native(env: &NativeEnv, value: &Primitive) -> Result<Self, WasmJVMError> {
+        match value {
+            Primitive::Reference(..) => T::from_array(env.global().array(value)?)
+                .map(JArray)
+                .ok_or_else(|| mismatch(&Self::java_type(), value)),
+            Primitive::Null => Err(WasmJVMError::NullPointerException(format!("Array argument is null"))),
+            _ => Err(mismatch(&Self::java_type(), value)),
+        }
+    }
+}
+
+impl<T: ArrayElement> IntoNative for JArray<T> {
+    fn into_native(self: Self, env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+        let array = T::into_array(self.0);
+
+        Ok(Primitive::Reference(env.global_mut().new_array_from(&T::java_type(), array)?))
+    }
+}
+
+impl<T: NativeType> NativeType for Option<T> {
+    fn java_type() -> Type {
+        T::java_type()
+    }
+}
+
+impl<T: FromNative> FromNative for Option<T> {
+    fn from_native(env: &NativeEnv, value: &Primitive) -> Result<Self, WasmJVMError> {
+        match value {
+            Primitive::Null => Ok(None),
+            _ => T::from_native(env, value).map(Some),
+        }
+    }
+}
+
+impl<T: IntoNative> IntoNative for Option<T> {
+    fn into_native(self: Self, env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+        match self {
+            Some(value) => value.into_native(env),
+            None => Ok(Primitive::Null),
+        }
+    }
+}
@@ -1,69 +1,87 @@
 use std::{
     collections::HashMap,
     io::{BufRead, Read, Write},
+    sync::OnceLock,
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
-use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
-use wasmjvm_native::{
-    async_box, register_method, NativeEnv, NativeInterface, Primitive, RustObject,
-};
+use wasmjvm_common::WasmJVMError;
+use wasmjvm_native::{native, JObject, NativeEnv, NativeInterface};
+
+/// The host's natives: files, clocks and randomness. They outrank the VM's own natives.
+pub const OS_LIBRARY: &str = "os";
+pub const OS_PRIORITY: i32 = 10;
 
-pub fn register(interface: &mut NativeInterface) {
+/// Prepares the stream table. The natives themselves are `#[native]` functions of `OS_LIBRARY`.
+pub fn register(_interface: &mut NativeInterface) {
     unsafe {
         STREAMS = Some(HashMap::new());
     }
+}
+
+/// The host values of the system properties the VM can't know on its own.
+pub fn properties() -> Vec<(String, String)> {
+    let os_name = match std::env::consts::OS {
+        "linux" => "Linux",
+        "macos" => "Mac OS X",
+        "windows" => "Windows",
+        "freebsd" => "FreeBSD",
+        other => other,
+    };
+    let os_arch = match std::env::consts::ARCH {
+        "x86_64" => "amd64",
+        "x86" => "x86",
+        other => other,
+    };
+    let windows = cfg!(windows);
+
+    let mut properties = vec![
+        ("os.name".to_string(), os_name.to_string()),
+        ("os.arch".to_string(), os_arch.to_string()),
+        ("file.separator".to_string(), std::path::MAIN_SEPARATOR.to_string()),
+        ("path.separator".to_string(), if windows { ";" } else { ":" }.to_string()),
+        ("line.separator".to_string(), if windows { "\r\n" } else { "\n" }.to_string()),
+        ("java.io.tmpdir".to_string(), std::env::temp_dir().display().to_string()),
+    ];
+
+    if let Ok(directory) = std::env::current_dir() {
+        properties.push(("user.dir".to_string(), directory.display().to_string()));
+    }
+
+    if let Some(home) = std::env::var_os(if windows { "USERPROFILE" } else { "HOME" }) {
+        properties.push(("user.home".to_string(), home.to_string_lossy().to_string()));
+    }
+
+    if let Some(user) = std::env::var_os(if windows { "USERNAME" } else { "USER" }) {
+        properties.push(("user.name".to_string(), user.to_string_lossy().to_string()));
+    }
 
-    register_method!(
-        interface,
-        async_file_bind_read,
-        "java/io/FileInputStream",
-        "nativeBind",
-        vec![],
-        Type::Single(SingleType::Void)
-    );
-
-    register_method!(
-        interface,
-        async_file_read,
-        "java/io/FileInputStream",
-        "nativeRead",
-        vec![],
-        Type::Single(SingleType::Int)
-    );
-
-    register_method!(
-        interface,
-        async_file_bind_write,
-        "java/io/FileOutputStream",
-        "nativeBind",
-        vec![],
-        Type::Single(SingleType::Void)
-    );
-
-    register_method!(
-        interface,
-        async_file_write,
-        "java/io/FileOutputStream",
-        "nativeWrite",
-        vec![Type::Single(SingleType::Int)],
-        Type::Single(SingleType::Void)
-    );
-
-    register_method!(
-        interface,
-        async_random_long,
-        "java/util/Random",
-        "nativeNextLong",
-        vec![],
-        Type::Single(SingleType::Long)
-    );
+    properties
 }
 
+/// `System.nanoTime` only has to be monotonic, so it counts from the first call.
+static CLOCK_ORIGIN: OnceLock<Instant> = OnceLock::new();
+
 static mut STREAMS: Option<HashMap<usize, Box<dyn FileCursor>>> = None;
 
+fn io_error(error: std::io::Error) -> WasmJVMError {
+    WasmJVMError::InternalError(format!("I/O error: {}", error))
+}
+
+/// The reason the JDK puts in I/O messages, like `No such file or directory`, without the
+/// ` (os error 2)` that Rust appends.
+fn os_error_reason(error: &std::io::Error) -> String {
+    let reason = error.to_string();
+
+    match reason.find(" (os error ") {
+        Some(end) => reason[..end].to_string(),
+        None => reason,
+    }
+}
+
 trait FileCursor {
-    fn write(self: &mut Self, value: i32);
-    fn read(self: &mut Self) -> i32;
+    fn write(self: &mut Self, value: i32) -> Result<(), WasmJVMError>;
+    fn read(self: &mut Self) -> Result<i32, WasmJVMError>;
 }
 
 struct SystemStream {
@@ -75,27 +93,47 @@ impl SystemStream {
         Self { buffer: Vec::new() }
     }
 
-    fn buffer_read(self: &mut Self) {
-        std::io::stdout().flush().unwrap();
-        let line = std::io::stdin().lock().lines().next().unwrap().unwrap();
-        let mut bytes: Vec<u8> = line.into();
-        bytes.push('\n' as u8);
-        bytes.reverse();
-        self.buffer.append(&mut bytes);
+    fn buffer_read(self: &mut Self) -> Result<(), WasmJVMError> {
+        std::io::stdout().flush().map_err(io_error)?;
+
+        if let Some(line) = std::io::stdin().lock().lines().next() {
+            let mut bytes: Vec<u8> = line.map_err(io_error)?.into();
+            bytes.push('\n' as u8);
+            bytes.reverse();
+            self.buffer.append(&mut bytes);
+        }
+
+        Ok(())
     }
 }
 
 impl FileCursor for SystemStream {
-    fn write(self: &mut Self, value: i32) {
-        std::io::stdout().lock().write(&[value as u8]).unwrap();
+    fn write(self: &mut Self, value: i32) -> Result<(), WasmJVMError> {
+        std::io::stdout().lock().write(&[value as u8]).map_err(io_error)?;
+
+        Ok(())
     }
 
-    fn read(self: &mut Self) -> i32 {
+    fn read(self: &mut Self) -> Result<i32, WasmJVMError> {
         if self.buffer.is_empty() {
-            self.buffer_read();
+            self.buffer_read()?;
         }
 
-        self.buffer.pop().unwrap() as i32
+        Ok(self.buffer.pop().map_or(-1, |byte| byte as i32))
+    }
+}
+
+struct ErrorStream;
+
+impl FileCursor for ErrorStream {
+    fn write(self: &mut Self, value: i32) -> Result<(), WasmJVMError> {
+        std::io::stderr().lock().write(&[value as u8]).map_err(io_error)?;
+
+        Ok(())
+    }
+
+    fn read(self: &mut Self) -> Result<i32, WasmJVMError> {
+        Ok(-1)
     }
 }
 
@@ -104,97 +142,112 @@ struct FileStream {
 }
 
 impl FileStream {
-    pub fn new(path: String, is_read: bool) -> Self {
+    pub fn new(path: &str, is_read: bool) -> Result<Self, std::io::Error> {
         let file = if is_read {
-            std::fs::File::open(path).unwrap()
+            std::fs::File::open(path)?
         } else {
-            std::fs::File::create(path).unwrap()
+            std::fs::File::create(path)?
         };
 
-        Self { file }
+        Ok(Self { file })
     }
 }
 
 impl FileCursor for FileStream {
-    fn write(self: &mut Self, value: i32) {
-        self.file.write(&[value as u8]).unwrap();
+    fn write(self: &mut Self, value: i32) -> Result<(), WasmJVMError> {
+        self.file.write(&[value as u8]).map_err(io_error)?;
+
+        Ok(())
     }
 
-    fn read(self: &mut Self) -> i32 {
+    fn read(self: &mut Self) -> Result<i32, WasmJVMError> {
         let mut buffer = [0u8; 1];
-        self.file.read(&mut buffer).unwrap();
+        self.file.read(&mut buffer).map_err(io_error)?;
 
-        buffer[0] as i32
+        Ok(buffer[0] as i32)
     }
 }
 
-fn file_bind_mode(env: &mut NativeEnv, is_read: bool) -> Primitive {
-    if let [this_ref, ..] = &env.variables()[..] {
-        if let Primitive::Reference(this_index) = this_ref {
-            let this = env.reference(&this_ref).unwrap();
-            let path_ref = this.fields.get("path").unwrap();
-            let path_object = env.reference(&path_ref).unwrap();
-            if let RustObject::String(path) = path_object.inner() {
-                if let Some(streams) = unsafe { &mut STREAMS } {
-                    if !streams.contains_key(this_index) {
-                        let stream: Box<dyn FileCursor> = if path == "<sys>" {
-                            Box::new(SystemStream::new())
-                        } else {
-                            Box::new(FileStream::new(path.to_string(), is_read))
-                        };
-
-                        streams.insert(*this_index, stream);
-
-                        return Primitive::Void;
-                    }
+fn file_stream(this: &JObject) -> Result<&'static mut Box<dyn FileCursor>, WasmJVMError> {
+    unsafe { STREAMS.as_mut() }
+        .and_then(|streams| this.reference().and_then(|this_index| streams.get_mut(&this_index)))
+        .ok_or_else(|| WasmJVMError::IllegalStateException(format!("Stream {:?} is not bound", this)))
+}
+
+fn file_bind_mode(env: &mut NativeEnv, this: JObject, is_read: bool) -> Result<(), WasmJVMError> {
+    let path_ref = env.field(&this.0, "path")?;
+    let path = env
+        .string(&path_ref)?
+        .ok_or_else(|| WasmJVMError::NullPointerException(format!("path")))?;
+
+    let (streams, this_index) = match (unsafe { STREAMS.as_mut() }, this.reference()) {
+        (Some(streams), Some(this_index)) => (streams, this_index),
+        _ => return Err(env.invalid_arguments()),
+    };
+
+    if !streams.contains_key(&this_index) {
+        let stream: Box<dyn FileCursor> = if path == "<sys>" {
+            Box::new(SystemStream::new())
+        } else if path == "<err>" {
+            Box::new(ErrorStream)
+        } else {
+            match FileStream::new(&path, is_read) {
+                Ok(stream) => Box::new(stream),
+                Err(error) => {
+                    let message = format!("{} ({})", path, os_error_reason(&error));
+                    env.throw_new("java/io/FileNotFoundException", &message)?;
+
+                    return Ok(());
                 }
             }
-        }
+        };
+
+        streams.insert(this_index, stream);
     }
 
-    unreachable!();
+    Ok(())
 }
 
-async_box!(async_file_bind_read, file_bind_read);
-async fn file_bind_read(env: &mut NativeEnv) -> Primitive {
-    file_bind_mode(env, true)
+#[native("java/io/FileInputStream", "nativeBind", library = "os")]
+fn file_bind_read(env: &mut NativeEnv, this: JObject) -> Result<(), WasmJVMError> {
+    file_bind_mode(env, this, true)
 }
 
-async_box!(async_file_bind_write, file_bind_write);
-async fn file_bind_write(env: &mut NativeEnv) -> Primitive {
-    file_bind_mode(env, false)
+#[native("java/io/FileOutputStream", "nativeBind", library = "os")]
+fn file_bind_write(env: &mut NativeEnv, this: JObject) -> Result<(), WasmJVMError> {
+    file_bind_mode(env, this, false)
 }
 
-async_box!(async_file_read, file_read);
-async fn file_read(env: &mut NativeEnv) -> Primitive {
-    if let [this_ref, ..] = &env.variables()[..] {
-        if let Primitive::Reference(this_index) = this_ref {
-            if let Some(streams) = unsafe { &mut STREAMS } {
-                return Primitive::Int(streams.get_mut(this_index).unwrap().read());
-            }
-        }
-    }
+#[native("java/io/FileInputStream", "nativeRead", library = "os")]
+fn file_read(this: JObject) -> Result<i32, WasmJVMError> {
+    file_stream(&this)?.read()
+}
 
-    unreachable!()
+#[native("java/io/FileOutputStream", "nativeWrite", library = "os")]
+fn file_write(this: JObject, value: i32) -> Result<(), WasmJVMError> {
+    file_stream(&this)?.write(value)
 }
 
-async_box!(async_file_write, file_write);
-async fn file_write(env: &mut NativeEnv) -> Primitive {
-    if let [this_ref, value, ..] = &env.variables()[..] {
-        if let Primitive::Reference(this_index) = this_ref {
-            if let Some(streams) = unsafe { &mut STREAMS } {
-                if let Primitive::Int(value) = value {
-                    streams.get_mut(this_index).unwrap().write(*value);
-                    return Primitive::Void;
-                }
-            }
-        }
-    }
+#[native("java/util/Random", "nativeNextLong", library = "os")]
+fn random_long() -> i64 {
+    rand::random::<i64>().abs()
+}
+
+#[native("java/lang/System", "currentTimeMillis", library = "os")]
+fn current_time_millis() -> Result<i64, WasmJVMError> {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|error| WasmJVMError::InternalError(format!("Clock error: {}", error)))?;
+
+    Ok(elapsed.as_millis() as i64)
+}
 
-    unreachable!()
+#[native("java/lang/System", "nanoTime", library = "os")]
+fn nano_time() -> i64 {
+    CLOCK_ORIGIN.get_or_init(Instant::now).elapsed().as_nanos() as i64
 }
 
-async_box!(async_random_long, random_long);
-async fn random_long(_env: &mut NativeEnv) -> Primitive {
-    Primitive::Long(rand::random::<i64>().abs())
+#[native("java/lang/Runtime", "availableProcessors", library = "os")]
+fn available_processors(_this: JObject) -> i32 {
+    std::thread::available_parallelism().map_or(1, |count| count.get()) as i32
 }
@@ -1,22 +1,70 @@
 mod implementation;
 
-use implementation::register;
+use implementation::{properties, register, OS_LIBRARY, OS_PRIORITY};
 use wasmjvm_common::WasmJVMError;
-use wasmjvm_native::{Primitive, Jar};
-use wasmjvm_vm::VM;
+use std::io::{Read, Seek, SeekFrom};
+
+use wasmjvm_native::{JImage, JMod, Jar, NativeLibrary, JAVA_LIBRARY};
+use wasmjvm_vm::{RunOutcome, VM};
+
+/// A class source named on the command line, told apart by its first bytes.
+enum Source {
+    Jar(Jar<std::fs::File>),
+    JMod(JMod<std::fs::File>),
+    JImage(JImage<std::fs::File>),
+}
+
+impl Source {
+    fn open(mut file: std::fs::File) -> Result<Self, WasmJVMError> {
+        let mut magic = [0u8; 4];
+        let read = file.read(&mut magic).and_then(|_| file.seek(SeekFrom::Start(0)));
+        read.map_err(|error| WasmJVMError::LinkageError(format!("Could not read file: {}", error)))?;
+
+        match magic {
+            [0xda, 0xda, 0xfe, 0xca] | [0xca, 0xfe, 0xda, 0xda] => Ok(Source::JImage(JImage::new(file)?)),
+            [b'J', b'M', 1, 0] => Ok(Source::JMod(JMod::new(file)?)),
+            _ => Ok(Source::Jar(Jar::new(file)?)),
+        }
+    }
+}
+
+async fn vm_eval(vm: &mut VM, jars: Vec<Source>) -> Result<RunOutcome, WasmJVMError> {
+    for (key, value) in properties() {
+        vm.set_property(&key, &value)?;
+    }
+
+    for (name, value) in std::env::vars() {
+        vm.set_environment_variable(&name, &value)?;
+    }
 
-async fn vm_eval<B: 'static + std::io::Read + std::io::Seek>(vm: &mut VM, jars: Vec<Jar<B>>) -> Result<Primitive, WasmJVMError> {
     for jar in jars {
-        vm.load_jar(jar)?;
+        match jar {
+            Source::Jar(jar) => vm.load_jar(jar)?,
+            Source::JMod(jmod) => vm.load_resource(jmod)?,
+            Source::JImage(jimage) => vm.load_resource(jimage)?,
+        }
     }
 
-    vm.register_native(Box::new(register))?;
-    vm.register_native(Box::new(wasmjvm_native::register))?;
+    vm.register_library(NativeLibrary::new(
+        JAVA_LIBRARY,
+        NativeLibrary::DEFAULT_PRIORITY,
+        Box::new(wasmjvm_native::register),
+    ))?;
+    vm.register_library(NativeLibrary::new(OS_LIBRARY, OS_PRIORITY, Box::new(register)))?;
+
+    vm.boot()?;
+
+    let report = vm.native_report()?;
+    if report.missing.len() > 0 {
+        eprintln!("{}", report);
+    }
 
     vm.run().await
 }
 
-fn jars() -> Result<Vec<Jar<std::fs::File>>, WasmJVMError> {
+/// The last argument is searched first, so the boot jar goes last and a JDK `lib/modules` or
+/// `java.base.jmod` right before it.
+fn jars() -> Result<Vec<Source>, WasmJVMError> {
     let args: Vec<String> = std::env::args().skip(1).rev().collect();
 
     if args.len() == 0 {
@@ -31,13 +79,17 @@ fn jars() -> Result<Vec<Jar<std::fs::File>>, WasmJVMError> {
             return Err(WasmJVMError::LinkageError(format!("Could not find file {}.", jar_path.to_str().unwrap())));
         }
 
-        jars.push(Jar::new(std::fs::File::open(jar_path).unwrap()));
+        let file = std::fs::File::open(&jar_path)
+            .map_err(|error| WasmJVMError::LinkageError(format!("Could not open {}: {}", jar_path.display(), error)))?;
+
+        jars.push(Source::open(file)?);
     }
 
     Ok(jars)
 }
 
-async fn eval() -> () {
+/// Runs the program and returns the process exit status.
+async fn eval() -> i32 {
     match jars() {
         Ok(jars) => {
             let mut vm = VM::new();
@@ -45,25 +97,35 @@ async fn eval() -> () {
             let result = vm_eval(&mut vm, jars).await;
 
             match result {
-                Ok(_result) => {
-                    // println!("{:?}", result);
+                Ok(outcome) => {
+                    if let RunOutcome::UncaughtException { trace, .. } = &outcome {
+                        eprintln!("{}", trace);
+                    }
+
+                    outcome.exit_code()
                 }
                 Err(err) => {
                     // println!("{}", vm.heap_trace().unwrap());
-                    println!("{}", vm.stack_trace().unwrap());
-                    println!("{:?}", err);
+                    if let Ok(stack_trace) = vm.stack_trace() {
+                        println!("{}", stack_trace);
+                    }
+                    println!("{}", err);
+
+                    1
                 }
             }
         }
         Err(err) => {
-            println!("{:?}", err);
+            println!("{}", err);
+
+            1
         }
     }
 }
 
 #[tokio::main]
-async fn main() -> Result<(), ()> {
-    eval().await;
+async fn main() {
+    let status = eval().await;
 
-    Ok(())
+    std::process::exit(status);
 }
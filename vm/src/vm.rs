@@ -1,14 +1,69 @@
+use wasmjvm_class::{AccessFlagType, Descriptor, MethodRef, WithAccessFlags, WithDescriptor, WithMethods};
 use wasmjvm_common::WasmJVMError;
 use wasmjvm_native::{
-    Global, Jar, Loader, NativeInterface, Primitive, RegisterFn, RustObject, Thread, ThreadResult,
-    JAVA_LOADER, JAVA_NATIVE, JAVA_THREAD,
+    CallOutcome, Global, Jar, Loader, NativeInterface, NativeLibrary, Primitive, Resource, RustObject, Thread,
+    ThreadResult, JAVA_LOADER, JAVA_NATIVE, JAVA_THREAD,
 };
 
+const JAVA_SHUTDOWN: &str = "java/lang/Shutdown";
+
+/// How a program ended.
+#[derive(Debug, Clone)]
+pub enum RunOutcome {
+    /// Every thread finished, or the program called `System.exit` or `Runtime.halt`.
+    Exit(i32),
+    /// A thread died with an uncaught throwable. `trace` is its Java stack trace, as the JVM
+    /// prints it.
+    UncaughtException {
+        thread: String,
+        throwable: usize,
+        trace: String,
+    },
+}
+
+/// Which declared native methods have no implementation, from `VM::native_report`.
+#[derive(Debug, Clone, Default)]
+pub struct NativeReport {
+    /// How many native methods were looked at.
+    pub checked: usize,
+    pub missing: Vec<MethodRef>,
+}
+
+impl std::fmt::Display for NativeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} of {} native methods have no implementation", self.missing.len(), self.checked)?;
+
+        for method_ref in self.missing.iter() {
+            write!(
+                f,
+                "\n\t{}.{}{}",
+                method_ref.class.replace('/', "."),
+                method_ref.name,
+                method_ref.descriptor.descriptor()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl RunOutcome {
+    /// The process exit status the `java` launcher would report.
+    pub fn exit_code(self: &Self) -> i32 {
+        match self {
+            RunOutcome::Exit(status) => *status,
+            RunOutcome::UncaughtException { .. } => 1,
+        }
+    }
+}
+
 pub struct VM {
     global: Global,
-    natives: Vec<RegisterFn>,
+    libraries: Vec<NativeLibrary>,
     loader: Option<Loader>,
-    booted: bool
+    booted: bool,
+    uncaught: Option<RunOutcome>,
+    shutting_down: bool,
 }
 
 impl VM {
@@ -16,32 +71,33 @@ impl VM {
         let global = Global::new();
         Self {
             global: global.clone(),
-            natives: Vec::new(),
+            libraries: Vec::new(),
             loader: Some(Loader::new(global)),
-            booted: false
+            booted: false,
+            uncaught: None,
+            shutting_down: false,
         }
     }
 
     pub fn boot(self: &mut Self) -> Result<(), WasmJVMError> {
+        let mut loader = self.loader.take().ok_or_else(already_booted)?;
         self.booted = true;
 
-        let mut loader: Loader = self
-            .loader
-            .replace(Loader::new(self.global.clone()))
-            .unwrap();
-
         loader.boot()?;
         loader.load_class_name(JAVA_NATIVE)?;
 
         self.global
             .new_rust_instance(JAVA_LOADER, RustObject::Loader(loader))?;
 
-        let native = NativeInterface::new();
+        let mut native = NativeInterface::new();
+        for library in self.libraries.drain(..) {
+            native.add_library(library)?;
+        }
+        native.link_boot_libraries()?;
+
         self.global
             .new_rust_instance(JAVA_NATIVE, RustObject::Native(native))?;
 
-        self.register_natives()?;
-
         Ok(())
     }
 
@@ -49,28 +105,189 @@ impl VM {
         self: &mut Self,
         jar: Jar<F>,
     ) -> Result<(), WasmJVMError> {
-        let loader = self.loader.as_mut().unwrap();
+        self.pending_loader()?.load_jar(jar)
+    }
 
-        loader.load_jar(jar)?;
+    /// Loads any class source, like a `JImage` or `JMod` of the JDK's `java.base`. Load it after
+    /// the boot jar so the boot classes win.
+    pub fn load_resource<R: 'static + Resource>(self: &mut Self, resource: R) -> Result<(), WasmJVMError> {
+        self.pending_loader()?.load_resource(resource)
+    }
 
-        Ok(())
+    /// The loader that collects class sources until `boot` moves it onto the heap.
+    fn pending_loader(self: &mut Self) -> Result<&mut Loader, WasmJVMError> {
+        self.loader.as_mut().ok_or_else(already_booted)
     }
 
-    pub fn register_native(self: &mut Self, r#fn: RegisterFn) -> Result<(), WasmJVMError> {
-        self.natives.push(r#fn);
+    pub fn set_property(self: &mut Self, key: &str, value: &str) -> Result<(), WasmJVMError> {
+        self.global.set_property(key.to_string(), value.to_string())?;
 
         Ok(())
     }
 
-    pub fn register_natives(self: &mut Self) -> Result<(), WasmJVMError> {
-        while self.natives.len() > 0 {
-            let r#fn = self.natives.pop().unwrap();
-            self.global.register_native(r#fn)?;
+    pub fn set_environment_variable(self: &mut Self, name: &str, value: &str) -> Result<(), WasmJVMError> {
+        self.global.set_environment_variable(name.to_string(), value.to_string())
+    }
+
+    /// Adds a native library. Libraries have to be registered before the VM boots.
+    pub fn register_library(self: &mut Self, library: NativeLibrary) -> Result<(), WasmJVMError> {
+        if self.booted {
+            return Err(WasmJVMError::IllegalStateException(format!(
+                "Native library {} registered after boot",
+                library.name()
+            )));
         }
 
+        self.libraries.push(library);
+
         Ok(())
     }
 
+    /// The native methods of the classes loaded so far that no linked library implements.
+    pub fn native_report(self: &Self) -> Result<NativeReport, WasmJVMError> {
+        let native = self.global.native()?;
+        let mut report = NativeReport::default();
+
+        for class_index in self.global.class_indices()? {
+            let metadata = self.global.class(class_index)?.metadata();
+
+            for method in metadata.methods().into_iter().flatten() {
+                if !method.access_flags().has_type(&AccessFlagType::Native) {
+                    continue;
+                }
+
+                let method_ref = MethodRef::new(
+                    metadata.this_class().to_string(),
+                    method.name().to_string(),
+                    method.descriptor().clone(),
+                );
+
+                report.checked += 1;
+                if !native.contains(&method_ref) {
+                    report.missing.push(method_ref);
+                }
+            }
+        }
+
+        report.missing.sort_by(|first, second| {
+            (&first.class, &first.name).cmp(&(&second.class, &second.name))
+        });
+
+        Ok(report)
+    }
+
+    /// Calls a static method, initializing its class first, and runs the VM until it returns or
+    /// throws. References in the outcome may be collected once the VM runs again.
+    pub async fn invoke_static(
+        self: &mut Self,
+        method_ref: MethodRef,
+        arguments: Vec<Primitive>,
+    ) -> Result<CallOutcome, WasmJVMError> {
+        let mut thread = self.call_thread()?;
+        self.global.check_call(&method_ref, None, &arguments)?;
+        thread.invoke_static(method_ref, arguments)?;
+
+        self.call(thread).await
+    }
+
+    /// Calls an instance method on `this`, running the implementation its class selects.
+    pub async fn invoke_virtual(
+        self: &mut Self,
+        method_ref: MethodRef,
+        this: Primitive,
+        arguments: Vec<Primitive>,
+    ) -> Result<CallOutcome, WasmJVMError> {
+        let mut thread = self.call_thread()?;
+        self.global.check_call(&method_ref, Some(&this), &arguments)?;
+        thread.invoke_virtual(method_ref, this, arguments)?;
+
+        self.call(thread).await
+    }
+
+    /// Creates an instance of `class` with the constructor that takes `descriptor`'s parameters.
+    /// A constructor that returns yields the instance.
+    pub async fn new_instance(
+        self: &mut Self,
+        class: &str,
+        descriptor: Descriptor,
+        arguments: Vec<Primitive>,
+    ) -> Result<CallOutcome, WasmJVMError> {
+        let mut thread = self.call_thread()?;
+        let constructor = MethodRef::new(class.to_string(), "<init>".to_string(), descriptor);
+        self.global.check_call(&constructor, None, &arguments)?;
+        let instance = thread.invoke_constructor(class, constructor.descriptor, arguments)?;
+
+        match self.call(thread).await? {
+            CallOutcome::Return(_) => Ok(CallOutcome::Return(Primitive::Reference(instance))),
+            outcome => Ok(outcome),
+        }
+    }
+
+    pub fn new_string(self: &mut Self, string: &str) -> Result<Primitive, WasmJVMError> {
+        Ok(Primitive::Reference(self.global.new_java_string(string.to_string())?))
+    }
+
+    /// The contents of a `java/lang/String`, or `None` for `null`.
+    pub fn string(self: &Self, reference: &Primitive) -> Result<Option<String>, WasmJVMError> {
+        self.global.java_string(reference)
+    }
+
+    /// A throwable's class, message and stack trace, with its causes, as the JVM prints them.
+    pub fn throwable_trace(self: &Self, throwable: &Primitive) -> Result<String, WasmJVMError> {
+        self.global.throwable_trace(throwable)
+    }
+
+    fn call_thread(self: &mut Self) -> Result<Thread, WasmJVMError> {
+        if !self.booted {
+            self.boot()?
+        }
+
+        Ok(Thread::new(self.global.clone(), "main", 0))
+    }
+
+    /// Ticks every thread until `thread` finishes, then drops it, also when ticking fails.
+    async fn call(self: &mut Self, thread: Thread) -> Result<CallOutcome, WasmJVMError> {
+        let call_index = self
+            .global
+            .new_rust_instance(JAVA_THREAD, RustObject::Thread(thread))?;
+
+        let outcome = self.drive(call_index).await;
+        self.global.remove_thread(call_index)?;
+
+        outcome
+    }
+
+    async fn drive(self: &mut Self, call_index: usize) -> Result<CallOutcome, WasmJVMError> {
+        loop {
+            self.global.collect_if_due()?;
+
+            for thread_index in self.global.threads().clone().iter() {
+                let result = self.global.thread_tick(*thread_index).await?;
+
+                if let Some(status) = self.global.take_exit_status()? {
+                    self.stop_threads()?;
+
+                    return Ok(CallOutcome::Exit(status));
+                }
+
+                match result {
+                    ThreadResult::Continue => {}
+                    ThreadResult::Stop | ThreadResult::Result(_) if *thread_index != call_index => {}
+                    ThreadResult::Stop => return Ok(CallOutcome::Return(Primitive::Void)),
+                    ThreadResult::Result(value) => return Ok(CallOutcome::Return(value)),
+                    ThreadResult::Uncaught(throwable) if *thread_index == call_index => {
+                        return Ok(CallOutcome::Throw(throwable));
+                    }
+                    ThreadResult::Uncaught(throwable) => {
+                        if self.uncaught.is_none() {
+                            self.uncaught = Some(self.uncaught_outcome(*thread_index, throwable)?);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     pub fn stack_trace(self: &mut Self) -> Result<String, WasmJVMError> {
         let mut buffer = Vec::new();
 
@@ -85,43 +302,105 @@ impl VM {
         self.global.heap_trace()
     }
 
-    pub async fn tick(self: &mut Self) -> Result<Option<Primitive>, WasmJVMError> {
-        let mut result = Primitive::Void;
+    /// Runs one tick of every thread. Once they have all stopped the shutdown hooks get a
+    /// thread of their own, and the run is over when that one stops too.
+    pub async fn tick(self: &mut Self) -> Result<Option<RunOutcome>, WasmJVMError> {
         let mut stop = true;
 
+        self.global.collect_if_due()?;
+
         for thread_index in self.global.threads().clone().iter() {
-            match self.global.thread_tick(*thread_index).await {
-                Ok(ThreadResult::Continue) => {
+            match self.global.thread_tick(*thread_index).await? {
+                ThreadResult::Continue => {
                     stop = false;
                 }
-                Ok(ThreadResult::Stop) => {}
-                Ok(ThreadResult::Result(value)) => {
-                    result = value;
+                ThreadResult::Stop | ThreadResult::Result(_) => {}
+                ThreadResult::Uncaught(throwable) => {
+                    if self.uncaught.is_none() {
+                        self.uncaught = Some(self.uncaught_outcome(*thread_index, throwable)?);
+                    }
                 }
-                Err(err) => return Err(err),
+            }
+
+            if let Some(status) = self.global.take_exit_status()? {
+                self.stop_threads()?;
+
+                return Ok(Some(RunOutcome::Exit(status)));
             }
         }
 
-        if stop {
-            Ok(Some(result))
-        } else {
+        if !stop {
+            Ok(None)
+        } else if !self.shutting_down {
+            self.shutting_down = true;
+            self.start_shutdown()?;
+
             Ok(None)
+        } else {
+            Ok(Some(self.uncaught.take().unwrap_or(RunOutcome::Exit(0))))
+        }
+    }
+
+    fn uncaught_outcome(self: &Self, thread_index: usize, throwable: Primitive) -> Result<RunOutcome, WasmJVMError> {
+        let thread = self.global.thread(thread_index)?.name().to_string();
+        let trace = format!(
+            "Exception in thread \"{}\" {}",
+            thread,
+            self.global.throwable_trace(&throwable)?
+        );
+
+        if let Primitive::Reference(throwable) = throwable {
+            Ok(RunOutcome::UncaughtException { thread, throwable, trace })
+        } else {
+            Err(WasmJVMError::InternalError(format!("Thread \"{}\" threw {:?}", thread, throwable)))
+        }
+    }
+
+    fn start_shutdown(self: &mut Self) -> Result<(), WasmJVMError> {
+        let mut thread = Thread::new(self.global.clone(), "DestroyJavaVM", 0);
+        thread.invoke_static(
+            MethodRef::new(JAVA_SHUTDOWN.to_string(), "shutdown".to_string(), Descriptor::void()),
+            Vec::new(),
+        )?;
+
+        self.global
+            .new_rust_instance(JAVA_THREAD, RustObject::Thread(thread))?;
+
+        Ok(())
+    }
+
+    fn stop_threads(self: &mut Self) -> Result<(), WasmJVMError> {
+        for thread_index in self.global.threads() {
+            self.global.thread_mut(thread_index)?.stop();
         }
+
+        Ok(())
     }
 
-    pub async fn run(self: &mut Self) -> Result<Primitive, WasmJVMError> {
+    pub async fn run(self: &mut Self) -> Result<RunOutcome, WasmJVMError> {
         if !self.booted {
             self.boot()?
         }
 
+        self.uncaught = None;
+        self.shutting_down = false;
+
+        if self.global.main_class_index().is_err() {
+            self.global.loader_mut()?.load_main_class()?;
+        }
+
         let main_thread = Thread::new_main(self.global.clone())?;
         self.global
             .new_rust_instance(&JAVA_THREAD.to_string(), RustObject::Thread(main_thread))?;
 
         loop {
-            if let Some(data) = self.tick().await? {
-                return Ok(data);
+            if let Some(outcome) = self.tick().await? {
+                return Ok(outcome);
             }
         }
     }
 }
+
+fn already_booted() -> WasmJVMError {
+    WasmJVMError::IllegalStateException(format!("Class sources have to be loaded before the VM boots"))
+}
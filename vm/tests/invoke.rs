@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use wasmjvm_class::{Descriptor, MethodRef};
+use wasmjvm_native::{CallOutcome, Jar, NativeLibrary, Primitive, JAVA_LIBRARY};
+use wasmjvm_vm::VM;
+
+/// A booted VM with the boot jar, or `None` when `cargo run -p wasmjvm_java` hasn't built it.
+fn vm() -> Option<VM> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../java/dist/Boot.jar");
+    let file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(..) => {
+            eprintln!("skipping, {} is missing", path.display());
+            return None;
+        }
+    };
+
+    let mut vm = VM::new();
+    vm.load_jar(Jar::new(file).unwrap()).unwrap();
+    vm.register_library(NativeLibrary::new(
+        JAVA_LIBRARY,
+        NativeLibrary::DEFAULT_PRIORITY,
+        Box::new(wasmjvm_native::register),
+    ))
+    .unwrap();
+    vm.boot().unwrap();
+
+    Some(vm)
+}
+
+fn method(class: &str, name: &str, descriptor: &str) -> MethodRef {
+    MethodRef::new(class.to_string(), name.to_string(), Descriptor::from_str(descriptor).unwrap())
+}
+
+fn parse_int() -> MethodRef {
+    method("java/lang/Integer", "parseInt", "(Ljava/lang/String;I)I")
+}
+
+#[tokio::test]
+async fn invoke_returns() {
+    let mut vm = match vm() {
+        Some(vm) => vm,
+        None => return,
+    };
+
+    let text = vm.new_string("42").unwrap();
+    let outcome = vm.invoke_static(parse_int(), vec![text, Primitive::Int(10)]).await.unwrap();
+    assert!(matches!(outcome, CallOutcome::Return(Primitive::Int(42))), "{:?}", outcome);
+
+    let builder = match vm
+        .new_instance("java/lang/StringBuilder", Descriptor::void(), vec![])
+        .await
+        .unwrap()
+    {
+        CallOutcome::Return(builder) => builder,
+        outcome => panic!("{:?}", outcome),
+    };
+
+    let append = method("java/lang/StringBuilder", "append", "(Ljava/lang/String;)Ljava/lang/StringBuilder;");
+    let greeting = vm.new_string("Hello").unwrap();
+    let outcome = vm.invoke_virtual(append, builder.clone(), vec![greeting]).await.unwrap();
+    assert!(matches!(&outcome, CallOutcome::Return(Primitive::Reference(_))), "{:?}", outcome);
+
+    let to_string = method("java/lang/Object", "toString", "()Ljava/lang/String;");
+    match vm.invoke_virtual(to_string, builder, vec![]).await.unwrap() {
+        CallOutcome::Return(string) => assert_eq!(vm.string(&string).unwrap().as_deref(), Some("Hello")),
+        outcome => panic!("{:?}", outcome),
+    }
+}
+
+#[tokio::test]
+async fn invoke_throws() {
+    let mut vm = match vm() {
+        Some(vm) => vm,
+        None => return,
+    };
+
+    let text = vm.new_string("forty-two").unwrap();
+    match vm.invoke_static(parse_int(), vec![text, Primitive::Int(10)]).await.unwrap() {
+        CallOutcome::Throw(throwable) => {
+            let trace = vm.throwable_trace(&throwable).unwrap();
+            assert!(trace.starts_with("java.lang.NumberFormatException"), "{}", trace);
+        }
+        outcome => panic!("{:?}", outcome),
+    }
+}
+
+#[tokio::test]
+async fn invoke_after_failed_call() {
+    let mut vm = match vm() {
+        Some(vm) => vm,
+        None => return,
+    };
+
+    assert!(vm.invoke_static(parse_int(), vec![Primitive::Int(5), Primitive::Int(10)]).await.is_err());
+    assert!(vm.invoke_static(parse_int(), vec![]).await.is_err());
+
+    let missing = method("java/lang/Integer", "parseInt", "(Ljava/lang/String;J)I");
+    let text = vm.new_string("7").unwrap();
+    assert!(vm.invoke_static(missing, vec![text, Primitive::Long(10)]).await.is_err());
+
+    // Only the os library implements it, so the call fails while the VM runs it.
+    let millis = method("java/lang/System", "currentTimeMillis", "()J");
+    assert!(vm.invoke_static(millis, vec![]).await.is_err());
+
+    let text = vm.new_string("7").unwrap();
+    let outcome = vm.invoke_static(parse_int(), vec![text, Primitive::Int(10)]).await.unwrap();
+    assert!(matches!(outcome, CallOutcome::Return(Primitive::Int(7))), "{:?}", outcome);
+}
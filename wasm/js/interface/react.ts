@@ -108,7 +108,7 @@ export default class ReactInterface {
     }
 
     public static bind(pointer: number, path: string) {
-        if(path == "<sys>") {
+        if(path == "<sys>" || path == "<err>") {
             this.streams[pointer] = new SystemStream();
         } else {
             this.streams[pointer] = new FileStream(path);
@@ -47,6 +47,31 @@ export default class RustInterface {
         return BigInt(Math.floor(Math.random() * RustInterface.MAX_INT));
     }
 
+    public static current_time_millis(): number {
+        return Date.now();
+    }
+
+    public static nano_time(): number {
+        return Math.round(performance.now() * 1000000);
+    }
+
+    public static available_processors(): number {
+        return navigator.hardwareConcurrency || 1;
+    }
+
+    public static properties(): [string, string][] {
+        return [
+            ["os.name", navigator.platform || "JavaScript"],
+            ["os.arch", "wasm32"],
+            ["user.name", "web"],
+            ["user.language", navigator.language || "en"]
+        ];
+    }
+
+    public static environment(): [string, string][] {
+        return [];
+    }
+
     public static error(message: string): void {
         console.error(message);
     }
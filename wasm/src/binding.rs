@@ -19,4 +19,19 @@ extern "C" {
 
     #[wasm_bindgen(static_method_of=JS, js_class="RustInterface")]
     pub fn random() -> i64;
+
+    #[wasm_bindgen(static_method_of=JS, js_class="RustInterface")]
+    pub fn current_time_millis() -> f64;
+
+    #[wasm_bindgen(static_method_of=JS, js_class="RustInterface")]
+    pub fn nano_time() -> f64;
+
+    #[wasm_bindgen(static_method_of=JS, js_class="RustInterface")]
+    pub fn available_processors() -> i32;
+
+    #[wasm_bindgen(static_method_of=JS, js_class="RustInterface")]
+    pub fn properties() -> js_sys::Array;
+
+    #[wasm_bindgen(static_method_of=JS, js_class="RustInterface")]
+    pub fn environment() -> js_sys::Array;
 }
@@ -1,7 +1,13 @@
 use crate::binding::JS;
 
 use wasmjvm_class::{Descriptor, MethodRef, SingleType, Type};
-use wasmjvm_native::{Primitive, NativeEnv, NativeInterface, register_method, async_box, RustObject};
+use wasmjvm_common::WasmJVMError;
+use wasmjvm_native::{Primitive, NativeEnv, NativeInterface, register_method, async_box};
+
+/// The browser's natives: files, clocks and randomness through the JavaScript bindings. They
+/// outrank the VM's own natives.
+pub const WASM_LIBRARY: &str = "wasm";
+pub const WASM_PRIORITY: i32 = 10;
 
 pub fn register(interface: &mut NativeInterface) {
     register_method!(
@@ -50,53 +56,113 @@ pub fn register(interface: &mut NativeInterface) {
         vec![],
         Type::Single(SingleType::Long)
     );
+
+    register_method!(
+        interface,
+        async_current_time_millis,
+        "java/lang/System",
+        "currentTimeMillis",
+        vec![],
+        Type::Single(SingleType::Long)
+    );
+
+    register_method!(
+        interface,
+        async_nano_time,
+        "java/lang/System",
+        "nanoTime",
+        vec![],
+        Type::Single(SingleType::Long)
+    );
+
+    register_method!(
+        interface,
+        async_available_processors,
+        "java/lang/Runtime",
+        "availableProcessors",
+        vec![],
+        Type::Single(SingleType::Int)
+    );
+}
+
+/// Reads the `[key, value]` pairs the JS host hands over for properties and the environment.
+fn pairs(entries: js_sys::Array) -> Vec<(String, String)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let entry = js_sys::Array::from(&entry);
+
+            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+        })
+        .collect()
+}
+
+pub fn properties() -> Vec<(String, String)> {
+    pairs(JS::properties())
+}
+
+pub fn environment() -> Vec<(String, String)> {
+    pairs(JS::environment())
 }
 
 async_box!(async_file_bind, file_bind);
-async fn file_bind(env: &mut NativeEnv) -> Primitive {
+async fn file_bind(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
     if let [this_ref, ..] = &env.variables()[..] {
         if let Primitive::Reference(this_index) = this_ref {
-            let this = env.reference(&this_ref).unwrap();
-            let path_ref = this.fields.get("path").unwrap();
-            let path_object = env.reference(&path_ref).unwrap();
-            if let RustObject::String(path) = path_object.inner() {
+            let path_ref = env.field(this_ref, "path")?;
+            if let Some(path) = env.string(&path_ref)? {
                 JS::file_bind(*this_index, path.clone());
 
-                return Primitive::Void;
+                return Ok(Primitive::Void);
             }
         }
     }
 
-    unreachable!();
+    Err(env.invalid_arguments())
 }
 
 async_box!(async_file_read, file_read);
-async fn file_read(env: &mut NativeEnv) -> Primitive {
-    if let [this_ref, ..] = &env.variables()[..] {
-        if let Primitive::Reference(this_index) = this_ref {
-            return Primitive::Int(JS::file_read(*this_index).await.as_f64().unwrap() as i32);
+async fn file_read(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(this_index), ..] = &env.variables()[..] {
+        let value = JS::file_read(*this_index).await;
+
+        if let Some(value) = value.as_f64() {
+            Ok(Primitive::Int(value as i32))
+        } else {
+            Err(WasmJVMError::InternalError(format!("File read returned {:?}", value)))
         }
+    } else {
+        Err(env.invalid_arguments())
     }
-
-    unreachable!()
 }
 
 async_box!(async_file_write, file_write);
-async fn file_write(env: &mut NativeEnv) -> Primitive {
-    if let [this_ref, value, ..] = &env.variables()[..] {
-        if let Primitive::Reference(this_index) = this_ref {
-                if let Primitive::Int(value) = value {
-                    JS::file_write(*this_index, *value);
+async fn file_write(env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    if let [Primitive::Reference(this_index), Primitive::Int(value), ..] = &env.variables()[..] {
+        JS::file_write(*this_index, *value);
 
-                    return Primitive::Void;
-                }
-        }
+        Ok(Primitive::Void)
+    } else {
+        Err(env.invalid_arguments())
     }
-
-    unreachable!()
 }
 
 async_box!(async_random_long, random_long);
-async fn random_long(_env: &mut NativeEnv) -> Primitive {
-    Primitive::Long(JS::random())
-}
\ No newline at end of file
+async fn random_long(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Long(JS::random()))
+}
+
+async_box!(async_current_time_millis, current_time_millis);
+async fn current_time_millis(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Long(JS::current_time_millis() as i64))
+}
+
+async_box!(async_nano_time, nano_time);
+async fn nano_time(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Long(JS::nano_time() as i64))
+}
+
+async_box!(async_available_processors, available_processors);
+async fn available_processors(_env: &mut NativeEnv) -> Result<Primitive, WasmJVMError> {
+    Ok(Primitive::Int(JS::available_processors().max(1)))
+}
@@ -4,10 +4,10 @@ mod implementation;
 use binding::JS;
 use wasm_bindgen::prelude::*;
 use wasmjvm_common::WasmJVMError;
-use wasmjvm_native::{Jar, Primitive};
-use wasmjvm_vm::VM;
+use wasmjvm_native::{Jar, NativeLibrary, JAVA_LIBRARY};
+use wasmjvm_vm::{RunOutcome, VM};
 
-use crate::implementation::register;
+use crate::implementation::{environment, properties, register, WASM_LIBRARY, WASM_PRIORITY};
 
 static mut STATIC_VM: Option<VM> = None;
 
@@ -16,8 +16,20 @@ fn check_vm() -> Result<(), WasmJVMError> {
         if STATIC_VM.is_none() {
             let mut vm = VM::new();
 
-            vm.register_native(Box::new(wasmjvm_native::register))?;
-            vm.register_native(Box::new(register))?;
+            for (key, value) in properties() {
+                vm.set_property(&key, &value)?;
+            }
+
+            for (name, value) in environment() {
+                vm.set_environment_variable(&name, &value)?;
+            }
+
+            vm.register_library(NativeLibrary::new(
+                JAVA_LIBRARY,
+                NativeLibrary::DEFAULT_PRIORITY,
+                Box::new(wasmjvm_native::register),
+            ))?;
+            vm.register_library(NativeLibrary::new(WASM_LIBRARY, WASM_PRIORITY, Box::new(register)))?;
 
             STATIC_VM = Some(vm);
         }
@@ -29,14 +41,18 @@ fn check_vm() -> Result<(), WasmJVMError> {
 #[wasm_bindgen]
 pub async fn run() -> JsValue {
     match run_inner().await {
-        Ok(result) => {
-            JsValue::from_str(format!("{:?}", result).as_str())    
+        Ok(RunOutcome::UncaughtException { trace, .. }) => {
+            JS::error(trace.clone());
+            JsValue::from_str(trace.as_str())
+        }
+        Ok(outcome) => {
+            JsValue::from_str(format!("{:?}", outcome).as_str())
         },
-        Err(err) => JsValue::from_str(format!("Error: {:?}", err).as_str()),
+        Err(err) => JsValue::from_str(format!("Error: {}", err).as_str()),
     }
 }
 
-pub async fn run_inner() -> Result<Primitive, WasmJVMError> {
+pub async fn run_inner() -> Result<RunOutcome, WasmJVMError> {
     check_vm()?;
 
     let vm = unsafe { STATIC_VM.as_mut().unwrap() };
@@ -50,7 +66,7 @@ pub fn load_jar(jar: Vec<u8>) -> JsValue {
 
     let jar = std::io::Cursor::new(jar);
 
-    match inner_load_jar(Jar::new(jar)) {
+    match Jar::new(jar).and_then(inner_load_jar) {
         Ok(string) => JsValue::from_str(format!("{}", string).as_str()),
         Err(err) => {
             JS::error(format!("{:?}", err));